@@ -0,0 +1,528 @@
+use std::time::Instant;
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::types::computation::model::{ComputationGraph, ImplicitModel};
+use crate::types::computation::traits::ModelFloat;
+use crate::types::computation::ModelError;
+use crate::types::geometry::{Plane, Vec2, Vec3};
+
+/// Polyline along an iso value of a model in a plane.
+#[derive(Debug, Clone)]
+pub struct IsoLine<T> {
+    /// Points along the line. The first point isn't repeated for closed lines.
+    pub points: Vec<Vec3<T>>,
+    /// Whether the line is a closed loop. Open lines end at the border of the sampled region.
+    pub closed: bool,
+}
+
+/// Values of a model sampled on a regular grid in a plane.
+///
+/// The in-plane axes are given by [`Vec3::orthonormal_basis`] of the plane normal,
+/// so for the global XY plane these are the global x and y axes.
+#[derive(Debug, Clone)]
+pub struct PlaneSection<T> {
+    plane: Plane<T>,
+    u_axis: Vec3<T>,
+    v_axis: Vec3<T>,
+    min: Vec2<T>,
+    cell_size: T,
+    nx: usize,
+    ny: usize,
+    values: Vec<T>,
+}
+
+impl<T: ModelFloat + 'static> PlaneSection<T> {
+    /// Sample a model on a grid in a plane. The points are evaluated in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model to sample.
+    /// * `output` - The tag of the component to evaluate.
+    /// * `plane` - The plane to sample in.
+    /// * `min` - Minimum of the sampled region, in plane coordinates relative to the plane origin.
+    /// * `max` - Maximum of the sampled region, in plane coordinates relative to the plane origin.
+    /// * `cell_size` - The distance between the sampled points.
+    pub fn sample(
+        model: &ImplicitModel<T>,
+        output: &str,
+        plane: &Plane<T>,
+        min: Vec2<T>,
+        max: Vec2<T>,
+        cell_size: T,
+    ) -> Result<Self, ModelError> {
+        let before = Instant::now();
+        let graph = model.compile(output)?;
+        let section = Self::sample_graph(&graph, plane, min, max, cell_size)?;
+
+        log::info!(
+            "Plane section with {} points sampled in {:.2?}",
+            section.nx * section.ny,
+            before.elapsed()
+        );
+
+        Ok(section)
+    }
+
+    /// Sample a compiled model on a grid in a plane.
+    pub(crate) fn sample_graph(
+        graph: &ComputationGraph<'_, T>,
+        plane: &Plane<T>,
+        min: Vec2<T>,
+        max: Vec2<T>,
+        cell_size: T,
+    ) -> Result<Self, ModelError> {
+        if cell_size <= T::zero() || cell_size.is_nan() {
+            return Err(ModelError::Custom(
+                "The cell size must be larger than zero.".to_string(),
+            ));
+        }
+        if max.x < min.x || max.y < min.y {
+            return Err(ModelError::Custom(
+                "The maximum of the region must be larger than the minimum.".to_string(),
+            ));
+        }
+        let count = |length: T| (length / cell_size).floor().to_usize().unwrap_or(0) + 1;
+        let (nx, ny) = (count(max.x - min.x), count(max.y - min.y));
+        let (u_axis, v_axis) = plane.normal().orthonormal_basis();
+
+        let mut section = Self {
+            plane: *plane,
+            u_axis,
+            v_axis,
+            min,
+            cell_size,
+            nx,
+            ny,
+            values: Vec::new(),
+        };
+        section.values = (0..ny)
+            .into_par_iter()
+            .flat_map_iter(|j| {
+                let section = &section;
+                (0..nx).map(move |i| {
+                    let point = section.point_at(i, j);
+                    graph.evaluate_at_coord(point.x, point.y, point.z)
+                })
+            })
+            .collect();
+
+        Ok(section)
+    }
+}
+
+impl<T: Float> PlaneSection<T> {
+    /// The plane of the section.
+    pub fn plane(&self) -> &Plane<T> {
+        &self.plane
+    }
+
+    /// The in-plane axes of the section.
+    pub fn axes(&self) -> (Vec3<T>, Vec3<T>) {
+        (self.u_axis, self.v_axis)
+    }
+
+    /// The distance between the sampled points.
+    pub fn cell_size(&self) -> T {
+        self.cell_size
+    }
+
+    /// The number of sampled points along each in-plane axis.
+    pub fn num_points(&self) -> (usize, usize) {
+        (self.nx, self.ny)
+    }
+
+    /// The sampled values, ordered by the first in-plane axis, then the second.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The sampled value at a grid index.
+    pub fn value(&self, i: usize, j: usize) -> T {
+        self.values[i + self.nx * j]
+    }
+
+    /// Point in space at a grid index.
+    pub fn point_at(&self, i: usize, j: usize) -> Vec3<T> {
+        self.to_space(Vec2::new(
+            self.min.x + self.cell_size * T::from(i).unwrap(),
+            self.min.y + self.cell_size * T::from(j).unwrap(),
+        ))
+    }
+
+    /// Convert plane coordinates relative to the plane origin into a point in space.
+    pub fn to_space(&self, point: Vec2<T>) -> Vec3<T> {
+        self.plane.origin() + self.u_axis * point.x + self.v_axis * point.y
+    }
+
+    /// Extract the iso-lines of the section using the marching squares algorithm.
+    ///
+    /// Values below the iso value are inside. Seen from the side the plane normal points to, the lines have the
+    /// inside on their left, so loops around inside regions are counter-clockwise and loops around holes are clockwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `iso_val` - The target iso value.
+    pub fn iso_lines(&self, iso_val: T) -> Vec<IsoLine<T>> {
+        grid_iso_lines(&self.values, self.nx, self.ny, iso_val)
+            .into_iter()
+            .map(|line| IsoLine {
+                points: line
+                    .points
+                    .iter()
+                    .map(|p| {
+                        self.to_space(Vec2::new(
+                            self.min.x + p.x * self.cell_size,
+                            self.min.y + p.y * self.cell_size,
+                        ))
+                    })
+                    .collect(),
+                closed: line.closed,
+            })
+            .collect()
+    }
+}
+
+/// Generate iso-lines of a model in a plane using the marching squares algorithm.
+///
+/// This is the planar counterpart of [`generate_iso_surface`](super::marching_cubes::generate_iso_surface),
+/// e.g. for section views. See [`PlaneSection::iso_lines`] for the orientation of the lines.
+///
+/// # Arguments
+///
+/// * `model` - The model to contour.
+/// * `output` - The tag of the component to evaluate.
+/// * `plane` - The plane to contour in.
+/// * `min` - Minimum of the sampled region, in plane coordinates relative to the plane origin.
+/// * `max` - Maximum of the sampled region, in plane coordinates relative to the plane origin.
+/// * `cell_size` - The distance between the sampled points.
+/// * `iso_val` - The target iso value.
+pub fn generate_iso_lines<T: ModelFloat + 'static>(
+    model: &ImplicitModel<T>,
+    output: &str,
+    plane: &Plane<T>,
+    min: Vec2<T>,
+    max: Vec2<T>,
+    cell_size: T,
+    iso_val: T,
+) -> Result<Vec<IsoLine<T>>, ModelError> {
+    let section = PlaneSection::sample(model, output, plane, min, max, cell_size)?;
+    Ok(section.iso_lines(iso_val))
+}
+
+/// Polyline extracted from a grid of values, in grid coordinates.
+///
+/// A point at `(x, y)` lies between the grid points `(floor(x), floor(y))` and `(ceil(x), ceil(y))`.
+#[derive(Debug, Clone)]
+pub(crate) struct GridIsoLine<T> {
+    /// Points along the line. The first point isn't repeated for closed lines.
+    pub points: Vec<Vec2<T>>,
+    /// Whether the line is a closed loop.
+    pub closed: bool,
+}
+
+/// Extract the iso-lines of a grid of values using the marching squares algorithm.
+///
+/// Values below the iso value are inside. The lines are oriented with the inside on their left,
+/// so loops around inside regions are counter-clockwise and loops around holes are clockwise.
+/// Ambiguous cells are resolved with the average of the four corners.
+///
+/// # Arguments
+///
+/// * `values` - Values of the grid, ordered by x, then y.
+/// * `nx` - Number of points along x.
+/// * `ny` - Number of points along y.
+/// * `iso_val` - The target iso value.
+pub(crate) fn grid_iso_lines<T: Float>(
+    values: &[T],
+    nx: usize,
+    ny: usize,
+    iso_val: T,
+) -> Vec<GridIsoLine<T>> {
+    assert_eq!(values.len(), nx * ny, "Grid size doesn't match the values.");
+    if nx < 2 || ny < 2 {
+        return Vec::new();
+    }
+
+    let value = |i: usize, j: usize| values[i + nx * j];
+    let inside = |i: usize, j: usize| value(i, j) < iso_val;
+    let num_horizontal = (nx - 1) * ny;
+
+    // Edges are identified by an index, with the horizontal edges first.
+    let horizontal = |i: usize, j: usize| i + (nx - 1) * j;
+    let vertical = |i: usize, j: usize| num_horizontal + i + nx * j;
+    let mut next = vec![usize::MAX; num_horizontal + nx * (ny - 1)];
+    let mut previous = vec![usize::MAX; next.len()];
+
+    for j in 0..ny - 1 {
+        for i in 0..nx - 1 {
+            // Corners and edges in counter-clockwise order, edge e going from corner e to corner e + 1.
+            let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+            let edges = [
+                horizontal(i, j),
+                vertical(i + 1, j),
+                horizontal(i, j + 1),
+                vertical(i, j),
+            ];
+            let is_inside = corners.map(|(ci, cj)| inside(ci, cj));
+            let crossings = (0..4)
+                .filter(|&e| is_inside[e] != is_inside[(e + 1) % 4])
+                .count();
+            if crossings == 0 {
+                continue;
+            }
+
+            let center_inside = crossings == 4 && {
+                let sum = corners
+                    .iter()
+                    .fold(T::zero(), |sum, &(ci, cj)| sum + value(ci, cj));
+                sum / T::from(4).unwrap() < iso_val
+            };
+
+            // A segment starts where the boundary leaves the inside region, and ends where it enters it again.
+            for e in 0..4 {
+                if !is_inside[e] || is_inside[(e + 1) % 4] {
+                    continue;
+                }
+                let end = if crossings == 2 {
+                    (1..4)
+                        .map(|offset| (e + offset) % 4)
+                        .find(|&o| !is_inside[o] && is_inside[(o + 1) % 4])
+                        .unwrap()
+                } else if center_inside {
+                    (e + 1) % 4
+                } else {
+                    (e + 3) % 4
+                };
+                next[edges[e]] = edges[end];
+                previous[edges[end]] = edges[e];
+            }
+        }
+    }
+
+    let point = |edge: usize| {
+        let ((ai, aj), (bi, bj)) = if edge < num_horizontal {
+            let (i, j) = (edge % (nx - 1), edge / (nx - 1));
+            ((i, j), (i + 1, j))
+        } else {
+            let index = edge - num_horizontal;
+            let (i, j) = (index % nx, index / nx);
+            ((i, j), (i, j + 1))
+        };
+        let (va, vb) = (value(ai, aj), value(bi, bj));
+        let t = ((iso_val - va) / (vb - va)).max(T::zero()).min(T::one());
+        let to_t = |index: usize| T::from(index).unwrap();
+        Vec2::new(
+            to_t(ai) + (to_t(bi) - to_t(ai)) * t,
+            to_t(aj) + (to_t(bj) - to_t(aj)) * t,
+        )
+    };
+
+    let mut lines = Vec::new();
+    let mut visited = vec![false; next.len()];
+    let follow = |start: usize, visited: &mut [bool]| {
+        let mut edges = vec![start];
+        visited[start] = true;
+        let mut current = start;
+        while next[current] != usize::MAX && !visited[next[current]] {
+            current = next[current];
+            visited[current] = true;
+            edges.push(current);
+        }
+        (edges, next[current] == start)
+    };
+
+    // Open lines start at the boundary of the grid, where no segment ends.
+    for start in 0..next.len() {
+        if next[start] != usize::MAX && previous[start] == usize::MAX {
+            let (edges, _) = follow(start, &mut visited);
+            lines.push(GridIsoLine {
+                points: edges.into_iter().map(point).collect(),
+                closed: false,
+            });
+        }
+    }
+    for start in 0..next.len() {
+        if next[start] != usize::MAX && !visited[start] {
+            let (edges, closed) = follow(start, &mut visited);
+            lines.push(GridIsoLine {
+                points: edges.into_iter().map(point).collect(),
+                closed,
+            });
+        }
+    }
+
+    lines
+}
+
+/// Signed area of a closed polygon, positive for counter-clockwise polygons.
+pub(crate) fn signed_area<T: Float>(points: impl Iterator<Item = (T, T)> + Clone) -> T {
+    let mut shifted = points.clone().cycle().skip(1);
+    let twice_area = points.fold(T::zero(), |sum, (x0, y0)| {
+        let (x1, y1) = shifted.next().unwrap();
+        sum + x0 * y1 - x1 * y0
+    });
+    twice_area / T::from(2).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::geometry::Sphere;
+
+    fn area(line: &GridIsoLine<f64>) -> f64 {
+        signed_area(line.points.iter().map(|p| (p.x, p.y)))
+    }
+
+    #[test]
+    fn test_circle_and_hole() {
+        // A ring, with values below zero between radius 2 and 4.
+        let n = 11;
+        let mut values = Vec::new();
+        for j in 0..n {
+            for i in 0..n {
+                let r = ((i as f64 - 5.0).powi(2) + (j as f64 - 5.0).powi(2)).sqrt();
+                values.push((r - 3.0).abs() - 1.0);
+            }
+        }
+        let lines = grid_iso_lines(&values, n, n, 0.0);
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.closed));
+        let mut areas: Vec<f64> = lines.iter().map(area).collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // The hole is clockwise, the outer boundary counter-clockwise.
+        assert!((areas[0] + std::f64::consts::PI * 4.0).abs() < 1.0);
+        assert!((areas[1] - std::f64::consts::PI * 16.0).abs() < 1.0);
+        for line in &lines {
+            for p in &line.points {
+                let r = ((p.x - 5.0).powi(2) + (p.y - 5.0).powi(2)).sqrt();
+                assert!((r - 2.0).abs() < 0.1 || (r - 4.0).abs() < 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_line_orientation() {
+        // Values increase with x, so the inside is on the left when walking in +y.
+        let values: Vec<f64> = (0..12).map(|i| (i % 4) as f64 - 1.5).collect();
+        let lines = grid_iso_lines(&values, 4, 3, 0.0);
+
+        assert_eq!(lines.len(), 1);
+        assert!(!lines[0].closed);
+        let points = &lines[0].points;
+        assert_eq!(points.len(), 3);
+        assert!(points.iter().all(|p| (p.x - 1.5).abs() < 1E-12));
+        assert!(points[0].y < points[2].y);
+    }
+
+    #[test]
+    fn test_saddle() {
+        // Corners (0, 0) and (1, 1) are inside.
+        let corner = |line: &GridIsoLine<f64>| {
+            let x = (line.points[0].x + line.points[1].x) / 2.0;
+            let y = (line.points[0].y + line.points[1].y) / 2.0;
+            (x.round() as usize, y.round() as usize)
+        };
+
+        // With an outside center, the segments cut off the inside corners.
+        let lines = grid_iso_lines(&[-1.0, 1.0, 1.0, -1.0], 2, 2, 0.0);
+        let mut corners: Vec<_> = lines.iter().map(corner).collect();
+        corners.sort();
+        assert_eq!(corners, vec![(0, 0), (1, 1)]);
+
+        // With an inside center, the segments cut off the outside corners.
+        let lines = grid_iso_lines(&[-3.0, 1.0, 1.0, -3.0], 2, 2, 0.0);
+        let mut corners: Vec<_> = lines.iter().map(corner).collect();
+        corners.sort();
+        assert_eq!(corners, vec![(0, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn test_iso_lines_on_tilted_plane() {
+        let mut model = ImplicitModel::new();
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        model
+            .add_function("Sphere", Sphere::new(center, 5.0))
+            .unwrap();
+        // The plane passes 3 units from the center, so the section is a circle with radius 4.
+        let normal = Vec3::new(1.0, 1.0, 1.0).normalize();
+        let plane = Plane::new(center + normal * 3.0, normal);
+        let lines = generate_iso_lines(
+            &model,
+            "Sphere",
+            &plane,
+            Vec2::new(-6.0, -6.0),
+            Vec2::new(6.0, 6.0),
+            0.25,
+            0.0,
+        )
+        .unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].closed);
+        let (u, v) = plane.normal().orthonormal_basis();
+        let origin = plane.origin();
+        let in_plane: Vec<(f64, f64)> = lines[0]
+            .points
+            .iter()
+            .map(|p| ((*p - origin).dot(&u), (*p - origin).dot(&v)))
+            .collect();
+        // Counter-clockwise around the inside.
+        let area = signed_area(in_plane.iter().copied());
+        assert!((area - std::f64::consts::PI * 16.0).abs() < 0.5);
+        for point in &lines[0].points {
+            assert!(plane.signed_distance_coord(point.x, point.y, point.z).abs() < 1E-9);
+            assert!((point.distance_to_vec3(&center) - 5.0).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_plane_section() {
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::origin(), 2.0))
+            .unwrap();
+        let section = PlaneSection::sample(
+            &model,
+            "Sphere",
+            &Plane::xy(),
+            Vec2::new(0.0, -1.0),
+            Vec2::new(4.0, 1.0),
+            0.5,
+        )
+        .unwrap();
+
+        assert_eq!(section.num_points(), (9, 5));
+        assert_eq!(section.values().len(), 45);
+        // The XY plane uses the global x and y axes.
+        let point = section.point_at(2, 4);
+        assert!(point.distance_to_vec3(&Vec3::new(1.0, 1.0, 0.0)) < 1E-12);
+        assert!((section.value(0, 2) + 2.0).abs() < 1E-12);
+
+        // The region cuts the circle, so the line is open and ends at the borders.
+        let lines = section.iso_lines(0.0);
+        assert_eq!(lines.len(), 1);
+        assert!(!lines[0].closed);
+        let first = lines[0].points.first().unwrap();
+        let last = lines[0].points.last().unwrap();
+        // Walking with the inside on the left goes from y = -1 to y = 1.
+        assert!((first.y + 1.0).abs() < 1E-12 && (last.y - 1.0).abs() < 1E-12);
+    }
+
+    #[test]
+    fn test_plane_section_invalid_arguments() {
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::origin(), 2.0))
+            .unwrap();
+        let sample = |min: Vec2<f64>, max: Vec2<f64>, cell_size: f64| {
+            PlaneSection::sample(&model, "Sphere", &Plane::xy(), min, max, cell_size)
+        };
+
+        assert!(sample(Vec2::new(-1.0, -1.0), Vec2::new(1.0, 1.0), 0.0).is_err());
+        assert!(sample(Vec2::new(-1.0, -1.0), Vec2::new(1.0, 1.0), -0.5).is_err());
+        assert!(sample(Vec2::new(1.0, -1.0), Vec2::new(-1.0, 1.0), 0.5).is_err());
+    }
+}
@@ -0,0 +1,325 @@
+use std::time::Instant;
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::types::computation::data::DenseField;
+use crate::types::computation::model::ImplicitModel;
+use crate::types::computation::traits::ModelFloat;
+use crate::types::computation::ModelError;
+use crate::types::geometry::{BoundingBox, Plane, Vec2, Vec3};
+
+use super::marching_squares::{grid_iso_lines, PlaneSection};
+
+/// Closed contours of a model at a constant z coordinate.
+#[derive(Debug, Clone)]
+pub struct SliceLayer<T> {
+    /// Height at which the layer is sampled, in the middle of the layer.
+    pub z: T,
+    /// Thickness of the layer, which is centered on the sampling height.
+    pub thickness: T,
+    /// Closed contours, without repeating the first point at the end.
+    ///
+    /// Outer contours are counter-clockwise seen from above, and contours around holes are clockwise.
+    pub contours: Vec<Vec<Vec3<T>>>,
+}
+
+impl<T: Float> SliceLayer<T> {
+    /// Height of the bottom of the layer.
+    pub fn bottom(&self) -> T {
+        self.z - self.thickness / T::from(2).unwrap()
+    }
+
+    /// Height of the top of the layer, which is the layer height used by build processors.
+    pub fn top(&self) -> T {
+        self.z + self.thickness / T::from(2).unwrap()
+    }
+}
+
+/// Slice a sampled field with planes along z, and extract the contours of each layer using marching squares.
+///
+/// The layers are placed in the middle of each layer height, starting at the bottom of the field.
+/// Values between the sampled points are interpolated linearly in z.
+/// The field is closed at its borders, so the contours are always closed.
+///
+/// # Arguments
+///
+/// * `field` - The field to slice.
+/// * `iso_val` - The iso value of the surface, with lower values inside.
+/// * `layer_height` - The distance between layers.
+pub fn slice_field<T: Float + Send + Sync>(
+    field: &DenseField<T>,
+    iso_val: T,
+    layer_height: T,
+) -> Result<Vec<SliceLayer<T>>, ModelError> {
+    let before = Instant::now();
+    let n = field.num_points_per_axis();
+    let origin = *field.origin();
+    let cell_size = field.cell_size();
+    let height = cell_size * T::from(n.k - 1).unwrap();
+
+    let layers: Vec<SliceLayer<T>> = layer_heights(origin.z, origin.z + height, layer_height)?
+        .into_par_iter()
+        .map(|z| {
+            let local = ((z - origin.z) / cell_size).max(T::zero());
+            let k = local
+                .floor()
+                .to_usize()
+                .unwrap_or(0)
+                .min(n.k.saturating_sub(2));
+            let t = local - T::from(k).unwrap();
+            let layer_size = n.i * n.j;
+            let upper_k = (k + 1).min(n.k - 1);
+            let lower = &field.data()[k * layer_size..(k + 1) * layer_size];
+            let upper = &field.data()[upper_k * layer_size..(upper_k + 1) * layer_size];
+            let values: Vec<T> = lower
+                .iter()
+                .zip(upper)
+                .map(|(&a, &b)| a + (b - a) * t)
+                .collect();
+            SliceLayer {
+                z,
+                thickness: layer_height,
+                contours: layer_contours(&values, n.i, n.j, origin, cell_size, z, iso_val),
+            }
+        })
+        .collect();
+
+    log::info!(
+        "{} layers with {} contours sliced in {:.2?}",
+        layers.len(),
+        layers.iter().map(|l| l.contours.len()).sum::<usize>(),
+        before.elapsed()
+    );
+
+    Ok(layers)
+}
+
+/// Slice a model with planes along z, and extract the contours of each layer using marching squares.
+///
+/// Each layer is sampled directly as a [`PlaneSection`], so no 3D field is stored.
+/// The layers are placed in the middle of each layer height, starting at the bottom of the bounds.
+/// The sampled region is closed at its borders, so the contours are always closed.
+///
+/// # Arguments
+///
+/// * `model` - The model to slice.
+/// * `output` - The tag of the component to evaluate.
+/// * `bounds` - The region to slice.
+/// * `cell_size` - The distance between the sampled points in each layer.
+/// * `layer_height` - The distance between layers.
+/// * `iso_val` - The iso value of the surface, with lower values inside.
+pub fn slice_model<T: ModelFloat + 'static>(
+    model: &ImplicitModel<T>,
+    output: &str,
+    bounds: &BoundingBox<T>,
+    cell_size: T,
+    layer_height: T,
+    iso_val: T,
+) -> Result<Vec<SliceLayer<T>>, ModelError> {
+    let before = Instant::now();
+    let graph = model.compile(output)?;
+    let origin = bounds.min;
+    let (x_dim, y_dim, _) = bounds.dimensions();
+
+    let layers: Vec<SliceLayer<T>> = layer_heights(bounds.min.z, bounds.max.z, layer_height)?
+        .into_par_iter()
+        .map(|z| {
+            // The in-plane axes of a plane along z are the global x and y axes.
+            let plane = Plane::new(Vec3::new(origin.x, origin.y, z), Vec3::z_axis());
+            let section = PlaneSection::sample_graph(
+                &graph,
+                &plane,
+                Vec2::new(T::zero(), T::zero()),
+                Vec2::new(x_dim, y_dim),
+                cell_size,
+            )?;
+            let (nx, ny) = section.num_points();
+            Ok(SliceLayer {
+                z,
+                thickness: layer_height,
+                contours: layer_contours(section.values(), nx, ny, origin, cell_size, z, iso_val),
+            })
+        })
+        .collect::<Result<_, ModelError>>()?;
+
+    log::info!(
+        "{} layers with {} contours sliced in {:.2?}",
+        layers.len(),
+        layers.iter().map(|l| l.contours.len()).sum::<usize>(),
+        before.elapsed()
+    );
+
+    Ok(layers)
+}
+
+/// Heights in the middle of each layer between two z coordinates.
+fn layer_heights<T: Float>(min_z: T, max_z: T, layer_height: T) -> Result<Vec<T>, ModelError> {
+    if layer_height <= T::zero() || layer_height.is_nan() {
+        return Err(ModelError::Custom(
+            "The layer height must be larger than zero.".to_string(),
+        ));
+    }
+    let count = ((max_z - min_z) / layer_height)
+        .floor()
+        .to_usize()
+        .unwrap_or(0);
+    let half = layer_height / T::from(2).unwrap();
+    Ok((0..count)
+        .map(|index| min_z + half + layer_height * T::from(index).unwrap())
+        .collect())
+}
+
+/// Contours of a grid of values at height `z`, padded with outside values so all contours are closed.
+fn layer_contours<T: Float>(
+    values: &[T],
+    nx: usize,
+    ny: usize,
+    origin: Vec3<T>,
+    cell_size: T,
+    z: T,
+    iso_val: T,
+) -> Vec<Vec<Vec3<T>>> {
+    let (px, py) = (nx + 2, ny + 2);
+    let mut padded = vec![T::max_value(); px * py];
+    for j in 0..ny {
+        let row = &values[j * nx..(j + 1) * nx];
+        padded[(j + 1) * px + 1..(j + 1) * px + 1 + nx].copy_from_slice(row);
+    }
+
+    grid_iso_lines(&padded, px, py, iso_val)
+        .into_iter()
+        .filter(|line| line.closed)
+        .map(|line| {
+            let mut points: Vec<Vec3<T>> = line
+                .points
+                .iter()
+                .map(|p| {
+                    Vec3::new(
+                        origin.x + (p.x - T::one()) * cell_size,
+                        origin.y + (p.y - T::one()) * cell_size,
+                        z,
+                    )
+                })
+                .collect();
+            // Crossings at the corners of the padding can repeat a point.
+            points.dedup_by(|a, b| a.distance_to_vec3(b) == T::zero());
+            if points.len() > 1
+                && points[0].distance_to_vec3(&points[points.len() - 1]) == T::zero()
+            {
+                points.pop();
+            }
+            points
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::marching_squares::signed_area,
+        types::{
+            computation::{
+                data::sampler::{DenseSampler, Sampler},
+                operations::shape::BooleanDifference,
+            },
+            geometry::Sphere,
+        },
+    };
+
+    fn area(contour: &[Vec3<f64>]) -> f64 {
+        signed_area(contour.iter().map(|p| (p.x, p.y)))
+    }
+
+    #[test]
+    fn test_slice_model_with_hole() {
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Outer", Sphere::new(Vec3::origin(), 8.0))
+            .unwrap();
+        model
+            .add_function("Inner", Sphere::new(Vec3::origin(), 4.0))
+            .unwrap();
+        model
+            .add_operation("Shell", BooleanDifference::new(), Some(&["Outer", "Inner"]))
+            .unwrap();
+        let bounds = BoundingBox::new(Vec3::new(-9.0, -9.0, -4.0), Vec3::new(9.0, 9.0, 4.0));
+        let layers = slice_model(&model, "Shell", &bounds, 0.25, 1.0, 0.0).unwrap();
+
+        assert_eq!(layers.len(), 8);
+        assert!((layers[0].z + 3.5).abs() < 1E-12);
+        assert!((layers[0].bottom() + 4.0).abs() < 1E-12);
+        assert!((layers[7].top() - 4.0).abs() < 1E-12);
+        let middle = &layers[4];
+        assert!((middle.z - 0.5).abs() < 1E-12);
+        assert_eq!(middle.contours.len(), 2);
+
+        // The outer contour is counter-clockwise, the hole clockwise.
+        let mut areas: Vec<f64> = middle.contours.iter().map(|c| area(c)).collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let pi = std::f64::consts::PI;
+        assert!((areas[0] + pi * (16.0 - 0.25)).abs() < 0.5);
+        assert!((areas[1] - pi * (64.0 - 0.25)).abs() < 1.0);
+        for point in middle.contours.iter().flatten() {
+            let value = model
+                .evaluate_at("Shell", point.x, point.y, point.z)
+                .unwrap();
+            assert!(value.abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_slice_field_is_closed_at_borders() {
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::new(0.0, 0.0, 0.0), 5.0))
+            .unwrap();
+        let mut sampler = DenseSampler::builder()
+            .with_bounds(BoundingBox::new(
+                Vec3::new(0.0, 0.0, -5.0),
+                Vec3::new(6.0, 6.0, 6.0),
+            ))
+            .with_cell_size(0.5)
+            .build()
+            .unwrap();
+        sampler.sample_field(&model).unwrap();
+        let layers = slice_field(sampler.field(), 0.0, 2.0).unwrap();
+
+        assert_eq!(layers.len(), 5);
+        for layer in &layers {
+            // A quarter disk, closed along the borders of the field at x = 0 and y = 0.
+            assert_eq!(layer.contours.len(), 1);
+            let radius = (25.0 - layer.z * layer.z).sqrt();
+            let expected = std::f64::consts::PI * radius * radius / 4.0;
+            assert!((area(&layer.contours[0]) - expected).abs() < 0.05 * expected + 0.3);
+        }
+    }
+
+    #[test]
+    fn test_invalid_layer_height() {
+        let field =
+            DenseField::from_data(Vec3::origin(), 1.0, (2, 2, 2).into(), vec![-1.0; 8]).unwrap();
+        assert!(slice_field(&field, 0.0, 0.0).is_err());
+        assert!(slice_field(&field, 0.0, -1.0).is_err());
+
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::origin(), 1.0))
+            .unwrap();
+        let bounds = BoundingBox::new(Vec3::new(-2.0, -2.0, -2.0), Vec3::new(2.0, 2.0, 2.0));
+        assert!(slice_model(&model, "Sphere", &bounds, 0.5, 0.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_slice_single_layer_field() {
+        let field = DenseField::from_data(
+            Vec3::origin(),
+            1.0,
+            (3, 3, 1).into(),
+            vec![1.0, 1.0, 1.0, 1.0, -1.0, 1.0, 1.0, 1.0, 1.0],
+        )
+        .unwrap();
+        assert!(slice_field(&field, 0.0, 0.5).unwrap().is_empty());
+    }
+}
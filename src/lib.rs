@@ -14,7 +14,7 @@
 //! - **Custom Distance Functions**: Define distance functions mathematically or derive them from external triangle meshes.
 //! - **Field Sampling**: Both dense and sparse field sampling for handling large domains.
 //! - **Iso-surfacing**: Efficient iso-surface extraction from discretized scalar fields using marching cubes.
-//! - **Mesh Export/Import**: Export results to `.obj` files or import external `.obj` files to create custom distance functions.
+//! - **Mesh Export/Import**: Export results to `.obj`, `.stl`, `.ply`, `.gltf`, `.glb` or `.3mf` files or import external `.obj` or `.stl` files to create custom distance functions.
 //!
 //! ### Optional Feature Flags
 //!
@@ -85,7 +85,8 @@
 //!     .iso_surface(0.0)
 //!     .expect("Extracting iso-surface should work.");
 //!
-//! write_obj_file(&mesh, "interpolation_example").unwrap();
+//! let file_path = std::env::temp_dir().join("interpolation_example");
+//! write_obj_file(&mesh, file_path.to_str().unwrap()).unwrap();
 //! ```
 //!
 //! For more examples and detailed API documentation, see:
@@ -97,10 +98,14 @@
 /// The current version of the `Imlet` library.
 pub const IMLET_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Algorithms for iso-surface extraction.
+/// Algorithms for iso-surface and contour extraction.
 pub mod algorithms {
     /// Marching cubes algorithm for polygonizing implicit models.
     pub mod marching_cubes;
+    /// Marching squares algorithm for contouring implicit models in a plane.
+    pub mod marching_squares;
+    /// Slicing of fields and models into layers of closed contours, e.g. for 3D printing.
+    pub mod slicing;
     mod tables;
 }
 
@@ -114,12 +119,39 @@ pub mod types {
 
 /// Utility modules for file I/O, logging, and math operations.
 pub mod utils {
-    /// Read and write data to and from files (e.g., OBJ, CSV).
+    /// Base64 encoding for binary data embedded in text formats.
+    pub(crate) mod base64;
+    /// Compact binary encoding of dense and sparse fields.
+    pub(crate) mod field_binary;
+    /// Writer for the glTF 2.0 format, as embedded `.gltf` or binary `.glb`.
+    pub(crate) mod gltf;
+    /// Styles and writers for images in the PPM and PNG formats, e.g. for sections of fields.
+    pub(crate) mod image;
+    /// Read and write data to and from files (e.g., OBJ, STL, PLY, glTF, 3MF, CSV, VTK, NRRD, SVG, CLI, PPM, PNG, raw volumes, binary fields).
     pub mod io;
     /// Logging utilities for debugging and tracing.
     pub mod logging;
     /// Mathematical helper functions.
     pub mod math_helper;
+    /// Reader for the PGM image format of the Netpbm family.
+    pub(crate) mod netpbm;
+    /// Reader for the Wavefront OBJ format.
+    pub(crate) mod obj;
+    /// Reader and writer for the PLY format, with ascii and binary encodings.
+    pub(crate) mod ply;
+    /// Writers for layer contours, as SVG images or in the Common Layer Interface format.
+    pub(crate) mod slices;
+    /// Reader and writer for the STL format, with ascii and binary encodings.
+    pub(crate) mod stl;
+    /// Shared fixtures and temporary files for tests.
+    #[cfg(test)]
+    pub(crate) mod test_helpers;
+    /// Writer for the 3MF package format.
+    pub(crate) mod three_mf;
+    /// Writers for volumetric formats, such as VTK and NRRD.
+    pub(crate) mod volume;
+    /// Writer for uncompressed zip archives.
+    pub(crate) mod zip;
 }
 
 /// Optional viewer for visualizing generated geometries.
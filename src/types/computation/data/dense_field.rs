@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::io;
 use std::time::Instant;
 
 use hashbrown::HashSet;
@@ -17,6 +18,7 @@ use crate::types::geometry::BoundingBox;
 use crate::types::geometry::Vec3;
 use crate::types::geometry::Vec3i;
 use crate::utils;
+use crate::utils::field_binary::{invalid_data, FieldReader, FieldWriter, DENSE_FIELD_MAGIC};
 use crate::utils::math_helper::index1d_from_index3d;
 use crate::utils::math_helper::index3d_from_index1d;
 
@@ -59,6 +61,11 @@ impl<T> DenseField<T> {
         self.n.product()
     }
 
+    /// Returns the number of points in each direction.
+    pub fn num_points_per_axis(&self) -> Vec3i {
+        self.n
+    }
+
     /// Returns the total number of cells in the field as the product of cells in each direction.
     /// Note that the number of cells is one less than the number of points in each direction.
     pub fn num_cells(&self) -> usize {
@@ -243,6 +250,55 @@ impl<T: Float> DenseField<T> {
         })
     }
 
+    /// Encode the field in a compact little endian binary layout.
+    ///
+    /// The origin, cell size, point count and values are stored with the precision of `T`.
+    /// Use [`DenseField::from_bytes`] to restore the field without sampling the model again.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = FieldWriter::new::<T>(DENSE_FIELD_MAGIC);
+        writer.write_vec3(&self.origin);
+        writer.write_value(self.cell_size);
+        writer.write_usize(self.n.i);
+        writer.write_usize(self.n.j);
+        writer.write_usize(self.n.k);
+        writer.write_values(&self.data);
+        writer.into_bytes()
+    }
+
+    /// Decode a field written with [`DenseField::to_bytes`].
+    ///
+    /// Fields stored with a different float precision are converted to `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The encoded field.
+    ///
+    /// # Returns
+    ///
+    /// [`Ok`] with the decoded [`DenseField`], or an [`io::Error`] if the data is invalid or truncated.
+    pub fn from_bytes(data: &[u8]) -> io::Result<Self> {
+        let mut reader = FieldReader::new(data, DENSE_FIELD_MAGIC)?;
+        let origin = reader.read_vec3()?;
+        let cell_size: T = reader.read_value()?;
+        let n = Vec3i::new(
+            reader.read_usize()?,
+            reader.read_usize()?,
+            reader.read_usize()?,
+        );
+        if n.i < 2 || n.j < 2 || n.k < 2 || cell_size <= T::zero() {
+            return Err(invalid_data(
+                "A field needs at least 2 points per axis and a positive cell size.",
+            ));
+        }
+        let count =
+            n.i.checked_mul(n.j)
+                .and_then(|count| count.checked_mul(n.k))
+                .ok_or_else(|| invalid_data("Point count is too large."))?;
+        let values = reader.read_values(count)?;
+        reader.finish()?;
+        Self::from_data(origin, cell_size, n, values).map_err(|err| invalid_data(err.to_string()))
+    }
+
     /// Sets the value at a specific index in the data buffer.
     ///
     /// # Arguments
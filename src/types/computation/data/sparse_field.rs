@@ -1,9 +1,11 @@
 use std::fmt::Debug;
+use std::io;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 
 use crate::types::computation::data::DenseField;
 use crate::utils;
+use crate::utils::field_binary::{invalid_data, FieldReader, FieldWriter, SPARSE_FIELD_MAGIC};
 use crate::utils::math_helper::index3d_from_index1d;
 use hashbrown::{HashMap, HashSet};
 use num_traits::Float;
@@ -66,6 +68,94 @@ impl<T: Float> SparseField<T> {
     pub fn init_bounds(&mut self, bounds: &BoundingBox<T>) {
         self.root.init_bounds(bounds, &self.config);
     }
+
+    /// Returns the bounds and values of all leaf nodes.
+    ///
+    /// Each leaf is a dense block of `n`x`n`x`n` points, with the values ordered by x, then y, then z.
+    pub(crate) fn leaf_blocks(&self) -> Vec<(BoundingBox<T>, &[T])> {
+        self.root
+            .table
+            .values()
+            .filter_map(|node| {
+                if let NodeHandle::Internal(internal) = node {
+                    Some(internal)
+                } else {
+                    None
+                }
+            })
+            .flat_map(|internal| internal.children.iter())
+            .filter_map(|child| {
+                if let NodeHandle::Leaf(leaf) = child {
+                    Some((leaf.bounds, leaf.values.as_slice()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl<T: Float> SparseField<T> {
+    /// Encode the field in a compact little endian binary layout.
+    ///
+    /// The config and the full node tree, including the bounds and values of all nodes, are
+    /// stored with the precision of `T`. Use [`SparseField::from_bytes`] to restore the field
+    /// without sampling the model again.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = FieldWriter::new::<T>(SPARSE_FIELD_MAGIC);
+        writer.write_u8(self.config.internal_size.value() as u8);
+        writer.write_u8(self.config.leaf_size.value() as u8);
+        writer.write_value(self.config.cell_size);
+
+        // Sort the root table so the output doesn't depend on the hash map order.
+        let mut keys: Vec<_> = self.root.table.keys().collect();
+        keys.sort_unstable();
+        writer.write_usize(keys.len());
+        for key in keys {
+            writer.write_usize(key.0);
+            writer.write_usize(key.1);
+            writer.write_usize(key.2);
+            self.root.table[key].write_binary(&mut writer);
+        }
+        writer.into_bytes()
+    }
+
+    /// Decode a field written with [`SparseField::to_bytes`].
+    ///
+    /// Fields stored with a different float precision are converted to `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The encoded field.
+    ///
+    /// # Returns
+    ///
+    /// [`Ok`] with the decoded [`SparseField`], or an [`io::Error`] if the data is invalid or truncated.
+    pub fn from_bytes(data: &[u8]) -> io::Result<Self> {
+        let mut reader = FieldReader::new(data, SPARSE_FIELD_MAGIC)?;
+        let config = SparseFieldConfig {
+            internal_size: BlockSize::from_value(reader.read_u8()? as usize)?,
+            leaf_size: BlockSize::from_value(reader.read_u8()? as usize)?,
+            cell_size: reader.read_value()?,
+        };
+        if config.cell_size <= T::zero() {
+            return Err(invalid_data("The cell size must be positive."));
+        }
+
+        let mut field = Self::new(config);
+        let count = reader.read_usize()?;
+        for _ in 0..count {
+            let key = (
+                reader.read_usize()?,
+                reader.read_usize()?,
+                reader.read_usize()?,
+            );
+            let node = NodeHandle::read_binary(&mut reader, &config, 0)?;
+            field.root.table.insert(key, node);
+        }
+        reader.finish()?;
+        Ok(field)
+    }
 }
 
 impl<T: ModelFloat + 'static + Default> SparseField<T> {
@@ -168,6 +258,19 @@ impl BlockSize {
         }
     }
 
+    /// Returns the block size with the given size value.
+    fn from_value(value: usize) -> io::Result<Self> {
+        match value {
+            2 => Ok(BlockSize::Size2),
+            4 => Ok(BlockSize::Size4),
+            8 => Ok(BlockSize::Size8),
+            16 => Ok(BlockSize::Size16),
+            32 => Ok(BlockSize::Size32),
+            64 => Ok(BlockSize::Size64),
+            _ => Err(invalid_data(format!("Invalid block size {}.", value))),
+        }
+    }
+
     /// Returns the total number of elements in a block (size^3).
     ///
     /// For example:
@@ -307,6 +410,82 @@ enum NodeHandle<T> {
     Empty,
 }
 
+const NODE_EMPTY: u8 = 0;
+const NODE_CONSTANT: u8 = 1;
+const NODE_LEAF: u8 = 2;
+const NODE_INTERNAL: u8 = 3;
+
+impl<T: Float> NodeHandle<T> {
+    /// Writes the node type, followed by the bounds and values or children of the node.
+    fn write_binary(&self, writer: &mut FieldWriter) {
+        match self {
+            NodeHandle::Empty => writer.write_u8(NODE_EMPTY),
+            NodeHandle::Constant(bounds, value) => {
+                writer.write_u8(NODE_CONSTANT);
+                writer.write_bounds(bounds);
+                writer.write_value(*value);
+            }
+            NodeHandle::Leaf(leaf) => {
+                writer.write_u8(NODE_LEAF);
+                writer.write_bounds(&leaf.bounds);
+                writer.write_values(&leaf.values);
+            }
+            NodeHandle::Internal(internal) => {
+                writer.write_u8(NODE_INTERNAL);
+                writer.write_bounds(&internal.bounds);
+                writer.write_usize(internal.children.len());
+                internal
+                    .children
+                    .iter()
+                    .for_each(|child| child.write_binary(writer));
+            }
+        }
+    }
+
+    /// Reads a node written by [`NodeHandle::write_binary`].
+    ///
+    /// The root table holds internal nodes at depth 0, which hold leaves at depth 1.
+    fn read_binary(
+        reader: &mut FieldReader,
+        config: &SparseFieldConfig<T>,
+        depth: usize,
+    ) -> io::Result<Self> {
+        match reader.read_u8()? {
+            NODE_EMPTY => Ok(NodeHandle::Empty),
+            NODE_CONSTANT => Ok(NodeHandle::Constant(
+                reader.read_bounds()?,
+                reader.read_value()?,
+            )),
+            NODE_LEAF if depth == 1 => Ok(NodeHandle::Leaf(LeafNode {
+                bounds: reader.read_bounds()?,
+                values: reader.read_values(config.leaf_size.total_size())?,
+            })),
+            NODE_INTERNAL if depth == 0 => {
+                let bounds = reader.read_bounds()?;
+                let count = reader.read_usize()?;
+                if count != config.internal_size.total_size() {
+                    return Err(invalid_data(format!(
+                        "Expected {} child nodes, found {}.",
+                        config.internal_size.total_size(),
+                        count
+                    )));
+                }
+                let children = (0..count)
+                    .map(|_| Self::read_binary(reader, config, depth + 1))
+                    .collect::<io::Result<_>>()?;
+                Ok(NodeHandle::Internal(Box::new(InternalNode {
+                    bounds,
+                    children,
+                })))
+            }
+            tag => Err(invalid_data(format!(
+                "Invalid node type {} at depth {}.",
+                tag, depth
+            ))),
+        }
+    }
+}
+
 /// Internal node in the sparse field tree structure
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -0,0 +1,470 @@
+use std::fmt::Debug;
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+    },
+    geometry::Vec3,
+};
+
+use super::{Gyroid, Neovius, SchwarzP};
+
+/// Triply periodic surface types which can be used in conformal and graded lattices.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TpmsSurface {
+    /// Gyroid surface, see [`Gyroid`].
+    Gyroid,
+    /// Schwarz-P surface, see [`SchwarzP`].
+    SchwarzP,
+    /// Neovius surface, see [`Neovius`].
+    Neovius,
+}
+
+impl TpmsSurface {
+    pub(crate) const NAMES: [&'static str; 3] = ["Gyroid", "SchwarzP", "Neovius"];
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            TpmsSurface::Gyroid => Self::NAMES[0],
+            TpmsSurface::SchwarzP => Self::NAMES[1],
+            TpmsSurface::Neovius => Self::NAMES[2],
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Gyroid" => Some(TpmsSurface::Gyroid),
+            "SchwarzP" => Some(TpmsSurface::SchwarzP),
+            "Neovius" => Some(TpmsSurface::Neovius),
+            _ => None,
+        }
+    }
+
+    /// Evaluate the surface in cell coordinates, where the surface repeats with a period of one in each direction.
+    ///
+    /// The result is scaled to a cell size of one, so multiplying it by the cell size gives the approximate distance.
+    pub fn eval_unit_cell<T: ModelFloat>(&self, x: T, y: T, z: T, linear: bool) -> T {
+        match self {
+            // The gyroid length parameter is half of the period.
+            TpmsSurface::Gyroid => {
+                Gyroid::with_equal_spacing(T::from(0.5).unwrap(), linear).eval(x, y, z)
+            }
+            TpmsSurface::SchwarzP => SchwarzP::with_equal_spacing(T::one(), linear).eval(x, y, z),
+            TpmsSurface::Neovius => Neovius::with_equal_spacing(T::one(), linear).eval(x, y, z),
+        }
+    }
+}
+
+/// Coordinate system used to map a [`ConformalTpms`] onto a geometry.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConformalMapping {
+    /// Cells are laid out in radius, azimuth and height around an axis.
+    Cylindrical,
+    /// Cells are laid out in radius, azimuth and polar angle around a center.
+    Spherical,
+}
+
+/// Function representing a triply periodic surface mapped to cylindrical or spherical coordinates.
+///
+/// Instead of tiling cartesian space, the cells follow the radius and azimuth around an axis,
+/// so that lattices in tubes, rings and shells have no cut cells around the circumference.
+///
+/// The number of cells around the circumference is an integer, which makes the surface seamless.
+/// For the cylindrical mapping, the cells along the axis have a fixed period.
+/// For the spherical mapping, the polar angle is divided in half as many cells as the azimuth, so the cells are square at the equator.
+///
+/// Like the cartesian TPMS functions, the output is an approximate distance.
+/// The distance is scaled by the smallest local cell size, which for the circumferential direction varies with the radius.
+/// For the spherical mapping, the circumferential cell size is measured at the equator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct ConformalTpms<T> {
+    pub surface: TpmsSurface,
+    pub mapping: ConformalMapping,
+    pub origin: Vec3<T>,
+    axis: Vec3<T>,
+    u_axis: Vec3<T>,
+    v_axis: Vec3<T>,
+    pub cells_around: usize,
+    pub radial_period: T,
+    pub axial_period: T,
+    pub linear: bool,
+}
+
+impl<T: Float> ConformalTpms<T> {
+    /// Create a new TPMS mapped to cylindrical coordinates.
+    /// # Arguments
+    ///
+    /// * `surface` - Type of periodic surface.
+    /// * `origin` - Point on the axis of the cylinder.
+    /// * `axis` - Direction of the cylinder axis.
+    /// * `cells_around` - Number of cells around the circumference.
+    /// * `radial_period` - Cell size in the radial direction.
+    /// * `axial_period` - Cell size along the axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis has zero length.
+    pub fn cylindrical(
+        surface: TpmsSurface,
+        origin: Vec3<T>,
+        axis: Vec3<T>,
+        cells_around: usize,
+        radial_period: T,
+        axial_period: T,
+    ) -> Self {
+        let mut tpms = Self {
+            surface,
+            mapping: ConformalMapping::Cylindrical,
+            origin,
+            axis: Vec3::z_axis(),
+            u_axis: Vec3::x_axis(),
+            v_axis: Vec3::y_axis(),
+            cells_around: cells_around.max(1),
+            radial_period,
+            axial_period,
+            linear: false,
+        };
+        tpms.set_axis(axis);
+        tpms
+    }
+
+    /// Create a new TPMS mapped to spherical coordinates.
+    /// # Arguments
+    ///
+    /// * `surface` - Type of periodic surface.
+    /// * `center` - Center of the sphere.
+    /// * `axis` - Direction of the polar axis.
+    /// * `cells_around` - Number of cells around the equator.
+    /// * `radial_period` - Cell size in the radial direction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis has zero length.
+    pub fn spherical(
+        surface: TpmsSurface,
+        center: Vec3<T>,
+        axis: Vec3<T>,
+        cells_around: usize,
+        radial_period: T,
+    ) -> Self {
+        let mut tpms = Self {
+            surface,
+            mapping: ConformalMapping::Spherical,
+            origin: center,
+            axis: Vec3::z_axis(),
+            u_axis: Vec3::x_axis(),
+            v_axis: Vec3::y_axis(),
+            cells_around: cells_around.max(1),
+            radial_period,
+            axial_period: radial_period,
+            linear: false,
+        };
+        tpms.set_axis(axis);
+        tpms
+    }
+
+    /// Use the linearized version of the surface function.
+    pub fn with_linear(mut self, linear: bool) -> Self {
+        self.linear = linear;
+        self
+    }
+
+    /// The unit direction of the axis.
+    pub fn axis(&self) -> Vec3<T> {
+        self.axis
+    }
+
+    /// Set the axis direction, which is normalized and used to update the reference directions around it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis has zero length.
+    pub fn set_axis(&mut self, axis: Vec3<T>) {
+        assert!(
+            axis.magnitude() >= T::epsilon(),
+            "Axis can't be a zero length vector."
+        );
+        self.axis = axis.normalize();
+        (self.u_axis, self.v_axis) = self.axis.orthonormal_basis();
+    }
+}
+
+static CONFORMAL_TPMS_PARAMETERS: &[Parameter; 8] = &[
+    Parameter {
+        name: "Surface",
+        data_type: DataType::Enum(&TpmsSurface::NAMES),
+    },
+    Parameter {
+        name: "Mapping",
+        data_type: DataType::Enum(&["Cylindrical", "Spherical"]),
+    },
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Axis",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Cells Around",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Radial Period",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Axial Period",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Linearize",
+        data_type: DataType::Boolean,
+    },
+];
+
+impl<T: ModelFloat> ImplicitFunction<T> for ConformalTpms<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let two_pi = T::from(2.0).unwrap() * T::pi();
+        let cells = T::from(self.cells_around).unwrap();
+
+        let d = Vec3::new(x, y, z) - self.origin;
+        let (u, v) = (self.u_axis, self.v_axis);
+        let height = d.dot(&self.axis);
+        let azimuth = d.dot(&v).atan2(d.dot(&u));
+        let azimuth_cells = azimuth / two_pi * cells;
+
+        let (cell_coords, local_size) = match self.mapping {
+            ConformalMapping::Cylindrical => {
+                let radius = (d.dot(&u).powi(2) + d.dot(&v).powi(2)).sqrt();
+                let arc_size = two_pi * radius / cells;
+                (
+                    Vec3::new(
+                        radius / self.radial_period,
+                        azimuth_cells,
+                        height / self.axial_period,
+                    ),
+                    self.radial_period.min(self.axial_period).min(arc_size),
+                )
+            }
+            ConformalMapping::Spherical => {
+                let radius = d.magnitude();
+                let polar = if radius > T::zero() {
+                    (height / radius).clamp(-T::one(), T::one()).acos()
+                } else {
+                    T::zero()
+                };
+                // Cells are measured at the equator, to avoid collapsing the distance towards the poles.
+                let arc_size = two_pi * radius / cells;
+                (
+                    Vec3::new(
+                        radius / self.radial_period,
+                        azimuth_cells,
+                        polar / T::pi() * cells / T::from(2.0).unwrap(),
+                    ),
+                    self.radial_period.min(arc_size),
+                )
+            }
+        };
+
+        local_size
+            * self
+                .surface
+                .eval_unit_cell(cell_coords.x, cell_coords.y, cell_coords.z, self.linear)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for ConformalTpms<T> {
+    fn parameters(&self) -> &[Parameter] {
+        CONFORMAL_TPMS_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(selection) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Surface")
+        {
+            match TpmsSurface::from_name(&selection) {
+                Some(surface) => self.surface = surface,
+                None => error!("Unknown surface: {}", selection),
+            }
+        } else if let Some(selection) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Mapping")
+        {
+            match selection.as_str() {
+                "Cylindrical" => self.mapping = ConformalMapping::Cylindrical,
+                "Spherical" => self.mapping = ConformalMapping::Spherical,
+                _ => error!("Unknown mapping: {}", selection),
+            }
+        } else if parameter_name == "Axis" {
+            let mut axis = self.axis;
+            Parameter::set_vec3_from_param(parameter_name, &data, "Axis", &mut axis);
+            if axis.magnitude() < T::epsilon() {
+                error!("Axis can't be a zero length vector.");
+            } else {
+                self.set_axis(axis);
+            }
+        } else if parameter_name == "Cells Around" {
+            let mut value = T::from(self.cells_around).unwrap();
+            Parameter::set_value_from_param(parameter_name, &data, "Cells Around", &mut value);
+            self.cells_around = value.round().to_usize().unwrap_or(1).max(1);
+        } else if !(Parameter::set_vec3_from_param(
+            parameter_name,
+            &data,
+            "Origin",
+            &mut self.origin,
+        ) || Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Radial Period",
+            &mut self.radial_period,
+        ) || Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Axial Period",
+            &mut self.axial_period,
+        ) || Parameter::set_bool_from_param(
+            parameter_name,
+            &data,
+            "Linearize",
+            &mut self.linear,
+        )) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Surface" => Some(Data::EnumValue(self.surface.as_str().to_string())),
+            "Mapping" => match self.mapping {
+                ConformalMapping::Cylindrical => Some(Data::EnumValue("Cylindrical".to_string())),
+                ConformalMapping::Spherical => Some(Data::EnumValue("Spherical".to_string())),
+            },
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Cells Around" => Some(Data::Value(T::from(self.cells_around).unwrap())),
+            "Radial Period" => Some(Data::Value(self.radial_period)),
+            "Axial Period" => Some(Data::Value(self.axial_period)),
+            "Linearize" => Some(Data::Boolean(self.linear)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "ConformalTpms"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_cell_matches_cartesian_functions() {
+        let gyroid = Gyroid::with_equal_spacing(5.0, false);
+        let schwarz = SchwarzP::with_equal_spacing(10.0, false);
+
+        let (x, y, z) = (1.3, 4.1, -2.7);
+        let gyroid_unit =
+            10.0 * TpmsSurface::Gyroid.eval_unit_cell(x / 10.0, y / 10.0, z / 10.0, false);
+        let schwarz_unit =
+            10.0 * TpmsSurface::SchwarzP.eval_unit_cell(x / 10.0, y / 10.0, z / 10.0, false);
+
+        assert!((gyroid.eval(x, y, z) - gyroid_unit).abs() < 1E-10);
+        assert!((schwarz.eval(x, y, z) - schwarz_unit).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_cylindrical_is_seamless_and_rotationally_periodic() {
+        let cells = 12;
+        let tpms = ConformalTpms::cylindrical(
+            TpmsSurface::Gyroid,
+            Vec3::origin(),
+            Vec3::z_axis(),
+            cells,
+            5.0,
+            8.0,
+        );
+
+        let step = 2.0 * std::f64::consts::PI / cells as f64;
+        for i in 0..20 {
+            let radius = 20.0 + i as f64;
+            let angle = 0.1 * i as f64;
+            let z = 0.7 * i as f64;
+            let value = tpms.eval(radius * angle.cos(), radius * angle.sin(), z);
+            let rotated = tpms.eval(
+                radius * (angle + step).cos(),
+                radius * (angle + step).sin(),
+                z,
+            );
+            assert!((value - rotated).abs() < 1E-9);
+        }
+
+        // Across the seam of the azimuth at -pi / pi
+        let eps = 1E-9;
+        let a = tpms.eval(-25.0, eps, 3.0);
+        let b = tpms.eval(-25.0, -eps, 3.0);
+        assert!((a - b).abs() < 1E-6);
+    }
+
+    #[test]
+    fn test_spherical_is_rotationally_periodic() {
+        let cells = 8;
+        let tpms = ConformalTpms::spherical(
+            TpmsSurface::SchwarzP,
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::z_axis(),
+            cells,
+            4.0,
+        );
+
+        let step = 2.0 * std::f64::consts::PI / cells as f64;
+        let (r, polar) = (30.0, 1.1);
+        for i in 0..10 {
+            let angle = 0.3 * i as f64;
+            let p = |a: f64| {
+                Vec3::new(
+                    1.0 + r * polar.sin() * a.cos(),
+                    2.0 + r * polar.sin() * a.sin(),
+                    3.0 + r * polar.cos(),
+                )
+            };
+            let (p1, p2) = (p(angle), p(angle + step));
+            assert!((tpms.eval(p1.x, p1.y, p1.z) - tpms.eval(p2.x, p2.y, p2.z)).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_get_assigns_params() {
+        let mut tpms = ConformalTpms::cylindrical(
+            TpmsSurface::Gyroid,
+            Vec3::origin(),
+            Vec3::z_axis(),
+            6,
+            5.0,
+            5.0,
+        );
+
+        tpms.set_parameter("Cells Around", Data::Value(10.4));
+        tpms.set_parameter("Mapping", Data::EnumValue("Spherical".to_string()));
+        tpms.set_parameter("Surface", Data::EnumValue("Neovius".to_string()));
+        tpms.set_parameter("Axis", Data::Vec3(Vec3::new(0.0, 0.0, 2.0)));
+
+        assert_eq!(tpms.cells_around, 10);
+        assert_eq!(tpms.mapping, ConformalMapping::Spherical);
+        assert_eq!(tpms.surface, TpmsSurface::Neovius);
+        assert!((tpms.axis.magnitude() - 1.0).abs() < 1E-10);
+
+        tpms.set_parameter("Axis", Data::Vec3(Vec3::origin()));
+        assert!((tpms.axis.z - 1.0).abs() < 1E-10);
+    }
+}
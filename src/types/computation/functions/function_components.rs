@@ -21,6 +21,22 @@ pub enum FunctionComponent {
     SchwarzP,
     /// Function to generate a triply periodic Neovius surface.
     Neovius,
+    /// Function to generate Perlin gradient noise.
+    PerlinNoise,
+    /// Function to generate simplex gradient noise.
+    SimplexNoise,
+    /// Function to generate a triply periodic surface in cylindrical or spherical coordinates.
+    ConformalTpms,
+    /// Function to generate cellular Worley noise.
+    WorleyNoise,
+    /// Function to generate a Voronoi foam or lattice from seed points.
+    Voronoi,
+    /// Function to generate a periodic strut lattice from a unit cell topology.
+    StrutLattice,
+    /// Function to generate tubes with a varying radius along a network of nodes and edges.
+    TubeNetwork,
+    /// Function to generate a beam lattice along the edges of a mesh.
+    MeshLattice,
     /// A remapped domain for the x-coordinate.
     XDomain,
     /// A remapped domain for the y-coordinate.
@@ -29,6 +45,12 @@ pub enum FunctionComponent {
     ZDomain,
     /// Simple function which maps to a coordinate, e.g. *f(x,y,z)->x*
     XYZValue,
+    /// Function which maps to a cylindrical coordinate around an axis.
+    CylindricalValue,
+    /// Function which maps to a spherical coordinate around a center.
+    SphericalValue,
+    /// Function which maps to a coordinate relative to a plane.
+    PlaneValue,
     /// Represents a component to generate the distance function for a sphere.
     Sphere,
     /// Represents a component to generate the distance function for a torus.
@@ -39,8 +61,18 @@ pub enum FunctionComponent {
     BoundingBox,
     /// Represents a component to generate the distance function for a capsule.
     Capsule,
+    /// Represents a component to generate the distance function for an extruded 2D profile.
+    Extrusion,
+    /// Represents a component to generate the distance function for a revolved 2D profile.
+    Revolution,
     /// Represents a component to generate the distance function for an arbitrary mesh.
     MeshFile,
+    /// Represents a component to generate the distance function for a point cloud.
+    PointCloud,
+    /// Represents a component to reconstruct a smooth surface from a point cloud with normals.
+    HrbfSurface,
+    /// Represents a component to interpolate the values of a dense field, such as a scan.
+    VolumeField,
 }
 
 impl FunctionComponent {
@@ -59,7 +91,59 @@ impl FunctionComponent {
             FunctionComponent::Neovius => {
                 Box::new(Neovius::with_equal_spacing(T::from(15).unwrap(), false))
             }
+            FunctionComponent::ConformalTpms => Box::new(ConformalTpms::cylindrical(
+                TpmsSurface::Gyroid,
+                Vec3::origin(),
+                Vec3::z_axis(),
+                12,
+                T::from(15).unwrap(),
+                T::from(15).unwrap(),
+            )),
+            FunctionComponent::PerlinNoise => Box::new(PerlinNoise::new(
+                0,
+                T::from(0.05).unwrap(),
+                T::from(5).unwrap(),
+            )),
+            FunctionComponent::SimplexNoise => Box::new(SimplexNoise::new(
+                0,
+                T::from(0.05).unwrap(),
+                T::from(5).unwrap(),
+            )),
+            FunctionComponent::WorleyNoise => Box::new(WorleyNoise::new(
+                0,
+                T::from(0.05).unwrap(),
+                T::from(20).unwrap(),
+            )),
+            FunctionComponent::Voronoi => Box::new(Voronoi::random(
+                BoundingBox::new(
+                    Vec3::new(-default_value, -default_value, -default_value),
+                    Vec3::new(default_value, default_value, default_value),
+                ),
+                50,
+                0,
+                VoronoiOutput::CellWalls,
+            )),
+            FunctionComponent::StrutLattice => Box::new(StrutLattice::new(
+                LatticeTopology::Bcc,
+                T::from(15).unwrap(),
+                T::from(1).unwrap(),
+            )),
+            FunctionComponent::TubeNetwork => Box::new(TubeNetwork::new()),
+            FunctionComponent::MeshLattice => Box::new(MeshLattice::new(T::one())),
             FunctionComponent::XYZValue => Box::new(XYZValue::new(CoordinateValue::X)),
+            FunctionComponent::CylindricalValue => Box::new(CylindricalValue::natural(
+                Vec3::origin(),
+                Vec3::z_axis(),
+                CylindricalCoordinate::Radius,
+            )),
+            FunctionComponent::SphericalValue => Box::new(SphericalValue::natural(
+                Vec3::origin(),
+                Vec3::z_axis(),
+                SphericalCoordinate::Radius,
+            )),
+            FunctionComponent::PlaneValue => {
+                Box::new(PlaneValue::natural(Plane::xy(), PlaneCoordinate::Distance))
+            }
             FunctionComponent::Sphere => Box::new(Sphere::new(Vec3::origin(), default_value)),
             FunctionComponent::Torus => Box::new(Torus::new(
                 Vec3::origin(),
@@ -78,7 +162,29 @@ impl FunctionComponent {
                 ),
                 T::from(5).unwrap(),
             )),
+            FunctionComponent::Extrusion => Box::new(Extrusion::new(
+                Profile::rectangle(
+                    Vec2::origin(),
+                    default_value,
+                    default_value,
+                    T::from(5).unwrap(),
+                ),
+                Plane::xy(),
+                default_value,
+            )),
+            FunctionComponent::Revolution => Box::new(Revolution::new(
+                Profile::rectangle(
+                    Vec2::new(T::from(30).unwrap(), T::zero()),
+                    T::from(15).unwrap(),
+                    default_value,
+                    T::from(2.5).unwrap(),
+                ),
+                Plane::xy(),
+            )),
             FunctionComponent::MeshFile => Box::new(MeshFile::new()),
+            FunctionComponent::PointCloud => Box::new(PointCloud::new()),
+            FunctionComponent::HrbfSurface => Box::new(HrbfSurface::new()),
+            FunctionComponent::VolumeField => Box::new(VolumeField::new()),
             FunctionComponent::XDomain => Box::new(XDomain::natural()),
             FunctionComponent::YDomain => Box::new(YDomain::natural()),
             FunctionComponent::ZDomain => Box::new(ZDomain::natural()),
@@ -96,16 +202,32 @@ impl FromStr for FunctionComponent {
             "Gyroid" => Ok(FunctionComponent::Gyroid),
             "SchwarzP" => Ok(FunctionComponent::SchwarzP),
             "Neovius" => Ok(FunctionComponent::Neovius),
+            "ConformalTpms" => Ok(FunctionComponent::ConformalTpms),
+            "PerlinNoise" => Ok(FunctionComponent::PerlinNoise),
+            "SimplexNoise" => Ok(FunctionComponent::SimplexNoise),
+            "WorleyNoise" => Ok(FunctionComponent::WorleyNoise),
+            "Voronoi" => Ok(FunctionComponent::Voronoi),
+            "StrutLattice" => Ok(FunctionComponent::StrutLattice),
+            "TubeNetwork" => Ok(FunctionComponent::TubeNetwork),
+            "MeshLattice" => Ok(FunctionComponent::MeshLattice),
             "XDomain" => Ok(FunctionComponent::XDomain),
             "YDomain" => Ok(FunctionComponent::YDomain),
             "ZDomain" => Ok(FunctionComponent::ZDomain),
             "XYZValue" => Ok(FunctionComponent::XYZValue),
+            "CylindricalValue" => Ok(FunctionComponent::CylindricalValue),
+            "SphericalValue" => Ok(FunctionComponent::SphericalValue),
+            "PlaneValue" => Ok(FunctionComponent::PlaneValue),
             "Sphere" => Ok(FunctionComponent::Sphere),
             "Torus" => Ok(FunctionComponent::Torus),
             "Plane" => Ok(FunctionComponent::Plane),
             "BoundingBox" => Ok(FunctionComponent::BoundingBox),
             "Capsule" => Ok(FunctionComponent::Capsule),
+            "Extrusion" => Ok(FunctionComponent::Extrusion),
+            "Revolution" => Ok(FunctionComponent::Revolution),
             "MeshFile" => Ok(FunctionComponent::MeshFile),
+            "PointCloud" => Ok(FunctionComponent::PointCloud),
+            "HrbfSurface" => Ok(FunctionComponent::HrbfSurface),
+            "VolumeField" => Ok(FunctionComponent::VolumeField),
             _ => Err(()),
         }
     }
@@ -116,7 +238,18 @@ pub const FUNCTION_COMPONENTS: &[FunctionComponent] = &[
     FunctionComponent::Gyroid,
     FunctionComponent::SchwarzP,
     FunctionComponent::Neovius,
+    FunctionComponent::ConformalTpms,
+    FunctionComponent::PerlinNoise,
+    FunctionComponent::SimplexNoise,
+    FunctionComponent::WorleyNoise,
+    FunctionComponent::Voronoi,
+    FunctionComponent::StrutLattice,
+    FunctionComponent::TubeNetwork,
+    FunctionComponent::MeshLattice,
     FunctionComponent::XYZValue,
+    FunctionComponent::CylindricalValue,
+    FunctionComponent::SphericalValue,
+    FunctionComponent::PlaneValue,
     FunctionComponent::XDomain,
     FunctionComponent::YDomain,
     FunctionComponent::ZDomain,
@@ -124,7 +257,12 @@ pub const FUNCTION_COMPONENTS: &[FunctionComponent] = &[
     FunctionComponent::Torus,
     FunctionComponent::Plane,
     FunctionComponent::Capsule,
+    FunctionComponent::Extrusion,
+    FunctionComponent::Revolution,
     FunctionComponent::MeshFile,
+    FunctionComponent::PointCloud,
+    FunctionComponent::HrbfSurface,
+    FunctionComponent::VolumeField,
 ];
 
 #[cfg(test)]
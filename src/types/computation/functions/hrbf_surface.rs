@@ -0,0 +1,609 @@
+use std::error::Error;
+
+use log::{error, info, warn};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::{
+        computation::{
+            model::{Data, DataType, Parameter},
+            traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+            ModelError,
+        },
+        geometry::{
+            traits::{Bounded, SpatialQuery},
+            BoundingBox, Octree, Vec3,
+        },
+    },
+    utils::io::parse_point_cloud_file,
+};
+
+use super::point_cloud::{validate_normals, CloudPoint};
+
+/// Maximum number of points in a cell before it is subdivided.
+const MAX_CELL_POINT_COUNT: usize = 24;
+const MAX_SUBDIVISION_DEPTH: usize = 10;
+/// Limits for the number of points in each local fit.
+const MIN_FIT_POINT_COUNT: usize = 8;
+const MAX_FIT_POINT_COUNT: usize = 48;
+/// Radius of a local support relative to the diagonal of its cell.
+const SUPPORT_RADIUS_FACTOR: f64 = 0.75;
+/// Weight of the closest point distance, used where no local fit applies.
+const FALLBACK_WEIGHT: f64 = 1E-6;
+
+/// Spherical region where a local fit is used, stored in an octree to find the fits around a point.
+#[derive(Debug, Clone, Copy)]
+struct Support<T> {
+    center: Vec3<T>,
+    radius: T,
+    index: usize,
+}
+
+impl<T: ModelFloat> Bounded<T> for Support<T> {
+    fn bounds(&self) -> BoundingBox<T> {
+        let offset = Vec3::new(self.radius, self.radius, self.radius);
+        BoundingBox::new(self.center - offset, self.center + offset)
+    }
+}
+
+impl<T: ModelFloat> SpatialQuery<T> for Support<T> {
+    fn default() -> Self {
+        Self {
+            center: Vec3::origin(),
+            radius: T::zero(),
+            index: 0,
+        }
+    }
+
+    fn closest_point(&self, query_point: &Vec3<T>) -> Vec3<T> {
+        let direction = *query_point - self.center;
+        let distance = direction.magnitude();
+        if distance <= self.radius {
+            *query_point
+        } else {
+            self.center + direction * (self.radius / distance)
+        }
+    }
+}
+
+/// Hermite interpolant of the points in one support, in coordinates local to the support.
+///
+/// The local coordinates are `(x - center) / scale`, which keeps the linear system well conditioned for any model size.
+#[derive(Debug, Clone)]
+struct LocalFit {
+    center: Vec3<f64>,
+    scale: f64,
+    nodes: Vec<Vec3<f64>>,
+    alpha: Vec<f64>,
+    beta: Vec<Vec3<f64>>,
+    constant: f64,
+    linear: Vec3<f64>,
+}
+
+impl LocalFit {
+    /// Fit `f(x) = sum(alpha * phi(x - x_j) - beta . grad(phi)(x - x_j)) + constant + linear . x`, with `phi(r) = r^3`,
+    /// so the function is zero at each point and its gradient matches the normal.
+    fn new(center: Vec3<f64>, scale: f64, points: &[(Vec3<f64>, Vec3<f64>)]) -> Option<Self> {
+        let mut nodes: Vec<Vec3<f64>> = Vec::with_capacity(points.len());
+        let mut normals = Vec::with_capacity(points.len());
+        for &(point, normal) in points {
+            let node = (point - center) * (1.0 / scale);
+            // Coincident points make the system singular.
+            if nodes
+                .iter()
+                .all(|other| other.distance_to_vec3(&node) > 1E-6)
+            {
+                nodes.push(node);
+                normals.push(normal);
+            }
+        }
+
+        let m = nodes.len();
+        let n = 4 * m + 4;
+        let mut matrix = vec![0.0; n * n];
+        let mut rhs = vec![0.0; n];
+        let mut set = |row: usize, column: usize, value: f64| matrix[row * n + column] = value;
+
+        for (i, node_i) in nodes.iter().enumerate() {
+            for (j, node_j) in nodes.iter().enumerate() {
+                let d = *node_i - *node_j;
+                let r = d.magnitude();
+                let d = [d.x, d.y, d.z];
+                set(4 * i, 4 * j, r * r * r);
+                for k in 0..3 {
+                    set(4 * i, 4 * j + 1 + k, -3.0 * r * d[k]);
+                    set(4 * i + 1 + k, 4 * j, 3.0 * r * d[k]);
+                    if r > 0.0 {
+                        for l in 0..3 {
+                            let identity = if k == l { r } else { 0.0 };
+                            set(
+                                4 * i + 1 + k,
+                                4 * j + 1 + l,
+                                -3.0 * (identity + d[k] * d[l] / r),
+                            );
+                        }
+                    }
+                }
+            }
+
+            // Linear polynomial and its side conditions.
+            let x = [node_i.x, node_i.y, node_i.z];
+            set(4 * i, 4 * m, 1.0);
+            set(4 * m, 4 * i, 1.0);
+            for (k, &x_k) in x.iter().enumerate() {
+                set(4 * i, 4 * m + 1 + k, x_k);
+                set(4 * i + 1 + k, 4 * m + 1 + k, 1.0);
+                set(4 * m + 1 + k, 4 * i, x_k);
+                set(4 * m + 1 + k, 4 * i + 1 + k, 1.0);
+            }
+
+            rhs[4 * i + 1] = normals[i].x;
+            rhs[4 * i + 2] = normals[i].y;
+            rhs[4 * i + 3] = normals[i].z;
+        }
+
+        let solution = solve_linear_system(matrix, rhs)?;
+        Some(Self {
+            center,
+            scale,
+            alpha: (0..m).map(|j| solution[4 * j]).collect(),
+            beta: (0..m)
+                .map(|j| {
+                    Vec3::new(
+                        solution[4 * j + 1],
+                        solution[4 * j + 2],
+                        solution[4 * j + 3],
+                    )
+                })
+                .collect(),
+            constant: solution[4 * m],
+            linear: Vec3::new(
+                solution[4 * m + 1],
+                solution[4 * m + 2],
+                solution[4 * m + 3],
+            ),
+            nodes,
+        })
+    }
+
+    fn eval(&self, point: &Vec3<f64>) -> f64 {
+        let x = (*point - self.center) * (1.0 / self.scale);
+        let mut value = self.constant + self.linear.dot(&x);
+        for ((node, alpha), beta) in self.nodes.iter().zip(&self.alpha).zip(&self.beta) {
+            let d = x - *node;
+            let r = d.magnitude();
+            value += alpha * r * r * r - 3.0 * r * beta.dot(&d);
+        }
+        value * self.scale
+    }
+}
+
+/// Solve a dense linear system with Gaussian elimination and partial pivoting.
+///
+/// Returns None if the matrix is singular.
+fn solve_linear_system(mut matrix: Vec<f64>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let n = rhs.len();
+    let tolerance = matrix.iter().fold(0.0, |max: f64, v| max.max(v.abs())) * 1E-12;
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&a, &b| {
+            matrix[a * n + column]
+                .abs()
+                .total_cmp(&matrix[b * n + column].abs())
+        })?;
+        if matrix[pivot * n + column].abs() <= tolerance {
+            return None;
+        }
+        if pivot != column {
+            for k in 0..n {
+                matrix.swap(pivot * n + k, column * n + k);
+            }
+            rhs.swap(pivot, column);
+        }
+
+        let diagonal = matrix[column * n + column];
+        for row in column + 1..n {
+            let factor = matrix[row * n + column] / diagonal;
+            if factor != 0.0 {
+                for k in column..n {
+                    matrix[row * n + k] -= factor * matrix[column * n + k];
+                }
+                rhs[row] -= factor * rhs[column];
+            }
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n)
+            .map(|k| matrix[row * n + k] * solution[k])
+            .sum();
+        solution[row] = (rhs[row] - sum) / matrix[row * n + row];
+    }
+    Some(solution)
+}
+
+/// Split the points into cells with few points each, and collect the bounds of the non-empty cells.
+fn subdivide<T: ModelFloat>(
+    points: &[Vec3<T>],
+    indices: Vec<usize>,
+    bounds: BoundingBox<T>,
+    depth: usize,
+    cells: &mut Vec<BoundingBox<T>>,
+) {
+    if indices.is_empty() {
+        return;
+    }
+    if indices.len() <= MAX_CELL_POINT_COUNT || depth == MAX_SUBDIVISION_DEPTH {
+        cells.push(bounds);
+        return;
+    }
+
+    let center = bounds.centroid();
+    let mut children: [Vec<usize>; 8] = Default::default();
+    for index in indices {
+        let p = points[index];
+        let octant = (p.x >= center.x) as usize
+            | ((p.y >= center.y) as usize) << 1
+            | ((p.z >= center.z) as usize) << 2;
+        children[octant].push(index);
+    }
+
+    for (octant, child) in children.into_iter().enumerate() {
+        let pick = |bit: usize, min: T, mid: T, max: T| {
+            if octant & bit == 0 {
+                (min, mid)
+            } else {
+                (mid, max)
+            }
+        };
+        let (min_x, max_x) = pick(1, bounds.min.x, center.x, bounds.max.x);
+        let (min_y, max_y) = pick(2, bounds.min.y, center.y, bounds.max.y);
+        let (min_z, max_z) = pick(4, bounds.min.z, center.z, bounds.max.z);
+        let child_bounds = BoundingBox::new(
+            Vec3::new(min_x, min_y, min_z),
+            Vec3::new(max_x, max_y, max_z),
+        );
+        subdivide(points, child, child_bounds, depth + 1, cells);
+    }
+}
+
+/// Local fits with their supports, and the points for the fallback distance.
+#[derive(Debug, Clone)]
+struct HrbfData<T> {
+    fits: Vec<LocalFit>,
+    supports: Octree<Support<T>, T>,
+    cloud: Octree<CloudPoint<T>, T>,
+}
+
+static HRBF_SURFACE_PARAMETERS: &[Parameter; 1] = &[Parameter {
+    name: "File Path",
+    data_type: DataType::Text,
+}];
+
+/// Smooth surface reconstructed from a point cloud with normals, using Hermite radial basis functions (HRBF).
+///
+/// The points are split into small cells, and each cell gets a local Hermite interpolant of the nearby points,
+/// which is zero at the points and has a gradient equal to the normals. The local fits are blended with compactly
+/// supported weights, so the result is a smooth function that approximates the signed distance near the points.
+///
+/// Away from the points, the function falls back to the signed distance to the closest point.
+///
+/// # Example
+///
+/// ```rust
+/// # use imlet::types::{computation::{functions::HrbfSurface, model::ImplicitModel}, geometry::Vec3};
+///
+/// // Points and outward normals of an octahedron
+/// let normals = [
+///     Vec3::x_axis(), Vec3::y_axis(), Vec3::z_axis(),
+///     Vec3::x_axis() * -1.0, Vec3::y_axis() * -1.0, Vec3::z_axis() * -1.0,
+/// ];
+/// let points: Vec<Vec3<f64>> = normals.iter().map(|&n| n * 10.0).collect();
+/// let surface = HrbfSurface::from_points(&points, &normals).unwrap();
+///
+/// let mut model = ImplicitModel::new();
+/// model.add_function("Surface", surface).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct HrbfSurface<T> {
+    /// Storing the origin of the points, if loaded from a file.
+    pub file_path: Option<String>,
+    points: Vec<Vec3<T>>,
+    normals: Vec<Vec3<T>>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    data: Option<HrbfData<T>>,
+}
+
+impl<T: ModelFloat> Default for HrbfSurface<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ModelFloat> HrbfSurface<T> {
+    /// Create a new empty surface.
+    pub fn new() -> Self {
+        Self {
+            file_path: None,
+            points: Vec::new(),
+            normals: Vec::new(),
+            data: None,
+        }
+    }
+
+    /// Reconstruct a surface from points and normals.
+    /// # Arguments
+    ///
+    /// * `points` - Points on the surface.
+    /// * `normals` - Outward normal at each point.
+    ///
+    /// # Returns
+    ///
+    /// An error if the number of normals doesn't match the number of points.
+    pub fn from_points(points: &[Vec3<T>], normals: &[Vec3<T>]) -> Result<Self, ModelError> {
+        validate_normals(points, Some(normals))?;
+
+        let mut surface = Self {
+            file_path: None,
+            points: points.to_vec(),
+            normals: normals.iter().map(|n| n.normalize()).collect(),
+            data: None,
+        };
+        surface.build();
+        Ok(surface)
+    }
+
+    /// Reconstruct a surface from an .xyz or .ply file with normals.
+    ///
+    /// See [`parse_point_cloud_file`] for the file formats.
+    pub fn from_path(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let (points, normals) = parse_point_cloud_file(file_path)?;
+        let normals = normals.ok_or_else(|| {
+            format!(
+                "Point cloud {} has no normals, which are needed for the surface reconstruction.",
+                file_path
+            )
+        })?;
+        let mut surface = Self::from_points(&points, &normals)?;
+        surface.file_path = Some(file_path.to_string());
+        Ok(surface)
+    }
+
+    /// Replace the points with the ones in a file, logging an error if the file can't be read.
+    pub fn set_points_from_file(&mut self, file_path: &str) {
+        match Self::from_path(file_path) {
+            Ok(surface) => *self = surface,
+            Err(err) => error!("{}", err),
+        }
+    }
+
+    /// Recompute the local fits from the points and normals.
+    pub fn build(&mut self) {
+        if self.points.is_empty() || self.points.len() != self.normals.len() {
+            self.data = None;
+            return;
+        }
+
+        let cloud = CloudPoint::octree(&self.points, Some(&self.normals));
+        let bounds = self.points.iter().skip(1).fold(
+            BoundingBox::new(self.points[0], self.points[0]),
+            |bounds, p| BoundingBox::new(bounds.min.min(p), bounds.max.max(p)),
+        );
+        let mut cells = Vec::new();
+        subdivide(
+            &self.points,
+            (0..self.points.len()).collect(),
+            bounds,
+            0,
+            &mut cells,
+        );
+
+        let min_fit_count = MIN_FIT_POINT_COUNT.min(self.points.len());
+        let mut fits = Vec::with_capacity(cells.len());
+        let mut supports = Vec::with_capacity(cells.len());
+        for cell in cells {
+            let center = cell.centroid();
+            let (dx, dy, dz) = cell.dimensions();
+            let diagonal = (dx * dx + dy * dy + dz * dz).sqrt();
+            let mut radius =
+                (diagonal * T::from(SUPPORT_RADIUS_FACTOR).unwrap()).max(Vec3::default_tolerance());
+
+            // Grow the support until it has enough points for a stable fit.
+            let mut neighbours = loop {
+                let mut neighbours = cloud.collect_nearby_objects(&center, radius);
+                neighbours.sort_by_key(|point| point.index);
+                neighbours.dedup_by_key(|point| point.index);
+                if neighbours.len() >= min_fit_count {
+                    break neighbours;
+                }
+                radius = radius * T::from(1.5).unwrap();
+            };
+            // Compared as f64 with a total order, so NaN input points can't break the sort.
+            neighbours.sort_by(|a, b| {
+                let distance = |position: &Vec3<T>| {
+                    position
+                        .distance_to_vec3_squared(&center)
+                        .to_f64()
+                        .unwrap_or(f64::NAN)
+                };
+                distance(&a.position).total_cmp(&distance(&b.position))
+            });
+            neighbours.truncate(MAX_FIT_POINT_COUNT);
+
+            let to_f64 = |v: Vec3<T>| v.convert::<f64>().unwrap();
+            let fit_points: Vec<(Vec3<f64>, Vec3<f64>)> = neighbours
+                .iter()
+                .map(|point| (to_f64(point.position), to_f64(point.normal)))
+                .collect();
+            match LocalFit::new(to_f64(center), radius.to_f64().unwrap(), &fit_points) {
+                Some(fit) => {
+                    supports.push(Support {
+                        center,
+                        radius,
+                        index: fits.len(),
+                    });
+                    fits.push(fit);
+                }
+                None => warn!(
+                    "Skipping a degenerate HRBF fit around {:?}.",
+                    center.convert::<f32>()
+                ),
+            }
+        }
+
+        info!(
+            "HRBF surface with {} points and {} local fits successfully built.",
+            self.points.len(),
+            fits.len()
+        );
+
+        self.data = Some(HrbfData {
+            fits,
+            supports: Octree::new()
+                .with_objects(&supports)
+                .with_max_depth(MAX_SUBDIVISION_DEPTH)
+                .with_max_leaf_size(MAX_CELL_POINT_COUNT)
+                .build(),
+            cloud,
+        });
+    }
+
+    /// Returns the points of the cloud.
+    pub fn points(&self) -> &[Vec3<T>] {
+        &self.points
+    }
+
+    /// Returns the normals of the cloud.
+    pub fn normals(&self) -> &[Vec3<T>] {
+        &self.normals
+    }
+
+    /// Return the bounds of the points.
+    pub fn bounds(&self) -> Option<BoundingBox<T>> {
+        self.data.as_ref().and_then(|data| data.cloud.bounds())
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for HrbfSurface<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let Some(data) = &self.data else {
+            return T::max_value();
+        };
+        let point = Vec3::new(x, y, z);
+        let fallback = data.cloud.signed_distance(&point).to_f64().unwrap();
+
+        let mut supports = data
+            .supports
+            .collect_nearby_objects(&point, Vec3::default_tolerance());
+        supports.sort_by_key(|support| support.index);
+        supports.dedup_by_key(|support| support.index);
+
+        let query = point.convert::<f64>().unwrap();
+        let mut weighted_sum = FALLBACK_WEIGHT * fallback;
+        let mut weight_sum = FALLBACK_WEIGHT;
+        for support in supports {
+            let fit = &data.fits[support.index];
+            let r = query.distance_to_vec3(&fit.center) / fit.scale;
+            if r < 1.0 {
+                // Wendland weight, smooth and zero at the edge of the support.
+                let weight = (1.0 - r).powi(4) * (4.0 * r + 1.0);
+                weighted_sum += weight * fit.eval(&query);
+                weight_sum += weight;
+            }
+        }
+
+        T::from(weighted_sum / weight_sum).unwrap()
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for HrbfSurface<T> {
+    fn parameters(&self) -> &[Parameter] {
+        HRBF_SURFACE_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        let mut new_file = String::new();
+        if Parameter::set_text_from_param(parameter_name, &data, "File Path", &mut new_file) {
+            self.set_points_from_file(&new_file);
+        } else {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "File Path" => {
+                if let Some(file_path) = &self.file_path {
+                    Some(Data::File(file_path.clone()))
+                } else {
+                    Some(Data::File("No file set.".to_string()))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "HrbfSurface"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_helpers::sphere_samples;
+
+    use super::*;
+
+    #[test]
+    fn test_solve_linear_system() {
+        let matrix = vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0];
+        let solution = solve_linear_system(matrix, vec![5.0, 3.0, 4.0]).unwrap();
+        for (value, expected) in solution.iter().zip([1.0, 2.0, 1.0]) {
+            assert!((value - expected).abs() < 1E-12);
+        }
+
+        assert!(solve_linear_system(vec![1.0, 2.0, 2.0, 4.0], vec![1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn test_interpolates_points_and_normals() {
+        let (points, normals) = sphere_samples(10.0, 80);
+        let fit_points: Vec<(Vec3<f64>, Vec3<f64>)> = points
+            .iter()
+            .copied()
+            .zip(normals.iter().copied())
+            .collect();
+        let fit = LocalFit::new(Vec3::origin(), 10.0, &fit_points).unwrap();
+
+        let h = 1E-5;
+        for (point, normal) in fit_points.iter().step_by(7) {
+            assert!(fit.eval(point).abs() < 1E-6);
+            let gradient = Vec3::new(
+                fit.eval(&(*point + Vec3::x_axis() * h)) - fit.eval(&(*point - Vec3::x_axis() * h)),
+                fit.eval(&(*point + Vec3::y_axis() * h)) - fit.eval(&(*point - Vec3::y_axis() * h)),
+                fit.eval(&(*point + Vec3::z_axis() * h)) - fit.eval(&(*point - Vec3::z_axis() * h)),
+            ) * (0.5 / h);
+            assert!(gradient.distance_to_vec3(normal) < 1E-4);
+        }
+    }
+
+    #[test]
+    fn test_sphere_reconstruction() {
+        let (points, normals) = sphere_samples(10.0, 600);
+        let surface = HrbfSurface::from_points(&points, &normals).unwrap();
+
+        // Points on the sphere between the samples.
+        let (test_points, _) = sphere_samples(10.0, 97);
+        for p in test_points {
+            assert!(surface.eval(p.x, p.y, p.z).abs() < 0.05);
+        }
+
+        assert!((surface.eval(0.0, 0.0, 11.0) - 1.0).abs() < 0.05);
+        assert!((surface.eval(0.0, 9.0, 0.0) + 1.0).abs() < 0.05);
+        assert!(surface.eval(0.0, 0.0, 0.0) < 0.0);
+        assert!(surface.eval(25.0, 0.0, 0.0) > 0.0);
+    }
+}
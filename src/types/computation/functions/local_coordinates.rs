@@ -0,0 +1,618 @@
+use log::error;
+use std::fmt::Debug;
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::computation::model::{Data, DataType, Parameter};
+use crate::types::computation::traits::{ImplicitComponent, ImplicitFunction, ModelFloat};
+use crate::types::geometry::{Plane, Vec3};
+use crate::utils::math_helper::normalize;
+
+/// Assign the min or max value of a remapped range, rejecting ranges of zero size.
+///
+/// Returns true if the parameter was one of the range parameters.
+fn set_range_param<T: Float>(
+    parameter_name: &str,
+    data: &Data<T>,
+    min: &mut T,
+    max: &mut T,
+) -> bool {
+    match parameter_name {
+        "Min" => {
+            let old_min = *min;
+            Parameter::set_value_from_param(parameter_name, data, "Min", min);
+            if (*min - *max).abs() < T::epsilon() {
+                error!("Min and max can't be same value.");
+                *min = old_min;
+            }
+            true
+        }
+        "Max" => {
+            let old_max = *max;
+            Parameter::set_value_from_param(parameter_name, data, "Max", max);
+            if (*min - *max).abs() < T::epsilon() {
+                error!("Min and max can't be same value.");
+                *max = old_max;
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Azimuth angle of a vector around an axis, in the range [-pi, pi].
+fn azimuth<T: Float>(d: &Vec3<T>, axis: &Vec3<T>) -> T {
+    let (u, v) = axis.orthonormal_basis();
+    d.dot(&v).atan2(d.dot(&u))
+}
+
+/// Value of a cylindrical coordinate system.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CylindricalCoordinate {
+    /// Distance from the axis.
+    Radius,
+    /// Angle around the axis in radians, in the range [-pi, pi].
+    Azimuth,
+    /// Distance along the axis from the origin.
+    Height,
+}
+
+static CYLINDRICAL_PARAMETERS: [Parameter; 5] = [
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Axis",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Coordinate",
+        data_type: DataType::Enum(&["Radius", "Azimuth", "Height"]),
+    },
+    Parameter {
+        name: "Min",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Max",
+        data_type: DataType::Value,
+    },
+];
+
+/// Function that evaluates to a cylindrical coordinate around an axis.
+///
+/// The azimuth is measured from the first vector of [`Vec3::orthonormal_basis`] of the axis.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct CylindricalValue<T> {
+    origin: Vec3<T>,
+    axis: Vec3<T>,
+    coordinate: CylindricalCoordinate,
+    min: T,
+    max: T,
+}
+
+impl<T: Float> CylindricalValue<T> {
+    /// Function returning a cylindrical coordinate value at any point.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - Point on the axis.
+    /// * `axis` - Direction of the axis.
+    /// * `coordinate` - Coordinate to return.
+    pub fn natural(origin: Vec3<T>, axis: Vec3<T>, coordinate: CylindricalCoordinate) -> Self {
+        Self::remapped(origin, axis, coordinate, T::zero(), T::one())
+    }
+
+    /// Create a function for a remapped cylindrical coordinate.
+    ///
+    /// Can be used for interpolation.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - Point on the axis.
+    /// * `axis` - Direction of the axis.
+    /// * `coordinate` - Coordinate to return.
+    /// * `min` - Coordinate value that maps to 0.
+    /// * `max` - Coordinate value that maps to 1.
+    pub fn remapped(
+        origin: Vec3<T>,
+        axis: Vec3<T>,
+        coordinate: CylindricalCoordinate,
+        min: T,
+        max: T,
+    ) -> Self {
+        Self {
+            origin,
+            axis: axis.normalize(),
+            coordinate,
+            min,
+            max,
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for CylindricalValue<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let d = Vec3::new(x, y, z) - self.origin;
+        let height = d.dot(&self.axis);
+        let value = match self.coordinate {
+            CylindricalCoordinate::Radius => (d - self.axis * height).magnitude(),
+            CylindricalCoordinate::Azimuth => azimuth(&d, &self.axis),
+            CylindricalCoordinate::Height => height,
+        };
+        normalize(value, self.min, self.max)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for CylindricalValue<T> {
+    fn parameters(&self) -> &[Parameter] {
+        &CYLINDRICAL_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(value) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Coordinate")
+        {
+            match value.as_str() {
+                "Radius" => self.coordinate = CylindricalCoordinate::Radius,
+                "Azimuth" => self.coordinate = CylindricalCoordinate::Azimuth,
+                "Height" => self.coordinate = CylindricalCoordinate::Height,
+                _ => error!("Unknown coordinate: {}", value),
+            }
+        } else if parameter_name == "Axis" {
+            let mut axis = self.axis;
+            Parameter::set_vec3_from_param(parameter_name, &data, "Axis", &mut axis);
+            if axis.magnitude() < T::epsilon() {
+                error!("Axis can't be a zero length vector.");
+            } else {
+                self.axis = axis.normalize();
+            }
+        } else if !(Parameter::set_vec3_from_param(
+            parameter_name,
+            &data,
+            "Origin",
+            &mut self.origin,
+        ) || set_range_param(parameter_name, &data, &mut self.min, &mut self.max))
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Coordinate" => match self.coordinate {
+                CylindricalCoordinate::Radius => Some(Data::EnumValue("Radius".to_string())),
+                CylindricalCoordinate::Azimuth => Some(Data::EnumValue("Azimuth".to_string())),
+                CylindricalCoordinate::Height => Some(Data::EnumValue("Height".to_string())),
+            },
+            "Min" => Some(Data::Value(self.min)),
+            "Max" => Some(Data::Value(self.max)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "CylindricalValue"
+    }
+}
+
+/// Value of a spherical coordinate system.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SphericalCoordinate {
+    /// Distance from the center.
+    Radius,
+    /// Angle from the polar axis in radians, in the range [0, pi].
+    Polar,
+    /// Angle around the polar axis in radians, in the range [-pi, pi].
+    Azimuth,
+}
+
+static SPHERICAL_PARAMETERS: [Parameter; 5] = [
+    Parameter {
+        name: "Center",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Axis",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Coordinate",
+        data_type: DataType::Enum(&["Radius", "Polar", "Azimuth"]),
+    },
+    Parameter {
+        name: "Min",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Max",
+        data_type: DataType::Value,
+    },
+];
+
+/// Function that evaluates to a spherical coordinate around a center.
+///
+/// The azimuth is measured from the first vector of [`Vec3::orthonormal_basis`] of the polar axis.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct SphericalValue<T> {
+    center: Vec3<T>,
+    axis: Vec3<T>,
+    coordinate: SphericalCoordinate,
+    min: T,
+    max: T,
+}
+
+impl<T: Float> SphericalValue<T> {
+    /// Function returning a spherical coordinate value at any point.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - Center of the coordinate system.
+    /// * `axis` - Direction of the polar axis.
+    /// * `coordinate` - Coordinate to return.
+    pub fn natural(center: Vec3<T>, axis: Vec3<T>, coordinate: SphericalCoordinate) -> Self {
+        Self::remapped(center, axis, coordinate, T::zero(), T::one())
+    }
+
+    /// Create a function for a remapped spherical coordinate.
+    ///
+    /// Can be used for interpolation.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - Center of the coordinate system.
+    /// * `axis` - Direction of the polar axis.
+    /// * `coordinate` - Coordinate to return.
+    /// * `min` - Coordinate value that maps to 0.
+    /// * `max` - Coordinate value that maps to 1.
+    pub fn remapped(
+        center: Vec3<T>,
+        axis: Vec3<T>,
+        coordinate: SphericalCoordinate,
+        min: T,
+        max: T,
+    ) -> Self {
+        Self {
+            center,
+            axis: axis.normalize(),
+            coordinate,
+            min,
+            max,
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for SphericalValue<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let d = Vec3::new(x, y, z) - self.center;
+        let radius = d.magnitude();
+        let value = match self.coordinate {
+            SphericalCoordinate::Radius => radius,
+            SphericalCoordinate::Polar => {
+                if radius > T::zero() {
+                    (d.dot(&self.axis) / radius)
+                        .clamp(-T::one(), T::one())
+                        .acos()
+                } else {
+                    T::zero()
+                }
+            }
+            SphericalCoordinate::Azimuth => azimuth(&d, &self.axis),
+        };
+        normalize(value, self.min, self.max)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for SphericalValue<T> {
+    fn parameters(&self) -> &[Parameter] {
+        &SPHERICAL_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(value) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Coordinate")
+        {
+            match value.as_str() {
+                "Radius" => self.coordinate = SphericalCoordinate::Radius,
+                "Polar" => self.coordinate = SphericalCoordinate::Polar,
+                "Azimuth" => self.coordinate = SphericalCoordinate::Azimuth,
+                _ => error!("Unknown coordinate: {}", value),
+            }
+        } else if parameter_name == "Axis" {
+            let mut axis = self.axis;
+            Parameter::set_vec3_from_param(parameter_name, &data, "Axis", &mut axis);
+            if axis.magnitude() < T::epsilon() {
+                error!("Axis can't be a zero length vector.");
+            } else {
+                self.axis = axis.normalize();
+            }
+        } else if !(Parameter::set_vec3_from_param(
+            parameter_name,
+            &data,
+            "Center",
+            &mut self.center,
+        ) || set_range_param(parameter_name, &data, &mut self.min, &mut self.max))
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Center" => Some(Data::Vec3(self.center)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Coordinate" => match self.coordinate {
+                SphericalCoordinate::Radius => Some(Data::EnumValue("Radius".to_string())),
+                SphericalCoordinate::Polar => Some(Data::EnumValue("Polar".to_string())),
+                SphericalCoordinate::Azimuth => Some(Data::EnumValue("Azimuth".to_string())),
+            },
+            "Min" => Some(Data::Value(self.min)),
+            "Max" => Some(Data::Value(self.max)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "SphericalValue"
+    }
+}
+
+/// Value of a coordinate system local to a plane.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaneCoordinate {
+    /// Signed distance from the plane, positive in the direction of the normal.
+    Distance,
+    /// Coordinate along the first in-plane axis.
+    U,
+    /// Coordinate along the second in-plane axis.
+    V,
+}
+
+static PLANE_VALUE_PARAMETERS: [Parameter; 5] = [
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Normal",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Coordinate",
+        data_type: DataType::Enum(&["Distance", "U", "V"]),
+    },
+    Parameter {
+        name: "Min",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Max",
+        data_type: DataType::Value,
+    },
+];
+
+/// Function that evaluates to a coordinate relative to a [`Plane`].
+///
+/// The in-plane axes are given by [`Vec3::orthonormal_basis`] of the plane normal.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct PlaneValue<T> {
+    plane: Plane<T>,
+    u_axis: Vec3<T>,
+    v_axis: Vec3<T>,
+    coordinate: PlaneCoordinate,
+    min: T,
+    max: T,
+}
+
+impl<T: Float> PlaneValue<T> {
+    /// Function returning a plane coordinate value at any point.
+    ///
+    /// # Arguments
+    ///
+    /// * `plane` - Plane defining the coordinate system.
+    /// * `coordinate` - Coordinate to return.
+    pub fn natural(plane: Plane<T>, coordinate: PlaneCoordinate) -> Self {
+        Self::remapped(plane, coordinate, T::zero(), T::one())
+    }
+
+    /// Create a function for a remapped plane coordinate.
+    ///
+    /// Can be used for interpolation.
+    ///
+    /// # Arguments
+    ///
+    /// * `plane` - Plane defining the coordinate system.
+    /// * `coordinate` - Coordinate to return.
+    /// * `min` - Coordinate value that maps to 0.
+    /// * `max` - Coordinate value that maps to 1.
+    pub fn remapped(plane: Plane<T>, coordinate: PlaneCoordinate, min: T, max: T) -> Self {
+        let (u_axis, v_axis) = plane.normal().orthonormal_basis();
+        Self {
+            plane,
+            u_axis,
+            v_axis,
+            coordinate,
+            min,
+            max,
+        }
+    }
+
+    /// Set the plane together with the in-plane axes.
+    fn set_plane(&mut self, plane: Plane<T>) {
+        (self.u_axis, self.v_axis) = plane.normal().orthonormal_basis();
+        self.plane = plane;
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for PlaneValue<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let d = Vec3::new(x, y, z) - self.plane.origin();
+        let value = match self.coordinate {
+            PlaneCoordinate::Distance => self.plane.signed_distance_coord(x, y, z),
+            PlaneCoordinate::U => d.dot(&self.u_axis),
+            PlaneCoordinate::V => d.dot(&self.v_axis),
+        };
+        normalize(value, self.min, self.max)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for PlaneValue<T> {
+    fn parameters(&self) -> &[Parameter] {
+        &PLANE_VALUE_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        let mut origin = self.plane.origin();
+        let mut normal = self.plane.normal();
+        if let Some(value) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Coordinate")
+        {
+            match value.as_str() {
+                "Distance" => self.coordinate = PlaneCoordinate::Distance,
+                "U" => self.coordinate = PlaneCoordinate::U,
+                "V" => self.coordinate = PlaneCoordinate::V,
+                _ => error!("Unknown coordinate: {}", value),
+            }
+        } else if Parameter::set_vec3_from_param(parameter_name, &data, "Origin", &mut origin) {
+            self.plane = Plane::new(origin, normal);
+        } else if Parameter::set_vec3_from_param(parameter_name, &data, "Normal", &mut normal) {
+            if normal.magnitude() < T::epsilon() {
+                error!("Normal can't be a zero length vector.");
+            } else {
+                self.set_plane(Plane::new(origin, normal));
+            }
+        } else if !set_range_param(parameter_name, &data, &mut self.min, &mut self.max) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.plane.origin())),
+            "Normal" => Some(Data::Vec3(self.plane.normal())),
+            "Coordinate" => match self.coordinate {
+                PlaneCoordinate::Distance => Some(Data::EnumValue("Distance".to_string())),
+                PlaneCoordinate::U => Some(Data::EnumValue("U".to_string())),
+                PlaneCoordinate::V => Some(Data::EnumValue("V".to_string())),
+            },
+            "Min" => Some(Data::Value(self.min)),
+            "Max" => Some(Data::Value(self.max)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "PlaneValue"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn test_cylindrical_values() {
+        let origin = Vec3::new(1.0, 1.0, 0.0);
+        let radius =
+            CylindricalValue::natural(origin, Vec3::z_axis(), CylindricalCoordinate::Radius);
+        let azimuth =
+            CylindricalValue::natural(origin, Vec3::z_axis(), CylindricalCoordinate::Azimuth);
+        let height = CylindricalValue::remapped(
+            origin,
+            Vec3::new(0.0, 0.0, 2.0),
+            CylindricalCoordinate::Height,
+            0.0,
+            10.0,
+        );
+
+        assert!((radius.eval(4.0, 5.0, 7.0) - 5.0).abs() < 1E-10);
+        assert!((azimuth.eval(1.0, 3.0, 7.0) - PI / 2.0).abs() < 1E-10);
+        assert!((height.eval(4.0, 5.0, 5.0) - 0.5).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_spherical_values() {
+        let center = Vec3::origin();
+        let radius = SphericalValue::natural(center, Vec3::z_axis(), SphericalCoordinate::Radius);
+        let polar =
+            SphericalValue::remapped(center, Vec3::z_axis(), SphericalCoordinate::Polar, 0.0, PI);
+        let azimuth = SphericalValue::natural(center, Vec3::z_axis(), SphericalCoordinate::Azimuth);
+
+        assert!((radius.eval(2.0, 3.0, 6.0) - 7.0).abs() < 1E-10);
+        assert!((polar.eval(0.0, 0.0, -3.0) - 1.0).abs() < 1E-10);
+        assert!((polar.eval(3.0, 0.0, 0.0) - 0.5).abs() < 1E-10);
+        assert!((azimuth.eval(-1.0, 0.0, 2.0) - PI).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_plane_values() {
+        let plane = Plane::new(Vec3::new(0.0, 0.0, 5.0), Vec3::z_axis());
+        let distance = PlaneValue::natural(plane, PlaneCoordinate::Distance);
+        let u = PlaneValue::natural(plane, PlaneCoordinate::U);
+        let v = PlaneValue::remapped(plane, PlaneCoordinate::V, -10.0, 10.0);
+
+        assert!((distance.eval(3.0, 4.0, 2.0) + 3.0).abs() < 1E-10);
+        assert!((u.eval(3.0, 4.0, 2.0) - 3.0).abs() < 1E-10);
+        assert!((v.eval(3.0, 4.0, 2.0) - 0.7).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_handle_zero_size_range() {
+        let mut radius = CylindricalValue::natural(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            CylindricalCoordinate::Radius,
+        );
+        radius.set_parameter("Max", Data::Value(0.0));
+
+        assert!((radius.eval(2.0, 0.0, 0.0) - 2.0).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_reject_zero_length_axis() {
+        let mut height = CylindricalValue::natural(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            CylindricalCoordinate::Height,
+        );
+        let mut polar =
+            SphericalValue::natural(Vec3::origin(), Vec3::z_axis(), SphericalCoordinate::Polar);
+        height.set_parameter("Axis", Data::Vec3(Vec3::origin()));
+        polar.set_parameter("Axis", Data::Vec3(Vec3::origin()));
+
+        assert!((height.eval(1.0, 2.0, 3.0) - 3.0).abs() < 1E-10);
+        assert!(polar.eval(0.0, 0.0, -3.0).is_finite());
+    }
+
+    #[test]
+    fn test_reject_zero_length_normal() {
+        let mut distance = PlaneValue::natural(Plane::xy(), PlaneCoordinate::Distance);
+        let mut u = PlaneValue::natural(Plane::xy(), PlaneCoordinate::U);
+        distance.set_parameter("Normal", Data::Vec3(Vec3::origin()));
+        u.set_parameter("Normal", Data::Vec3(Vec3::origin()));
+
+        assert!((distance.eval(1.0, 2.0, 3.0) - 3.0).abs() < 1E-10);
+        assert!((u.eval(1.0, 2.0, 3.0) - 1.0).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_plane_value_axes_follow_normal() {
+        let mut u = PlaneValue::natural(Plane::xy(), PlaneCoordinate::U);
+        u.set_parameter("Normal", Data::Vec3(Vec3::x_axis()));
+
+        assert!((u.eval(1.0, 2.0, 3.0) - 2.0).abs() < 1E-10);
+    }
+}
@@ -1,6 +1,6 @@
 use std::error::Error;
 
-use log::{error, info};
+use log::{error, info, warn};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -10,16 +10,79 @@ use crate::{
         computation::{
             model::{Data, DataType, Parameter},
             traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+            ModelError,
         },
-        geometry::{BoundingBox, Octree, Transform, Triangle, Vec3},
+        geometry::{BoundingBox, Mesh, Octree, Transform, Triangle, TriangleBvh, Vec3},
+    },
+    utils::{
+        base64,
+        io::{parse_obj_file, parse_stl_file},
     },
-    utils::io::parse_obj_file,
 };
 
 const MAX_LEAF_TRIANGLE_COUNT: usize = 24;
 const MAX_TREE_DEPTH: usize = 12;
 
-/// Distance function from a mesh loaded from a file.
+/// Method used to decide if a point is inside or outside of a mesh.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SignMethod {
+    /// Use the normal at the closest point. This is the fastest, but gives wrong signs near holes and flipped faces.
+    #[default]
+    Normal,
+    /// Use the generalized winding number, which is robust to holes and small defects but needs consistent face orientations.
+    WindingNumber,
+    /// Use the parity of ray crossings, which ignores face orientations but needs a closed mesh.
+    RayParity,
+}
+
+#[cfg(feature = "serde")]
+fn default_vec3<T: num_traits::Float>() -> Vec3<T> {
+    Vec3::origin()
+}
+
+#[cfg(feature = "serde")]
+fn default_scale<T: num_traits::Float>() -> T {
+    T::one()
+}
+
+/// Length unit of the coordinates in a mesh file.
+///
+/// Models are assumed to be in millimeters, so meshes are scaled from their unit to millimeters.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MeshUnits {
+    #[default]
+    Millimeters,
+    Centimeters,
+    Meters,
+    Inches,
+    Feet,
+}
+
+impl MeshUnits {
+    /// Returns the number of millimeters in one unit.
+    pub fn to_millimeters(&self) -> f64 {
+        match self {
+            MeshUnits::Millimeters => 1.0,
+            MeshUnits::Centimeters => 10.0,
+            MeshUnits::Meters => 1000.0,
+            MeshUnits::Inches => 25.4,
+            MeshUnits::Feet => 304.8,
+        }
+    }
+}
+
+/// Distance function from a mesh loaded from an .obj or .stl file.
+///
+/// The mesh is placed in the model before the distance data is built. The placement is applied in this order:
+/// 1. Flip of the y and z axes, for meshes with y as up-direction.
+/// 2. Scaling from the [`MeshUnits`] of the file to millimeters, and by the uniform scale.
+/// 3. Centering at the origin, if enabled.
+/// 4. Rotation and translation, as a [`Transform`].
+///
+/// The distance is signed using the normal at the closest point by default.
+/// For scanned or imperfect meshes, a more robust [`SignMethod`] can be selected.
 ///
 /// # Example
 ///
@@ -38,15 +101,59 @@ const MAX_TREE_DEPTH: usize = 12;
 /// let mesh_tag = model.add_function("Mesh", mesh_file).unwrap();
 ///
 /// ```
+///
+/// To place a mesh, set the placement fields and rebuild it.
+///
+/// ```rust
+/// # use imlet::types::{computation::functions::{MeshFile, MeshUnits}, geometry::Vec3};
+///
+/// let mut mesh_file = MeshFile::<f64>::new();
+/// mesh_file.file_path = Some("assets/geometry/bunny.obj".to_string());
+/// mesh_file.units = MeshUnits::Centimeters;
+/// mesh_file.translation = Vec3::new(0.0, 0.0, 50.0);
+/// mesh_file.build().unwrap();
+/// ```
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "T: num_traits::Float + Deserialize<'de>"))
+)]
 pub struct MeshFile<T> {
     /// Storing the origin of the file.
     pub file_path: Option<String>,
     /// Option to center the geometry
     pub center: bool,
+    /// Translation applied after the rotation.
+    #[cfg_attr(feature = "serde", serde(default = "default_vec3"))]
+    pub translation: Vec3<T>,
+    /// Rotation around the x, y and z axes in radians, as for a [`Transform`].
+    #[cfg_attr(feature = "serde", serde(default = "default_vec3"))]
+    pub rotation: Vec3<T>,
+    /// Uniform scale factor, applied in addition to the unit scaling.
+    #[cfg_attr(feature = "serde", serde(default = "default_scale"))]
+    pub scale: T,
+    /// Option to flip the y and z directions, for meshes with y as up-direction.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flip_yz: bool,
+    /// Unit of the coordinates in the file.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub units: MeshUnits,
+    /// Method used to compute the sign of the distance.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sign_method: SignMethod,
+    /// Option to store the mesh inside of serialized models.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub embed_geometry: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    embedded_geometry: Option<EmbeddedMesh>,
     /// Geometry to use for signed distance computation
     #[cfg_attr(feature = "serde", serde(skip_serializing))]
     pub geometry_data: Option<Octree<Triangle<T>, T>>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    sign_data: Option<TriangleBvh<T>>,
 }
 
 impl<T: ModelFloat> Default for MeshFile<T> {
@@ -61,51 +168,185 @@ impl<T: ModelFloat> MeshFile<T> {
         Self {
             file_path: None,
             center: false,
+            translation: Vec3::origin(),
+            rotation: Vec3::origin(),
+            scale: T::one(),
+            flip_yz: false,
+            units: MeshUnits::Millimeters,
+            sign_method: SignMethod::Normal,
+            embed_geometry: false,
+            embedded_geometry: None,
             geometry_data: None,
+            sign_data: None,
         }
     }
 
-    /// Rebuild the internal octree based on the file path
-    pub fn build(&mut self) {
-        if let Some(file_path) = self.file_path.clone() {
-            self.set_mesh_from_file(&file_path);
-        }
+    /// Create a new distance function from a mesh.
+    ///
+    /// As there is no file to read the mesh from, the mesh is always embedded when the model is serialized.
+    /// # Arguments
+    ///
+    /// * `mesh` - Mesh to compute the distance to.
+    pub fn from_mesh(mesh: &Mesh<T>) -> Self {
+        let mut mesh_file = Self::new();
+        mesh_file.embed_geometry = true;
+        mesh_file.embedded_geometry = Some(EmbeddedMesh::encode(mesh));
+        mesh_file.set_geometry(mesh.clone());
+        mesh_file
     }
 
-    /// Create a new custom sdf from a mesh loaded from an obj file.
+    /// Create a new custom sdf from a mesh loaded from an .obj or .stl file.
     pub fn from_path(file_path: &str) -> Result<Self, Box<dyn Error>> {
-        let mesh = parse_obj_file(file_path, false, false)?;
-        let octree = mesh.compute_octree(MAX_TREE_DEPTH, MAX_LEAF_TRIANGLE_COUNT);
-        Ok(Self {
-            file_path: Some(file_path.to_string()),
-            center: false,
-            geometry_data: Some(octree),
-        })
+        let mut mesh_file = Self::new();
+        mesh_file.file_path = Some(file_path.to_string());
+        mesh_file.build()?;
+        Ok(mesh_file)
     }
 
-    pub fn set_mesh_from_file(&mut self, file_path: &str) {
-        let parse_result = parse_obj_file::<T>(file_path, false, false);
+    /// Set the method used to compute the sign of the distance.
+    pub fn with_sign_method(mut self, sign_method: SignMethod) -> Self {
+        self.set_sign_method(sign_method);
+        self
+    }
 
-        match parse_result {
-            Ok(mut mesh) => {
-                if self.center {
-                    let translation = mesh.centroid() * -T::one();
-                    mesh.transform_self_par(Transform::translation(translation));
-                }
+    /// Set the method used to compute the sign of the distance, and build the data it needs.
+    pub fn set_sign_method(&mut self, sign_method: SignMethod) {
+        self.sign_method = sign_method;
+        self.update_sign_data();
+    }
 
-                let bounds = mesh.bounds();
-                info!(
-                    "Loaded mesh with bounds [{:?},{:?}]",
-                    bounds.min.convert::<f32>(),
-                    bounds.max.convert::<f32>()
-                );
-                let octree = mesh.compute_octree(MAX_TREE_DEPTH, MAX_LEAF_TRIANGLE_COUNT);
-                self.geometry_data = Some(octree);
-                self.file_path = Some(file_path.to_string());
-            }
-            Err(err) => {
-                error!("{}", err);
+    fn update_sign_data(&mut self) {
+        self.sign_data = match self.sign_method {
+            SignMethod::Normal => None,
+            SignMethod::WindingNumber | SignMethod::RayParity => self
+                .geometry_data
+                .as_ref()
+                .map(|tree| TriangleBvh::new(tree.objects())),
+        };
+    }
+
+    /// Set whether the mesh is stored inside of serialized models, so they can be loaded without the original file.
+    ///
+    /// # Returns
+    ///
+    /// An error if the mesh has to be embedded but the file can't be read.
+    pub fn set_embed_geometry(&mut self, embed_geometry: bool) -> Result<(), ModelError> {
+        if embed_geometry {
+            if self.embedded_geometry.is_none() {
+                if let Some(file_path) = &self.file_path {
+                    let mesh = read_mesh_file::<T>(file_path)?;
+                    self.embedded_geometry = Some(EmbeddedMesh::encode(&mesh));
+                }
             }
+        } else if self.file_path.is_some() {
+            self.embedded_geometry = None;
+        } else if self.embedded_geometry.is_some() {
+            warn!("Mesh without a file is always embedded.");
+            return Ok(());
+        }
+        self.embed_geometry = embed_geometry;
+        Ok(())
+    }
+
+    /// Rebuild the internal octree from the embedded mesh, or from the file if the mesh is not embedded.
+    ///
+    /// # Returns
+    ///
+    /// An error if the mesh is not embedded and the file can't be read.
+    pub fn build(&mut self) -> Result<(), ModelError> {
+        let Some(mesh) = self.source_mesh()? else {
+            return Ok(());
+        };
+        if self.embed_geometry && self.embedded_geometry.is_none() {
+            self.embedded_geometry = Some(EmbeddedMesh::encode(&mesh));
+        }
+        self.set_geometry(mesh);
+        Ok(())
+    }
+
+    /// Rebuild the internal octree, logging an error if it fails.
+    fn rebuild(&mut self) {
+        if let Err(err) = self.build() {
+            error!("{}", err);
+        }
+    }
+
+    /// Returns the mesh with the placement applied.
+    ///
+    /// # Returns
+    ///
+    /// An error if the mesh is not embedded and the file can't be read, or if no mesh is set.
+    pub fn mesh(&self) -> Result<Mesh<T>, ModelError> {
+        let mesh = self
+            .source_mesh()?
+            .ok_or_else(|| ModelError::Custom("No mesh file set.".to_string()))?;
+        Ok(self.place(mesh))
+    }
+
+    /// Whether the placement changes the mesh beyond the optional centering.
+    pub(super) fn is_placed(&self) -> bool {
+        self.translation.magnitude() > T::zero()
+            || self.rotation.magnitude() > T::zero()
+            || self.scale != T::one()
+            || self.flip_yz
+            || self.units != MeshUnits::Millimeters
+    }
+
+    fn source_mesh(&self) -> Result<Option<Mesh<T>>, ModelError> {
+        match (&self.embedded_geometry, &self.file_path) {
+            (Some(embedded), _) => embedded.decode().map(Some),
+            (None, Some(file_path)) => read_mesh_file(file_path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    fn place(&self, mesh: Mesh<T>) -> Mesh<T> {
+        let scale = self.scale * T::from(self.units.to_millimeters()).unwrap();
+        let vertices: Vec<Vec3<T>> = mesh
+            .vertices()
+            .iter()
+            .map(|v| {
+                let v = if self.flip_yz {
+                    Vec3::new(v.x, -v.z, v.y)
+                } else {
+                    *v
+                };
+                v * scale
+            })
+            .collect();
+
+        let mut placed = Mesh::new();
+        placed.add_vertices(&vertices);
+        placed.add_faces(mesh.faces());
+        if self.center {
+            let translation = placed.centroid() * -T::one();
+            placed.transform_self_par(Transform::translation(translation));
+        }
+        placed.transform_self_par(Transform::new(self.translation, self.rotation));
+        placed
+    }
+
+    fn set_geometry(&mut self, mesh: Mesh<T>) {
+        let mesh = self.place(mesh);
+        let bounds = mesh.bounds();
+        info!(
+            "Loaded mesh with bounds [{:?},{:?}]",
+            bounds.min.convert::<f32>(),
+            bounds.max.convert::<f32>()
+        );
+        self.geometry_data = Some(mesh.compute_octree(MAX_TREE_DEPTH, MAX_LEAF_TRIANGLE_COUNT));
+        self.update_sign_data();
+    }
+
+    /// Replace the mesh with the one in a file, logging an error if the file can't be read.
+    pub fn set_mesh_from_file(&mut self, file_path: &str) {
+        let previous_file = self.file_path.replace(file_path.to_string());
+        let previous_embedded = self.embedded_geometry.take();
+
+        if let Err(err) = self.build() {
+            error!("{}", err);
+            self.file_path = previous_file;
+            self.embedded_geometry = previous_embedded;
         }
     }
 
@@ -115,6 +356,95 @@ impl<T: ModelFloat> MeshFile<T> {
     }
 }
 
+/// Read a mesh from an .obj or .stl file, depending on the extension.
+pub(super) fn read_mesh_file<T: ModelFloat>(file_path: &str) -> Result<Mesh<T>, ModelError> {
+    let is_stl = std::path::Path::new(file_path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("stl"));
+    let mesh = if is_stl {
+        parse_stl_file(file_path, false)
+    } else {
+        parse_obj_file(file_path, false, false).map_err(Into::into)
+    };
+    mesh.map_err(|err| ModelError::FileRead {
+        path: file_path.to_string(),
+        message: err.to_string(),
+    })
+}
+
+/// Mesh stored inside of a serialized model, as base64 encoded little endian binary data.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+struct EmbeddedMesh {
+    /// Vertex coordinates as 64 bit floats.
+    vertices: String,
+    /// Vertex indices of the faces as 32 bit unsigned integers.
+    faces: String,
+}
+
+impl EmbeddedMesh {
+    fn encode<T: ModelFloat>(mesh: &Mesh<T>) -> Self {
+        let vertices: Vec<u8> = mesh
+            .vertices()
+            .iter()
+            .flat_map(|v| [v.x, v.y, v.z])
+            .flat_map(|value| value.to_f64().unwrap().to_le_bytes())
+            .collect();
+        let faces: Vec<u8> = mesh
+            .faces()
+            .iter()
+            .flatten()
+            .flat_map(|&index| (index as u32).to_le_bytes())
+            .collect();
+
+        Self {
+            vertices: base64::encode(&vertices),
+            faces: base64::encode(&faces),
+        }
+    }
+
+    fn decode<T: ModelFloat>(&self) -> Result<Mesh<T>, ModelError> {
+        let invalid =
+            |message: &str| ModelError::Custom(format!("Invalid embedded mesh. {message}"));
+        let vertices = base64::decode(&self.vertices).map_err(|err| invalid(&err))?;
+        let faces = base64::decode(&self.faces).map_err(|err| invalid(&err))?;
+        if vertices.len() % 24 != 0 || faces.len() % 12 != 0 {
+            return Err(invalid("Data size doesn't match whole vertices and faces."));
+        }
+
+        let vertices: Vec<Vec3<T>> = vertices
+            .chunks_exact(24)
+            .map(|chunk| {
+                let value = |i: usize| {
+                    let bytes: [u8; 8] = chunk[8 * i..8 * i + 8].try_into().unwrap();
+                    T::from(f64::from_le_bytes(bytes)).unwrap()
+                };
+                Vec3::new(value(0), value(1), value(2))
+            })
+            .collect();
+        let faces = faces
+            .chunks_exact(12)
+            .map(|chunk| {
+                let index = |i: usize| {
+                    let bytes: [u8; 4] = chunk[4 * i..4 * i + 4].try_into().unwrap();
+                    u32::from_le_bytes(bytes) as usize
+                };
+                let face = [index(0), index(1), index(2)];
+                if face.iter().all(|&index| index < vertices.len()) {
+                    Ok(face)
+                } else {
+                    Err(invalid("Face refers to a missing vertex."))
+                }
+            })
+            .collect::<Result<Vec<[usize; 3]>, ModelError>>()?;
+
+        let mut mesh = Mesh::new();
+        mesh.add_vertices(&vertices);
+        mesh.add_faces(&faces);
+        Ok(mesh)
+    }
+}
+
 static MESH_FILE_PARAMETERS: &[Parameter] = &[
     Parameter {
         name: "File Path",
@@ -124,14 +454,57 @@ static MESH_FILE_PARAMETERS: &[Parameter] = &[
         name: "Center Geometry",
         data_type: DataType::Boolean,
     },
+    Parameter {
+        name: "Sign Method",
+        data_type: DataType::Enum(&["Normal", "Winding Number", "Ray Parity"]),
+    },
+    Parameter {
+        name: "Embed Geometry",
+        data_type: DataType::Boolean,
+    },
+    Parameter {
+        name: "Translation",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Rotation",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Scale",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Flip YZ",
+        data_type: DataType::Boolean,
+    },
+    Parameter {
+        name: "Units",
+        data_type: DataType::Enum(&["Millimeters", "Centimeters", "Meters", "Inches", "Feet"]),
+    },
 ];
 
 impl<T: ModelFloat> ImplicitFunction<T> for MeshFile<T> {
     fn eval(&self, x: T, y: T, z: T) -> T {
-        if let Some(geometry_data) = &self.geometry_data {
-            geometry_data.signed_distance(&Vec3::new(x, y, z))
+        let Some(geometry_data) = &self.geometry_data else {
+            return T::zero();
+        };
+        let point = Vec3::new(x, y, z);
+
+        let inside = match (&self.sign_method, &self.sign_data) {
+            (SignMethod::WindingNumber, Some(bvh)) => {
+                bvh.winding_number(&point) > T::from(0.5).unwrap()
+            }
+            (SignMethod::RayParity, Some(bvh)) => bvh.is_inside_by_parity(&point),
+            _ => return geometry_data.signed_distance(&point),
+        };
+        let distance = geometry_data
+            .closest_point(&point)
+            .map_or(T::zero(), |(closest, _)| closest.distance_to_vec3(&point));
+        if inside {
+            -distance
         } else {
-            T::zero()
+            distance
         }
     }
 }
@@ -143,6 +516,8 @@ impl<T: ModelFloat> ImplicitComponent<T> for MeshFile<T> {
 
     fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
         let mut new_file = String::new();
+        let mut embed_geometry = self.embed_geometry;
+        let mut scale = self.scale;
         if Parameter::set_text_from_param(parameter_name, &data, "File Path", &mut new_file) {
             self.set_mesh_from_file(&new_file);
         } else if Parameter::set_bool_from_param(
@@ -150,10 +525,65 @@ impl<T: ModelFloat> ImplicitComponent<T> for MeshFile<T> {
             &data,
             "Center Geometry",
             &mut self.center,
+        ) || Parameter::set_vec3_from_param(
+            parameter_name,
+            &data,
+            "Translation",
+            &mut self.translation,
+        ) || Parameter::set_vec3_from_param(
+            parameter_name,
+            &data,
+            "Rotation",
+            &mut self.rotation,
+        ) || Parameter::set_bool_from_param(
+            parameter_name,
+            &data,
+            "Flip YZ",
+            &mut self.flip_yz,
+        ) {
+            self.rebuild();
+        } else if Parameter::set_value_from_param(parameter_name, &data, "Scale", &mut scale) {
+            if scale > T::zero() {
+                self.scale = scale;
+                self.rebuild();
+            } else {
+                error!("Scale must be larger than zero.");
+            }
+        } else if let Some(value) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Units")
+        {
+            self.units = match value.as_str() {
+                "Millimeters" => MeshUnits::Millimeters,
+                "Centimeters" => MeshUnits::Centimeters,
+                "Meters" => MeshUnits::Meters,
+                "Inches" => MeshUnits::Inches,
+                "Feet" => MeshUnits::Feet,
+                _ => {
+                    error!("Unknown units: {}", value);
+                    return;
+                }
+            };
+            self.rebuild();
+        } else if let Some(value) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Sign Method")
+        {
+            match value.as_str() {
+                "Normal" => self.set_sign_method(SignMethod::Normal),
+                "Winding Number" => self.set_sign_method(SignMethod::WindingNumber),
+                "Ray Parity" => self.set_sign_method(SignMethod::RayParity),
+                _ => error!("Unknown sign method: {}", value),
+            }
+        } else if Parameter::set_bool_from_param(
+            parameter_name,
+            &data,
+            "Embed Geometry",
+            &mut embed_geometry,
         ) {
-            if let Some(file_path) = self.file_path.clone() {
-                self.set_mesh_from_file(&file_path);
+            if let Err(err) = self.set_embed_geometry(embed_geometry) {
+                error!("{}", err);
             }
+        } else {
+            error!("Unknown parameter name: {}", parameter_name);
         }
     }
 
@@ -167,6 +597,20 @@ impl<T: ModelFloat> ImplicitComponent<T> for MeshFile<T> {
                 }
             }
             "Center Geometry" => Some(Data::<T>::Boolean(self.center)),
+            "Sign Method" => Some(Data::EnumValue(
+                match self.sign_method {
+                    SignMethod::Normal => "Normal",
+                    SignMethod::WindingNumber => "Winding Number",
+                    SignMethod::RayParity => "Ray Parity",
+                }
+                .to_string(),
+            )),
+            "Embed Geometry" => Some(Data::Boolean(self.embed_geometry)),
+            "Translation" => Some(Data::Vec3(self.translation)),
+            "Rotation" => Some(Data::Vec3(self.rotation)),
+            "Scale" => Some(Data::Value(self.scale)),
+            "Flip YZ" => Some(Data::Boolean(self.flip_yz)),
+            "Units" => Some(Data::EnumValue(format!("{:?}", self.units))),
             _ => None,
         }
     }
@@ -175,3 +619,215 @@ impl<T: ModelFloat> ImplicitComponent<T> for MeshFile<T> {
         "MeshFile"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Box from {0, 0, 0} to {20, 20, 20}, with one half of the top face removed, or one half of the side face at x = 20 flipped.
+    fn damaged_box(flip: bool) -> Mesh<f64> {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let mut faces = mesh.faces().to_vec();
+        let find = |faces: &[[usize; 3]], check: &dyn Fn(&Vec3<f64>) -> bool| {
+            faces
+                .iter()
+                .position(|f| f.iter().all(|&i| check(&mesh.vertices()[i])))
+                .unwrap()
+        };
+        if flip {
+            let side = find(&faces, &|v| v.x == 20.0);
+            faces[side].swap(0, 1);
+        } else {
+            let top = find(&faces, &|v| v.z == 20.0);
+            faces.remove(top);
+        }
+
+        let mut damaged = Mesh::new();
+        damaged.add_vertices(mesh.vertices());
+        damaged.add_faces(&faces);
+        damaged
+    }
+
+    #[test]
+    fn test_sign_methods_on_closed_mesh() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        for sign_method in [
+            SignMethod::Normal,
+            SignMethod::WindingNumber,
+            SignMethod::RayParity,
+        ] {
+            let mesh_file = MeshFile::from_mesh(&mesh).with_sign_method(sign_method);
+            assert!((mesh_file.eval(10.0, 10.0, 10.0) + 10.0).abs() < 1E-9);
+            assert!((mesh_file.eval(10.0, 10.0, 25.0) - 5.0).abs() < 1E-9);
+            assert!((mesh_file.eval(18.0, 11.0, 7.0) + 2.0).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_winding_number_with_hole() {
+        let mesh_file =
+            MeshFile::from_mesh(&damaged_box(false)).with_sign_method(SignMethod::WindingNumber);
+
+        assert!((mesh_file.eval(10.0, 10.0, 10.0) + 10.0).abs() < 1E-9);
+        assert!(mesh_file.eval(10.0, 10.0, 19.0) < 0.0);
+        assert!(mesh_file.eval(10.0, 10.0, 21.0) > 0.0);
+        assert!(mesh_file.eval(10.0, 10.0, 25.0) > 0.0);
+    }
+
+    #[test]
+    fn test_ray_parity_with_flipped_face() {
+        let mesh = damaged_box(true);
+        let normal = MeshFile::from_mesh(&mesh);
+        let ray_parity = MeshFile::from_mesh(&mesh).with_sign_method(SignMethod::RayParity);
+
+        // Points inside, close to each half of the side face.
+        let points = [(19.0, 5.0, 10.0), (19.0, 15.0, 10.0)];
+        for (x, y, z) in points {
+            assert!((ray_parity.eval(x, y, z) + 1.0).abs() < 1E-9);
+            assert!((ray_parity.eval(x + 2.0, y, z) - 1.0).abs() < 1E-9);
+        }
+        assert!(
+            points.iter().any(|&(x, y, z)| normal.eval(x, y, z) > 0.0),
+            "The closest point normal should fail on the flipped face."
+        );
+    }
+
+    #[test]
+    fn test_sign_method_parameter() {
+        let mut mesh_file = MeshFile::from_mesh(&damaged_box(true));
+        mesh_file.set_parameter("Sign Method", Data::EnumValue("Ray Parity".to_string()));
+
+        assert_eq!(mesh_file.sign_method, SignMethod::RayParity);
+        assert!(matches!(
+            mesh_file.read_parameter("Sign Method"),
+            Some(Data::EnumValue(value)) if value == "Ray Parity"
+        ));
+        assert!(mesh_file.eval(19.0, 5.0, 10.0) < 0.0);
+        assert!(mesh_file.eval(19.0, 15.0, 10.0) < 0.0);
+    }
+
+    #[test]
+    fn test_embedded_mesh_round_trip() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let decoded: Mesh<f64> = EmbeddedMesh::encode(&mesh).decode().unwrap();
+
+        assert_eq!(decoded.faces(), mesh.faces());
+        for (a, b) in decoded.vertices().iter().zip(mesh.vertices()) {
+            assert_eq!(a.distance_to_vec3(b), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_placement() {
+        let mut mesh_file = MeshFile::<f64>::new();
+        mesh_file.file_path = Some("assets/geometry/box.obj".to_string());
+        mesh_file.units = MeshUnits::Centimeters;
+        mesh_file.scale = 0.5;
+        mesh_file.flip_yz = true;
+        mesh_file.translation = Vec3::new(10.0, 0.0, 0.0);
+        mesh_file.build().unwrap();
+
+        // The box spans 0 to 100 mm, with y and z flipped and moved along x.
+        let bounds = mesh_file.mesh().unwrap().bounds();
+        assert!(bounds.min.distance_to_vec3(&Vec3::new(10.0, -100.0, 0.0)) < 1E-9);
+        assert!(bounds.max.distance_to_vec3(&Vec3::new(110.0, 0.0, 100.0)) < 1E-9);
+        assert!((mesh_file.eval(60.0, -50.0, 50.0) + 50.0).abs() < 1E-9);
+        assert!((mesh_file.eval(60.0, -50.0, 105.0) - 5.0).abs() < 1E-9);
+
+        mesh_file.set_parameter("Units", Data::EnumValue("Millimeters".to_string()));
+        mesh_file.set_parameter("Flip YZ", Data::Boolean(false));
+        mesh_file.set_parameter("Translation", Data::Vec3(Vec3::origin()));
+        mesh_file.set_parameter("Scale", Data::Value(-1.0));
+        mesh_file.set_parameter(
+            "Rotation",
+            Data::Vec3(Vec3::new(0.0, 0.0, std::f64::consts::FRAC_PI_2)),
+        );
+
+        // The box spans 0 to 10 mm, rotated around the z axis.
+        assert_eq!(mesh_file.scale, 0.5);
+        assert!((mesh_file.eval(-5.0, 5.0, 5.0) + 5.0).abs() < 1E-9);
+        assert!((mesh_file.eval(5.0, 5.0, 5.0) - 5.0).abs() < 1E-9);
+        assert!(matches!(
+            mesh_file.read_parameter("Units"),
+            Some(Data::EnumValue(value)) if value == "Millimeters"
+        ));
+    }
+
+    #[test]
+    fn test_stl_file() {
+        use crate::utils::{
+            io::{write_stl_file, StlFormat},
+            test_helpers::temp_file_name,
+        };
+
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let file_name = temp_file_name("box");
+        write_stl_file(&mesh, &file_name, StlFormat::Binary).unwrap();
+        let file_path = format!("{}.stl", file_name);
+        let mesh_file = MeshFile::<f64>::from_path(&file_path);
+        let stl_mesh = mesh_file.as_ref().ok().map(|mesh_file| mesh_file.mesh());
+        std::fs::remove_file(&file_path).unwrap();
+
+        let mesh_file = mesh_file.unwrap();
+        let stl_mesh = stl_mesh.unwrap().unwrap();
+        assert_eq!(stl_mesh.num_vertices(), mesh.num_vertices());
+        assert_eq!(stl_mesh.num_faces(), mesh.num_faces());
+        assert!((mesh_file.eval(10.0, 10.0, 10.0) + 10.0).abs() < 1E-6);
+        assert!((mesh_file.eval(10.0, 10.0, 25.0) - 5.0).abs() < 1E-6);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let Err(err) = MeshFile::<f64>::from_path("assets/geometry/missing.obj") else {
+            panic!("Reading a missing file should fail.");
+        };
+        assert!(err.to_string().contains("assets/geometry/missing.obj"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_embedded_geometry() {
+        use crate::types::computation::model::ImplicitModel;
+
+        let model_json = |embed_geometry: bool| {
+            let mut mesh_file = MeshFile::<f64>::from_path("assets/geometry/box.obj").unwrap();
+            mesh_file.set_embed_geometry(embed_geometry).unwrap();
+            let mut model = ImplicitModel::new();
+            model.add_function("Mesh", mesh_file).unwrap();
+            // Point the model to a file that doesn't exist.
+            serde_json::to_string(&model)
+                .unwrap()
+                .replace("assets/geometry/box.obj", "assets/geometry/missing.obj")
+        };
+
+        let model: ImplicitModel<f64> = serde_json::from_str(&model_json(true)).unwrap();
+        let value = model.evaluate_at("Mesh", 10.0, 10.0, 10.0).unwrap();
+        assert!((value + 10.0).abs() < 1E-9);
+
+        let Err(err) = serde_json::from_str::<ImplicitModel<f64>>(&model_json(false)) else {
+            panic!("Reading a model with a missing file should fail.");
+        };
+        assert!(err.to_string().contains("Failed to read file"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_placement() {
+        use crate::types::computation::model::ImplicitModel;
+
+        let mut mesh_file = MeshFile::<f64>::new();
+        mesh_file.file_path = Some("assets/geometry/box.obj".to_string());
+        mesh_file.units = MeshUnits::Meters;
+        mesh_file.scale = 0.01;
+        mesh_file.center = true;
+        mesh_file.translation = Vec3::new(0.0, 0.0, 100.0);
+        mesh_file.build().unwrap();
+        let mut model = ImplicitModel::new();
+        model.add_function("Mesh", mesh_file).unwrap();
+
+        let json = serde_json::to_string(&model).unwrap();
+        let model: ImplicitModel<f64> = serde_json::from_str(&json).unwrap();
+        let value = model.evaluate_at("Mesh", 0.0, 0.0, 100.0).unwrap();
+        assert!((value + 100.0).abs() < 1E-9);
+    }
+}
@@ -0,0 +1,374 @@
+use std::error::Error;
+
+use log::{error, info};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+    },
+    geometry::{BoundingBox, Line, Mesh, Octree, Transform, Vec3},
+};
+
+use super::{mesh_file::read_mesh_file, MeshFile};
+
+const MAX_LEAF_STRUT_COUNT: usize = 16;
+const MAX_TREE_DEPTH: usize = 10;
+
+/// Struts and nodes of the lattice stored in octrees.
+#[derive(Debug, Clone)]
+struct MeshLatticeData<T> {
+    struts: Octree<Line<T>, T>,
+    nodes: Octree<Vec3<T>, T>,
+}
+
+/// Distance function for a beam lattice following the edges of a triangle mesh.
+///
+/// Each unique edge of the mesh becomes a capsule with the strut radius. Optionally, a sphere is placed at each vertex,
+/// which can be used to reinforce the nodes where struts meet. This gives conformal lattices from a remeshed surface or from the faces of a tetrahedral mesh.
+///
+/// The struts and nodes are stored in [`Octree`]s, so evaluation only considers the edges near the query point.
+///
+/// # Example
+///
+/// ```rust
+/// # use imlet::types::{computation::{functions::MeshLattice, model::ImplicitModel}};
+///
+/// let lattice = MeshLattice::<f64>::from_path("assets/geometry/box.obj", 1.0)
+///     .unwrap()
+///     .with_node_radius(2.0);
+///
+/// let mut model = ImplicitModel::new();
+/// model.add_function("Lattice", lattice).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MeshLattice<T> {
+    /// Storing the origin of the mesh, if loaded from a file.
+    pub file_path: Option<String>,
+    /// Option to center the geometry when loaded from a file.
+    pub center: bool,
+    /// Radius of the struts along the mesh edges.
+    pub strut_radius: T,
+    /// Radius of the spheres at the mesh vertices. Nodes are not added if zero.
+    pub node_radius: T,
+    vertices: Vec<Vec3<T>>,
+    edges: Vec<[usize; 2]>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    data: Option<MeshLatticeData<T>>,
+}
+
+impl<T: ModelFloat> MeshLattice<T> {
+    /// Create a new lattice without any geometry.
+    /// # Arguments
+    ///
+    /// * `strut_radius` - Radius of the struts along the mesh edges.
+    pub fn new(strut_radius: T) -> Self {
+        Self {
+            file_path: None,
+            center: false,
+            strut_radius,
+            node_radius: T::zero(),
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            data: None,
+        }
+    }
+
+    /// Create a lattice from the edges of a mesh.
+    /// # Arguments
+    ///
+    /// * `mesh` - Mesh whose edges are turned into struts.
+    /// * `strut_radius` - Radius of the struts along the mesh edges.
+    pub fn from_mesh(mesh: &Mesh<T>, strut_radius: T) -> Self {
+        let mut lattice = Self::new(strut_radius);
+        lattice.set_mesh(mesh);
+        lattice
+    }
+
+    /// Create a lattice from the edges of a mesh loaded from an .obj or .stl file.
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the mesh file.
+    /// * `strut_radius` - Radius of the struts along the mesh edges.
+    pub fn from_path(file_path: &str, strut_radius: T) -> Result<Self, Box<dyn Error>> {
+        let mesh = read_mesh_file(file_path)?;
+        let mut lattice = Self::from_mesh(&mesh, strut_radius);
+        lattice.file_path = Some(file_path.to_string());
+        Ok(lattice)
+    }
+
+    /// Create a lattice from the edges of the mesh referenced by a [`MeshFile`], using the same placement.
+    ///
+    /// The file path is only kept if the mesh file is at most centered, as the lattice can't reload the file with the other placement parameters.
+    /// # Arguments
+    ///
+    /// * `mesh_file` - Mesh file which references the mesh.
+    /// * `strut_radius` - Radius of the struts along the mesh edges.
+    pub fn from_mesh_file(
+        mesh_file: &MeshFile<T>,
+        strut_radius: T,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut lattice = Self::from_mesh(&mesh_file.mesh()?, strut_radius);
+        if !mesh_file.is_placed() {
+            lattice.file_path = mesh_file.file_path.clone();
+            lattice.center = mesh_file.center;
+        }
+        Ok(lattice)
+    }
+
+    /// Set the radius of the spheres at the mesh vertices.
+    pub fn with_node_radius(mut self, node_radius: T) -> Self {
+        self.node_radius = node_radius;
+        self
+    }
+
+    /// Replace the lattice geometry with the edges of a mesh.
+    pub fn set_mesh(&mut self, mesh: &Mesh<T>) {
+        self.vertices = mesh.vertices().to_vec();
+        self.edges = mesh.edge_indices();
+        self.build();
+    }
+
+    /// Replace the lattice geometry with the edges of a mesh loaded from an .obj or .stl file, logging an error if the file can't be read.
+    pub fn set_mesh_from_file(&mut self, file_path: &str) {
+        if let Err(err) = self.load_mesh(file_path) {
+            error!("{}", err);
+        }
+    }
+
+    fn load_mesh(&mut self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let mut mesh = read_mesh_file::<T>(file_path)?;
+        if self.center {
+            let translation = mesh.centroid() * -T::one();
+            mesh.transform_self_par(Transform::translation(translation));
+        }
+        self.set_mesh(&mesh);
+        self.file_path = Some(file_path.to_string());
+        info!(
+            "Loaded lattice with {} struts from {}",
+            self.edges.len(),
+            file_path
+        );
+        Ok(())
+    }
+
+    /// Rebuild the internal octrees from the vertices and edges.
+    ///
+    /// Edges which refer to missing vertices are skipped.
+    pub fn build(&mut self) {
+        let struts: Vec<Line<T>> = self
+            .edges
+            .iter()
+            .filter_map(|&[i, j]| {
+                let (start, end) = (self.vertices.get(i)?, self.vertices.get(j)?);
+                Some(Line::new(*start, *end))
+            })
+            .collect();
+
+        self.data = if struts.is_empty() {
+            None
+        } else {
+            Some(MeshLatticeData {
+                struts: Octree::new()
+                    .with_objects(&struts)
+                    .with_max_depth(MAX_TREE_DEPTH)
+                    .with_max_leaf_size(MAX_LEAF_STRUT_COUNT)
+                    .build(),
+                nodes: Octree::new()
+                    .with_objects(&self.vertices)
+                    .with_max_depth(MAX_TREE_DEPTH)
+                    .with_max_leaf_size(MAX_LEAF_STRUT_COUNT)
+                    .build(),
+            })
+        };
+    }
+
+    /// Number of struts in the lattice.
+    pub fn num_struts(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Return the bounds of the lattice centerlines.
+    pub fn bounds(&self) -> Option<BoundingBox<T>> {
+        self.data.as_ref().and_then(|data| data.struts.bounds())
+    }
+}
+
+static MESH_LATTICE_PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "File Path",
+        data_type: DataType::Text,
+    },
+    Parameter {
+        name: "Center Geometry",
+        data_type: DataType::Boolean,
+    },
+    Parameter {
+        name: "Strut Radius",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Node Radius",
+        data_type: DataType::Value,
+    },
+];
+
+impl<T: ModelFloat> ImplicitFunction<T> for MeshLattice<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let Some(data) = &self.data else {
+            return T::max_value();
+        };
+        let point = Vec3::new(x, y, z);
+
+        // With a constant radius, the closest capsule is the one with the closest centerline.
+        let strut_distance = data
+            .struts
+            .closest_point(&point)
+            .map_or(T::max_value(), |(closest, _)| {
+                closest.distance_to_vec3(&point) - self.strut_radius
+            });
+
+        if self.node_radius > T::zero() {
+            let node_distance = data
+                .nodes
+                .closest_point(&point)
+                .map_or(T::max_value(), |(closest, _)| {
+                    closest.distance_to_vec3(&point) - self.node_radius
+                });
+            strut_distance.min(node_distance)
+        } else {
+            strut_distance
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for MeshLattice<T> {
+    fn parameters(&self) -> &[Parameter] {
+        MESH_LATTICE_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        let mut new_file = String::new();
+        if Parameter::set_text_from_param(parameter_name, &data, "File Path", &mut new_file) {
+            self.set_mesh_from_file(&new_file);
+        } else if Parameter::set_bool_from_param(
+            parameter_name,
+            &data,
+            "Center Geometry",
+            &mut self.center,
+        ) {
+            if let Some(file_path) = self.file_path.clone() {
+                self.set_mesh_from_file(&file_path);
+            }
+        } else if !(Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Strut Radius",
+            &mut self.strut_radius,
+        ) || Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Node Radius",
+            &mut self.node_radius,
+        )) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "File Path" => {
+                if let Some(file_path) = &self.file_path {
+                    Some(Data::File(file_path.clone()))
+                } else {
+                    Some(Data::File("No file set.".to_string()))
+                }
+            }
+            "Center Geometry" => Some(Data::Boolean(self.center)),
+            "Strut Radius" => Some(Data::Value(self.strut_radius)),
+            "Node Radius" => Some(Data::Value(self.node_radius)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "MeshLattice"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tetrahedron() -> Mesh<f64> {
+        let mut mesh = Mesh::new();
+        mesh.add_vertices(&[
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(10.0, 0.0, 0.0),
+            Vec3::new(0.0, 10.0, 0.0),
+            Vec3::new(0.0, 0.0, 10.0),
+        ]);
+        mesh.add_faces(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]]);
+        mesh
+    }
+
+    #[test]
+    fn test_unique_mesh_edges() {
+        let mesh = tetrahedron();
+
+        assert_eq!(
+            mesh.edge_indices(),
+            vec![[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]]
+        );
+        assert_eq!(mesh.edges().len(), 6);
+    }
+
+    #[test]
+    fn test_lattice_distance() {
+        let lattice = MeshLattice::from_mesh(&tetrahedron(), 1.0);
+
+        // On the edge from {0,0,0} to {10,0,0}.
+        assert!((lattice.eval(5.0, 0.0, 0.0) + 1.0).abs() < 1E-9);
+        // Above the center of the bottom face, closest to the edges of that face.
+        assert!((lattice.eval(2.0, 2.0, -4.0) - (20.0_f64.sqrt() - 1.0)).abs() < 1E-9);
+        // Beyond a vertex.
+        assert!((lattice.eval(-3.0, -4.0, 0.0) - 4.0).abs() < 1E-9);
+    }
+
+    #[test]
+    fn test_lattice_nodes() {
+        let lattice = MeshLattice::from_mesh(&tetrahedron(), 1.0).with_node_radius(2.0);
+
+        assert!((lattice.eval(-3.0, -4.0, 0.0) - 3.0).abs() < 1E-9);
+        assert!((lattice.eval(5.0, 0.0, 0.0) + 1.0).abs() < 1E-9);
+    }
+
+    #[test]
+    fn test_lattice_from_file() {
+        let lattice = MeshLattice::<f64>::from_path("assets/geometry/box.obj", 0.5).unwrap();
+
+        // 12 box edges and one diagonal per side.
+        assert_eq!(lattice.num_struts(), 18);
+        assert!((lattice.eval(10.0, 0.0, 0.0) + 0.5).abs() < 1E-9);
+
+        let mut mesh_file = MeshFile::<f64>::new();
+        mesh_file.center = true;
+        mesh_file.set_mesh_from_file("assets/geometry/box.obj");
+        let centered = MeshLattice::from_mesh_file(&mesh_file, 0.5).unwrap();
+        assert!((centered.eval(0.0, -10.0, -10.0) + 0.5).abs() < 1E-9);
+
+        mesh_file.scale = 0.5;
+        mesh_file.translation = Vec3::new(0.0, 0.0, 5.0);
+        mesh_file.build().unwrap();
+        let mut placed = MeshLattice::from_mesh_file(&mesh_file, 0.5).unwrap();
+        assert!((placed.eval(0.0, -5.0, 0.0) + 0.5).abs() < 1E-9);
+
+        // The placement can't be reloaded from the file, so toggling the centering keeps the placed geometry.
+        assert!(placed.file_path.is_none());
+        placed.set_parameter("Center Geometry", Data::Boolean(false));
+        assert!((placed.eval(0.0, -5.0, 0.0) + 0.5).abs() < 1E-9);
+    }
+}
@@ -9,18 +9,44 @@
 //! - [`YDomain`](functions::YDomain)
 //! - [`ZDomain`](functions::ZDomain)
 //! - [`XYZValue`](functions::XYZValue)
+//! - [`CylindricalValue`](functions::CylindricalValue)
+//! - [`SphericalValue`](functions::SphericalValue)
+//! - [`PlaneValue`](functions::PlaneValue)
 //!
 //! ## Periodic Surface Functions
 //! - [`Gyroid`](functions::Gyroid)
 //! - [`Neovius`](functions::Neovius)
 //! - [`SchwarzP`](functions::SchwarzP)
+//! - [`ConformalTpms`](functions::ConformalTpms) for surfaces in cylindrical or spherical coordinates.
+//!
+//! ## Profiles
+//! - [`Extrusion`](functions::Extrusion) of a 2D [`Profile`](crate::types::geometry::Profile) along a plane normal.
+//! - [`Revolution`](functions::Revolution) of a 2D [`Profile`](crate::types::geometry::Profile) around an axis.
+//!
+//! ## Strut Lattices
+//! - [`StrutLattice`](functions::StrutLattice)
+//! - [`TubeNetwork`](functions::TubeNetwork) for tubes with a varying radius along polylines and graphs.
+//! - [`MeshLattice`](functions::MeshLattice) for beam lattices along the edges of a mesh.
+//!
+//! ## Noise
+//! - [`PerlinNoise`](functions::PerlinNoise)
+//! - [`SimplexNoise`](functions::SimplexNoise)
+//! - [`WorleyNoise`](functions::WorleyNoise)
+//!
+//! ## Stochastic Structures
+//! - [`Voronoi`](functions::Voronoi)
 //!
 //! ## Custom
 //! - [`MeshFile`](functions::MeshFile)
+//! - [`PointCloud`](functions::PointCloud) for distances to points loaded from .xyz or .ply files.
+//! - [`HrbfSurface`](functions::HrbfSurface) for smooth surfaces reconstructed from points with normals.
+//! - [`VolumeField`](functions::VolumeField) for interpolated values of a sampled field or an imported scan.
 
 // Modules
 mod coordinates;
 pub use coordinates::*;
+mod local_coordinates;
+pub use local_coordinates::*;
 
 mod gyroid;
 pub use gyroid::*;
@@ -28,9 +54,30 @@ mod neovius;
 pub use neovius::*;
 mod schwarz;
 pub use schwarz::*;
+mod conformal_tpms;
+pub use conformal_tpms::*;
+mod profile_solids;
+pub use profile_solids::*;
+mod strut_lattice;
+pub use strut_lattice::*;
+mod tube_network;
+pub use tube_network::*;
+mod mesh_lattice;
+pub use mesh_lattice::*;
+
+mod noise;
+pub use noise::*;
+mod voronoi;
+pub use voronoi::*;
 
 mod mesh_file;
 pub use mesh_file::*;
+mod point_cloud;
+pub use point_cloud::*;
+mod hrbf_surface;
+pub use hrbf_surface::*;
+mod volume_field;
+pub use volume_field::*;
 
 mod function_components;
 pub use function_components::*;
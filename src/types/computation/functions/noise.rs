@@ -0,0 +1,728 @@
+use std::fmt::Debug;
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::computation::model::{Data, DataType, Parameter};
+use crate::types::computation::traits::{ImplicitComponent, ImplicitFunction, ModelFloat};
+
+/// Frequency multiplier between successive octaves of fractal noise.
+const LACUNARITY: f64 = 2.0;
+/// Amplitude multiplier between successive octaves of fractal noise.
+const GAIN: f64 = 0.5;
+/// Upper limit for the number of octaves, to avoid runaway evaluation cost.
+const MAX_OCTAVES: usize = 12;
+
+/// Gradient directions used for Perlin and simplex noise. These are the midpoints of the edges of a cube.
+const GRADIENTS: [[i8; 3]; 12] = [
+    [1, 1, 0],
+    [-1, 1, 0],
+    [1, -1, 0],
+    [-1, -1, 0],
+    [1, 0, 1],
+    [-1, 0, 1],
+    [1, 0, -1],
+    [-1, 0, -1],
+    [0, 1, 1],
+    [0, -1, 1],
+    [0, 1, -1],
+    [0, -1, -1],
+];
+
+/// Hash an integer lattice coordinate together with a seed.
+///
+/// Only wrapping integer arithmetic is used, so the result is identical on all platforms and threads.
+#[inline(always)]
+pub(super) fn hash(i: i32, j: i32, k: i32, seed: u32) -> u32 {
+    let mut h = seed.wrapping_mul(0x27d4_eb2d);
+    h ^= (i as u32).wrapping_mul(0x8da6_b343);
+    h ^= (j as u32).wrapping_mul(0xd816_3841);
+    h ^= (k as u32).wrapping_mul(0xcb1a_b31f);
+    h = (h ^ (h >> 16)).wrapping_mul(0x7feb_352d);
+    h = (h ^ (h >> 15)).wrapping_mul(0x846c_a68b);
+    h ^ (h >> 16)
+}
+
+/// Map a hash to a value in the range [0, 1).
+#[inline(always)]
+pub(super) fn unit_value<T: Float>(hash: u32) -> T {
+    T::from(hash >> 8).unwrap() / T::from(1u32 << 24).unwrap()
+}
+
+/// Dot product between the hashed gradient for a lattice point and an offset vector.
+#[inline(always)]
+fn gradient_dot<T: Float>(hash: u32, x: T, y: T, z: T) -> T {
+    let g = GRADIENTS[(hash % 12) as usize];
+    T::from(g[0]).unwrap() * x + T::from(g[1]).unwrap() * y + T::from(g[2]).unwrap() * z
+}
+
+/// Split a coordinate into its integer lattice cell and the fractional position inside the cell.
+#[inline(always)]
+fn lattice<T: Float>(value: T) -> (i32, T) {
+    let floor = value.floor();
+    (floor.to_i32().unwrap_or(0), value - floor)
+}
+
+/// Quintic fade curve `6t^5 - 15t^4 + 10t^3`.
+#[inline(always)]
+fn fade<T: Float>(t: T) -> T {
+    let six = T::from(6.0).unwrap();
+    let fifteen = T::from(15.0).unwrap();
+    let ten = T::from(10.0).unwrap();
+    t * t * t * (t * (t * six - fifteen) + ten)
+}
+
+#[inline(always)]
+fn lerp<T: Float>(a: T, b: T, t: T) -> T {
+    a + t * (b - a)
+}
+
+/// Single octave of improved Perlin noise. Returns values in approximately [-1, 1].
+fn perlin<T: Float>(x: T, y: T, z: T, seed: u32) -> T {
+    let (i, fx) = lattice(x);
+    let (j, fy) = lattice(y);
+    let (k, fz) = lattice(z);
+    let one = T::one();
+
+    let corner = |di: i32, dj: i32, dk: i32| {
+        let h = hash(
+            i.wrapping_add(di),
+            j.wrapping_add(dj),
+            k.wrapping_add(dk),
+            seed,
+        );
+        gradient_dot(
+            h,
+            fx - T::from(di).unwrap(),
+            fy - T::from(dj).unwrap(),
+            fz - T::from(dk).unwrap(),
+        )
+    };
+
+    let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+
+    let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), u);
+    let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), u);
+    let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), u);
+    let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), u);
+
+    let value = lerp(lerp(x00, x10, v), lerp(x01, x11, v), w);
+    value.clamp(-one, one)
+}
+
+/// Single octave of 3d simplex noise. Returns values in approximately [-1, 1].
+fn simplex<T: Float>(x: T, y: T, z: T, seed: u32) -> T {
+    let f3 = T::one() / T::from(3.0).unwrap();
+    let g3 = T::one() / T::from(6.0).unwrap();
+    let two = T::from(2.0).unwrap();
+    let three = T::from(3.0).unwrap();
+
+    // Skew the input space to find the simplex cell.
+    let s = (x + y + z) * f3;
+    let (i, _) = lattice(x + s);
+    let (j, _) = lattice(y + s);
+    let (k, _) = lattice(z + s);
+
+    let t = T::from(i as f64 + j as f64 + k as f64).unwrap() * g3;
+    let x0 = x - (T::from(i).unwrap() - t);
+    let y0 = y - (T::from(j).unwrap() - t);
+    let z0 = z - (T::from(k).unwrap() - t);
+
+    // Determine which simplex the point is in.
+    let (i1, j1, k1, i2, j2, k2) = if x0 >= y0 {
+        if y0 >= z0 {
+            (1, 0, 0, 1, 1, 0)
+        } else if x0 >= z0 {
+            (1, 0, 0, 1, 0, 1)
+        } else {
+            (0, 0, 1, 1, 0, 1)
+        }
+    } else if y0 < z0 {
+        (0, 0, 1, 0, 1, 1)
+    } else if x0 < z0 {
+        (0, 1, 0, 0, 1, 1)
+    } else {
+        (0, 1, 0, 1, 1, 0)
+    };
+
+    let offsets = [
+        (0, 0, 0, x0, y0, z0),
+        (
+            i1,
+            j1,
+            k1,
+            x0 - T::from(i1).unwrap() + g3,
+            y0 - T::from(j1).unwrap() + g3,
+            z0 - T::from(k1).unwrap() + g3,
+        ),
+        (
+            i2,
+            j2,
+            k2,
+            x0 - T::from(i2).unwrap() + two * g3,
+            y0 - T::from(j2).unwrap() + two * g3,
+            z0 - T::from(k2).unwrap() + two * g3,
+        ),
+        (
+            1,
+            1,
+            1,
+            x0 - T::one() + three * g3,
+            y0 - T::one() + three * g3,
+            z0 - T::one() + three * g3,
+        ),
+    ];
+
+    let radius = T::from(0.6).unwrap();
+    let mut sum = T::zero();
+    for (di, dj, dk, dx, dy, dz) in offsets {
+        let falloff = radius - dx * dx - dy * dy - dz * dz;
+        if falloff > T::zero() {
+            let h = hash(
+                i.wrapping_add(di),
+                j.wrapping_add(dj),
+                k.wrapping_add(dk),
+                seed,
+            );
+            let falloff2 = falloff * falloff;
+            sum = sum + falloff2 * falloff2 * gradient_dot(h, dx, dy, dz);
+        }
+    }
+
+    (T::from(32.0).unwrap() * sum).clamp(-T::one(), T::one())
+}
+
+/// Single octave of cellular noise. Returns the distances to the closest and second closest feature point, in cell units.
+fn worley<T: Float>(x: T, y: T, z: T, seed: u32) -> (T, T) {
+    let (i, fx) = lattice(x);
+    let (j, fy) = lattice(y);
+    let (k, fz) = lattice(z);
+
+    let mut f1 = T::infinity();
+    let mut f2 = T::infinity();
+    for dk in -1..=1 {
+        for dj in -1..=1 {
+            for di in -1..=1 {
+                let h = hash(
+                    i.wrapping_add(di),
+                    j.wrapping_add(dj),
+                    k.wrapping_add(dk),
+                    seed,
+                );
+                // Derive three independent jitter values from the cell hash.
+                let px = T::from(di).unwrap() + unit_value::<T>(h);
+                let py = T::from(dj).unwrap() + unit_value::<T>(hash(h as i32, 1, 0, seed));
+                let pz = T::from(dk).unwrap() + unit_value::<T>(hash(h as i32, 2, 0, seed));
+
+                let d =
+                    ((px - fx) * (px - fx) + (py - fy) * (py - fy) + (pz - fz) * (pz - fz)).sqrt();
+                if d < f1 {
+                    f2 = f1;
+                    f1 = d;
+                } else if d < f2 {
+                    f2 = d;
+                }
+            }
+        }
+    }
+
+    (f1, f2)
+}
+
+/// Sum a number of octaves of a noise function as fractal Brownian motion.
+///
+/// The result is normalized by the total amplitude of all octaves, so it stays within the range of a single octave.
+#[inline(always)]
+fn fractal<T: Float, F: Fn(T, T, T, u32) -> T>(
+    noise: F,
+    x: T,
+    y: T,
+    z: T,
+    seed: u32,
+    frequency: T,
+    octaves: usize,
+) -> T {
+    let lacunarity = T::from(LACUNARITY).unwrap();
+    let gain = T::from(GAIN).unwrap();
+
+    let mut sum = T::zero();
+    let mut total = T::zero();
+    let mut weight = T::one();
+    let mut f = frequency;
+    for octave in 0..octaves.clamp(1, MAX_OCTAVES) {
+        sum = sum + weight * noise(x * f, y * f, z * f, seed.wrapping_add(octave as u32));
+        total = total + weight;
+        weight = weight * gain;
+        f = f * lacunarity;
+    }
+    sum / total
+}
+
+static NOISE_PARAMETERS: &[Parameter; 4] = &[
+    Parameter {
+        name: "Seed",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Frequency",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Amplitude",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Octaves",
+        data_type: DataType::Value,
+    },
+];
+
+static WORLEY_PARAMETERS: &[Parameter; 5] = &[
+    Parameter {
+        name: "Seed",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Frequency",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Amplitude",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Octaves",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Distance",
+        data_type: DataType::Enum(&["F1", "F2", "F2-F1"]),
+    },
+];
+
+/// Assign the shared noise parameters. Returns true if the parameter name matched one of them.
+fn set_noise_param<T: Float>(
+    parameter_name: &str,
+    data: &Data<T>,
+    seed: &mut u32,
+    frequency: &mut T,
+    amplitude: &mut T,
+    octaves: &mut usize,
+) -> bool {
+    match parameter_name {
+        "Seed" => {
+            let mut value = T::from(*seed).unwrap();
+            Parameter::set_value_from_param(parameter_name, data, "Seed", &mut value);
+            *seed = value.max(T::zero()).round().to_u32().unwrap_or(*seed);
+            true
+        }
+        "Frequency" => {
+            let mut value = *frequency;
+            Parameter::set_value_from_param(parameter_name, data, "Frequency", &mut value);
+            if value <= T::zero() {
+                error!("Frequency must be larger than zero.");
+            } else {
+                *frequency = value;
+            }
+            true
+        }
+        "Amplitude" => {
+            Parameter::set_value_from_param(parameter_name, data, "Amplitude", amplitude)
+        }
+        "Octaves" => {
+            let mut value = T::from(*octaves).unwrap();
+            Parameter::set_value_from_param(parameter_name, data, "Octaves", &mut value);
+            *octaves = value
+                .round()
+                .to_usize()
+                .unwrap_or(*octaves)
+                .clamp(1, MAX_OCTAVES);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Read the shared noise parameters.
+fn read_noise_param<T: Float>(
+    parameter_name: &str,
+    seed: u32,
+    frequency: T,
+    amplitude: T,
+    octaves: usize,
+) -> Option<Data<T>> {
+    match parameter_name {
+        "Seed" => Some(Data::Value(T::from(seed).unwrap())),
+        "Frequency" => Some(Data::Value(frequency)),
+        "Amplitude" => Some(Data::Value(amplitude)),
+        "Octaves" => Some(Data::Value(T::from(octaves).unwrap())),
+        _ => None,
+    }
+}
+
+/// Function generating improved Perlin gradient noise.
+///
+/// With more than one octave, the noise is summed as fractal Brownian motion, where each octave doubles the frequency and halves the amplitude.
+/// The output is in the range [-amplitude, amplitude].
+///
+/// The noise is computed using integer hashing of the lattice, so the output only depends on the seed and the coordinate,
+/// and is identical regardless of sampling strategy, thread or platform.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct PerlinNoise<T> {
+    pub seed: u32,
+    pub frequency: T,
+    pub amplitude: T,
+    pub octaves: usize,
+}
+
+impl<T: Float> PerlinNoise<T> {
+    /// Create a new Perlin noise function.
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the random gradients.
+    /// * `frequency` - Number of noise periods per unit length. The inverse of the feature size.
+    /// * `amplitude` - Maximum magnitude of the output.
+    pub fn new(seed: u32, frequency: T, amplitude: T) -> Self {
+        Self {
+            seed,
+            frequency,
+            amplitude,
+            octaves: 1,
+        }
+    }
+
+    /// Set the number of octaves of fractal Brownian motion.
+    pub fn with_octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves.clamp(1, MAX_OCTAVES);
+        self
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for PerlinNoise<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        self.amplitude * fractal(perlin, x, y, z, self.seed, self.frequency, self.octaves)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for PerlinNoise<T> {
+    fn parameters(&self) -> &[Parameter] {
+        NOISE_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !set_noise_param(
+            parameter_name,
+            &data,
+            &mut self.seed,
+            &mut self.frequency,
+            &mut self.amplitude,
+            &mut self.octaves,
+        ) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        read_noise_param(
+            parameter_name,
+            self.seed,
+            self.frequency,
+            self.amplitude,
+            self.octaves,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "PerlinNoise"
+    }
+}
+
+/// Function generating simplex gradient noise.
+///
+/// Simplex noise has fewer directional artifacts than Perlin noise and is cheaper to evaluate.
+/// With more than one octave, the noise is summed as fractal Brownian motion, where each octave doubles the frequency and halves the amplitude.
+/// The output is in the range [-amplitude, amplitude].
+///
+/// The noise is computed using integer hashing of the lattice, so the output only depends on the seed and the coordinate,
+/// and is identical regardless of sampling strategy, thread or platform.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct SimplexNoise<T> {
+    pub seed: u32,
+    pub frequency: T,
+    pub amplitude: T,
+    pub octaves: usize,
+}
+
+impl<T: Float> SimplexNoise<T> {
+    /// Create a new simplex noise function.
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the random gradients.
+    /// * `frequency` - Number of noise periods per unit length. The inverse of the feature size.
+    /// * `amplitude` - Maximum magnitude of the output.
+    pub fn new(seed: u32, frequency: T, amplitude: T) -> Self {
+        Self {
+            seed,
+            frequency,
+            amplitude,
+            octaves: 1,
+        }
+    }
+
+    /// Set the number of octaves of fractal Brownian motion.
+    pub fn with_octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves.clamp(1, MAX_OCTAVES);
+        self
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for SimplexNoise<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        self.amplitude * fractal(simplex, x, y, z, self.seed, self.frequency, self.octaves)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for SimplexNoise<T> {
+    fn parameters(&self) -> &[Parameter] {
+        NOISE_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !set_noise_param(
+            parameter_name,
+            &data,
+            &mut self.seed,
+            &mut self.frequency,
+            &mut self.amplitude,
+            &mut self.octaves,
+        ) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        read_noise_param(
+            parameter_name,
+            self.seed,
+            self.frequency,
+            self.amplitude,
+            self.octaves,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "SimplexNoise"
+    }
+}
+
+/// Distance measure returned from a [`WorleyNoise`] function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub enum WorleyDistance {
+    /// Distance to the closest feature point.
+    F1,
+    /// Distance to the second closest feature point.
+    F2,
+    /// Difference between the second closest and the closest distance, which is zero on the cell borders.
+    F2MinusF1,
+}
+
+/// Function generating cellular (Worley) noise.
+///
+/// Space is divided into cells of size `1 / frequency`, each containing a randomly placed feature point.
+/// The output is the selected [`WorleyDistance`] measured in cell units and multiplied by the amplitude.
+/// Setting the amplitude equal to the cell size gives distances in model units, for example to create randomised pores.
+///
+/// The noise is computed using integer hashing of the lattice, so the output only depends on the seed and the coordinate,
+/// and is identical regardless of sampling strategy, thread or platform.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct WorleyNoise<T> {
+    pub seed: u32,
+    pub frequency: T,
+    pub amplitude: T,
+    pub octaves: usize,
+    pub distance: WorleyDistance,
+}
+
+impl<T: Float> WorleyNoise<T> {
+    /// Create a new cellular noise function, returning the distance to the closest feature point.
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the random feature points.
+    /// * `frequency` - Number of cells per unit length.
+    /// * `amplitude` - Scale factor for the output.
+    pub fn new(seed: u32, frequency: T, amplitude: T) -> Self {
+        Self {
+            seed,
+            frequency,
+            amplitude,
+            octaves: 1,
+            distance: WorleyDistance::F1,
+        }
+    }
+
+    /// Set the number of octaves of fractal Brownian motion.
+    pub fn with_octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves.clamp(1, MAX_OCTAVES);
+        self
+    }
+
+    /// Set the distance measure to return.
+    pub fn with_distance(mut self, distance: WorleyDistance) -> Self {
+        self.distance = distance;
+        self
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for WorleyNoise<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let noise = |x: T, y: T, z: T, seed: u32| {
+            let (f1, f2) = worley(x, y, z, seed);
+            match self.distance {
+                WorleyDistance::F1 => f1,
+                WorleyDistance::F2 => f2,
+                WorleyDistance::F2MinusF1 => f2 - f1,
+            }
+        };
+        self.amplitude * fractal(noise, x, y, z, self.seed, self.frequency, self.octaves)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for WorleyNoise<T> {
+    fn parameters(&self) -> &[Parameter] {
+        WORLEY_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(selection) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Distance")
+        {
+            match selection.as_str() {
+                "F1" => self.distance = WorleyDistance::F1,
+                "F2" => self.distance = WorleyDistance::F2,
+                "F2-F1" => self.distance = WorleyDistance::F2MinusF1,
+                _ => error!("Unknown distance option: {}", selection),
+            }
+        } else if !set_noise_param(
+            parameter_name,
+            &data,
+            &mut self.seed,
+            &mut self.frequency,
+            &mut self.amplitude,
+            &mut self.octaves,
+        ) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Distance" => match self.distance {
+                WorleyDistance::F1 => Some(Data::EnumValue("F1".to_string())),
+                WorleyDistance::F2 => Some(Data::EnumValue("F2".to_string())),
+                WorleyDistance::F2MinusF1 => Some(Data::EnumValue("F2-F1".to_string())),
+            },
+            _ => read_noise_param(
+                parameter_name,
+                self.seed,
+                self.frequency,
+                self.amplitude,
+                self.octaves,
+            ),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "WorleyNoise"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    use super::*;
+
+    fn sample_points() -> Vec<(f64, f64, f64)> {
+        (0..1000)
+            .map(|i| {
+                let t = i as f64;
+                (0.37 * t - 120.0, 0.11 * t + 3.3, -0.23 * t + 17.9)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_perlin_zero_at_lattice_points() {
+        let noise = PerlinNoise::new(7, 1.0, 1.0);
+
+        assert!(noise.eval(0.0, 0.0, 0.0).abs() < 1E-10);
+        assert!(noise.eval(3.0, -2.0, 5.0).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_noise_within_amplitude() {
+        let amplitude = 2.5;
+        let perlin = PerlinNoise::new(1, 0.3, amplitude).with_octaves(4);
+        let simplex = SimplexNoise::new(1, 0.3, amplitude).with_octaves(4);
+
+        for (x, y, z) in sample_points() {
+            assert!(perlin.eval(x, y, z).abs() <= amplitude);
+            assert!(simplex.eval(x, y, z).abs() <= amplitude);
+        }
+    }
+
+    #[test]
+    fn test_seed_changes_noise() {
+        let a = SimplexNoise::new(1, 0.5, 1.0);
+        let b = SimplexNoise::new(2, 0.5, 1.0);
+
+        let differs = sample_points()
+            .into_iter()
+            .any(|(x, y, z)| (a.eval(x, y, z) - b.eval(x, y, z)).abs() > 1E-6);
+        assert!(differs);
+    }
+
+    #[test]
+    fn test_noise_deterministic_across_threads() {
+        let worley = WorleyNoise::new(42, 0.2, 5.0).with_octaves(2);
+        let perlin = PerlinNoise::new(42, 0.2, 1.0).with_octaves(3);
+
+        let points = sample_points();
+        let sequential: Vec<(f64, f64)> = points
+            .iter()
+            .map(|&(x, y, z)| (worley.eval(x, y, z), perlin.eval(x, y, z)))
+            .collect();
+        let parallel: Vec<(f64, f64)> = points
+            .into_par_iter()
+            .map(|(x, y, z)| (worley.eval(x, y, z), perlin.eval(x, y, z)))
+            .collect();
+
+        for (a, b) in sequential.iter().zip(parallel.iter()) {
+            assert_eq!(a.0.to_bits(), b.0.to_bits());
+            assert_eq!(a.1.to_bits(), b.1.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_worley_distances() {
+        let f1 = WorleyNoise::new(3, 1.0, 1.0);
+        let f2 = WorleyNoise::new(3, 1.0, 1.0).with_distance(WorleyDistance::F2);
+        let border = WorleyNoise::new(3, 1.0, 1.0).with_distance(WorleyDistance::F2MinusF1);
+
+        for (x, y, z) in sample_points() {
+            let d1 = f1.eval(x, y, z);
+            let d2 = f2.eval(x, y, z);
+            assert!(d1 >= 0.0);
+            // Closest point is never further away than the corner of the neighbouring cell.
+            assert!(d1 < 3.0.sqrt());
+            assert!(d2 >= d1);
+            assert!((border.eval(x, y, z) - (d2 - d1)).abs() < 1E-10);
+        }
+    }
+}
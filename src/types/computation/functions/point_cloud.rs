@@ -0,0 +1,357 @@
+use std::error::Error;
+
+use log::error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::{
+        computation::{
+            model::{Data, DataType, Parameter},
+            traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+            ModelError,
+        },
+        geometry::{
+            traits::{Bounded, SignedQuery, SpatialQuery},
+            BoundingBox, Octree, Vec3,
+        },
+    },
+    utils::io::parse_point_cloud_file,
+};
+
+const MAX_LEAF_POINT_COUNT: usize = 16;
+const MAX_TREE_DEPTH: usize = 12;
+
+/// Point of a cloud with its normal, stored in an octree for closest point queries.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CloudPoint<T> {
+    pub index: usize,
+    pub position: Vec3<T>,
+    pub normal: Vec3<T>,
+}
+
+impl<T: ModelFloat> CloudPoint<T> {
+    /// Build an octree of points, with normals if given.
+    pub fn octree(points: &[Vec3<T>], normals: Option<&[Vec3<T>]>) -> Octree<CloudPoint<T>, T> {
+        let cloud_points: Vec<CloudPoint<T>> = points
+            .iter()
+            .enumerate()
+            .map(|(index, &position)| CloudPoint {
+                index,
+                position,
+                normal: normals
+                    .and_then(|normals| normals.get(index).copied())
+                    .unwrap_or(Vec3::origin()),
+            })
+            .collect();
+
+        Octree::new()
+            .with_objects(&cloud_points)
+            .with_max_depth(MAX_TREE_DEPTH)
+            .with_max_leaf_size(MAX_LEAF_POINT_COUNT)
+            .build()
+    }
+}
+
+impl<T: ModelFloat> Bounded<T> for CloudPoint<T> {
+    fn bounds(&self) -> BoundingBox<T> {
+        BoundingBox::new(self.position, self.position)
+    }
+}
+
+impl<T: ModelFloat> SpatialQuery<T> for CloudPoint<T> {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            position: Vec3::origin(),
+            normal: Vec3::origin(),
+        }
+    }
+
+    fn closest_point(&self, _query_point: &Vec3<T>) -> Vec3<T> {
+        self.position
+    }
+}
+
+impl<T: ModelFloat> SignedQuery<T> for CloudPoint<T> {
+    fn closest_point_with_normal(&self, _query_point: &Vec3<T>) -> (Vec3<T>, Vec3<T>) {
+        (self.position, self.normal)
+    }
+}
+
+/// Check that a list of normals matches a list of points.
+pub(super) fn validate_normals<T>(
+    points: &[Vec3<T>],
+    normals: Option<&[Vec3<T>]>,
+) -> Result<(), ModelError> {
+    match normals {
+        Some(normals) if normals.len() != points.len() => Err(ModelError::Custom(format!(
+            "Point cloud has {} points but {} normals.",
+            points.len(),
+            normals.len()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+static POINT_CLOUD_PARAMETERS: &[Parameter; 2] = &[
+    Parameter {
+        name: "File Path",
+        data_type: DataType::Text,
+    },
+    Parameter {
+        name: "Signed",
+        data_type: DataType::Boolean,
+    },
+];
+
+/// Distance function to the points of a point cloud.
+///
+/// By default, the function is the unsigned distance to the closest point. If the cloud has normals, the distance can be signed,
+/// using the normal at the closest point to classify the query point as inside or outside.
+/// The signed distance is a rough approximation between the points. Use [`HrbfSurface`](super::HrbfSurface) for a smooth reconstruction of the surface.
+///
+/// # Example
+///
+/// ```rust
+/// # use imlet::types::{computation::{functions::PointCloud, model::ImplicitModel}, geometry::Vec3};
+///
+/// let points = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0)];
+/// let cloud = PointCloud::from_points(&points, None).unwrap();
+///
+/// let mut model = ImplicitModel::new();
+/// model.add_function("Cloud", cloud).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PointCloud<T> {
+    /// Storing the origin of the points, if loaded from a file.
+    pub file_path: Option<String>,
+    /// Use the normals to compute a signed distance.
+    pub signed: bool,
+    points: Vec<Vec3<T>>,
+    normals: Option<Vec<Vec3<T>>>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    data: Option<Octree<CloudPoint<T>, T>>,
+}
+
+impl<T: ModelFloat> Default for PointCloud<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ModelFloat> PointCloud<T> {
+    /// Create a new empty point cloud.
+    pub fn new() -> Self {
+        Self {
+            file_path: None,
+            signed: false,
+            points: Vec::new(),
+            normals: None,
+            data: None,
+        }
+    }
+
+    /// Create a point cloud from points and optional normals.
+    ///
+    /// The distance is signed if normals are given.
+    /// # Arguments
+    ///
+    /// * `points` - Positions of the points.
+    /// * `normals` - Outward normal at each point.
+    ///
+    /// # Returns
+    ///
+    /// An error if the number of normals doesn't match the number of points.
+    pub fn from_points(
+        points: &[Vec3<T>],
+        normals: Option<&[Vec3<T>]>,
+    ) -> Result<Self, ModelError> {
+        validate_normals(points, normals)?;
+
+        let mut cloud = Self {
+            file_path: None,
+            signed: normals.is_some(),
+            points: points.to_vec(),
+            normals: normals.map(|normals| normals.iter().map(|n| n.normalize()).collect()),
+            data: None,
+        };
+        cloud.build();
+        Ok(cloud)
+    }
+
+    /// Create a point cloud from an .xyz or .ply file.
+    ///
+    /// See [`parse_point_cloud_file`] for the file formats.
+    pub fn from_path(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let (points, normals) = parse_point_cloud_file(file_path)?;
+        let mut cloud = Self::from_points(&points, normals.as_deref())?;
+        cloud.file_path = Some(file_path.to_string());
+        Ok(cloud)
+    }
+
+    /// Set whether the distance is signed using the normals.
+    pub fn with_signed(mut self, signed: bool) -> Self {
+        self.signed = signed;
+        self
+    }
+
+    /// Replace the points with the ones in a file, logging an error if the file can't be read.
+    ///
+    /// The current signed setting is kept.
+    pub fn set_points_from_file(&mut self, file_path: &str) {
+        match Self::from_path(file_path) {
+            Ok(cloud) => *self = cloud.with_signed(self.signed),
+            Err(err) => error!("{}", err),
+        }
+    }
+
+    /// Rebuild the internal octree from the points.
+    pub fn build(&mut self) {
+        self.data = (!self.points.is_empty())
+            .then(|| CloudPoint::octree(&self.points, self.normals.as_deref()));
+    }
+
+    /// Returns the points of the cloud.
+    pub fn points(&self) -> &[Vec3<T>] {
+        &self.points
+    }
+
+    /// Returns the normals of the cloud, if any.
+    pub fn normals(&self) -> Option<&[Vec3<T>]> {
+        self.normals.as_deref()
+    }
+
+    /// Return the bounds of the points.
+    pub fn bounds(&self) -> Option<BoundingBox<T>> {
+        self.data.as_ref().and_then(|tree| tree.bounds())
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for PointCloud<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let Some(data) = &self.data else {
+            return T::max_value();
+        };
+        let point = Vec3::new(x, y, z);
+        if self.signed && self.normals.is_some() {
+            data.signed_distance(&point)
+        } else {
+            data.closest_point(&point)
+                .map_or(T::max_value(), |(closest, _)| {
+                    closest.distance_to_vec3(&point)
+                })
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for PointCloud<T> {
+    fn parameters(&self) -> &[Parameter] {
+        POINT_CLOUD_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        let mut new_file = String::new();
+        if Parameter::set_text_from_param(parameter_name, &data, "File Path", &mut new_file) {
+            self.set_points_from_file(&new_file);
+        } else if !Parameter::set_bool_from_param(parameter_name, &data, "Signed", &mut self.signed)
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "File Path" => {
+                if let Some(file_path) = &self.file_path {
+                    Some(Data::File(file_path.clone()))
+                } else {
+                    Some(Data::File("No file set.".to_string()))
+                }
+            }
+            "Signed" => Some(Data::Boolean(self.signed)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "PointCloud"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::utils::test_helpers::{sphere_samples, temp_file};
+
+    use super::*;
+
+    #[test]
+    fn test_unsigned_distance() {
+        let points: Vec<Vec3<f64>> = (0..50)
+            .map(|i| {
+                let t = i as f64;
+                Vec3::new((t * 3.7) % 20.0, (t * 5.3) % 20.0, (t * 2.9) % 20.0)
+            })
+            .collect();
+        let cloud = PointCloud::from_points(&points, None).unwrap();
+
+        for i in 0..100 {
+            let t = i as f64;
+            let query = Vec3::new((t * 1.31) % 24.0, (t * 0.77) % 24.0, (t * 2.13) % 24.0);
+            let expected = points
+                .iter()
+                .map(|p| p.distance_to_vec3(&query))
+                .fold(f64::MAX, f64::min);
+            assert!((cloud.eval(query.x, query.y, query.z) - expected).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_signed_distance_from_normals() {
+        let (points, normals) = sphere_samples(10.0, 2000);
+        let cloud = PointCloud::from_points(&points, Some(&normals)).unwrap();
+
+        assert!((cloud.eval(0.0, 0.0, 0.0) + 10.0).abs() < 1E-9);
+        assert!((cloud.eval(0.0, 0.0, 15.0) - 5.0).abs() < 0.1);
+        assert!(cloud.eval(7.0, 0.0, 0.0) < 0.0);
+
+        let unsigned = cloud.clone().with_signed(false);
+        assert!((unsigned.eval(0.0, 0.0, 0.0) - 10.0).abs() < 1E-9);
+    }
+
+    #[test]
+    fn test_read_xyz_and_ply() {
+        let xyz = temp_file(
+            "cloud.xyz",
+            b"# points with normals\n0 0 0 0 0 1\n1.5, 2, 3, 1, 0, 0\n\n",
+        );
+        let cloud = PointCloud::<f64>::from_path(xyz.to_str().unwrap()).unwrap();
+        assert!(cloud.points()[1].distance_to_vec3(&Vec3::new(1.5, 2.0, 3.0)) < 1E-9);
+        assert!(cloud.normals().unwrap()[1].distance_to_vec3(&Vec3::x_axis()) < 1E-9);
+        assert!(cloud.signed);
+
+        let mut ply = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\nend_header\n".to_vec();
+        for (point, red) in [([0.0f32, 0.0, 0.0], 1u8), ([1.5, 2.0, 3.0], 2)] {
+            for value in point {
+                ply.extend_from_slice(&value.to_le_bytes());
+            }
+            ply.push(red);
+        }
+        let ply = temp_file("cloud.ply", &ply);
+        let cloud = PointCloud::<f64>::from_path(ply.to_str().unwrap()).unwrap();
+        assert!(cloud.points()[1].distance_to_vec3(&Vec3::new(1.5, 2.0, 3.0)) < 1E-9);
+        assert!(cloud.normals().is_none());
+        assert!((cloud.eval(1.5, 2.0, 5.0) - 2.0).abs() < 1E-9);
+
+        let invalid = temp_file("invalid.xyz", b"0 0 0 0 0 1\n1 2 3\n");
+        let err = PointCloud::<f64>::from_path(invalid.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+
+        for path in [xyz, ply, invalid] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::fmt::Debug;
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+    },
+    geometry::{Plane, Profile, Vec2, Vec3},
+};
+
+/// Plane together with its in-plane axes, which are given by [`Vec3::orthonormal_basis`] of the plane normal.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+struct PlaneFrame<T> {
+    plane: Plane<T>,
+    u_axis: Vec3<T>,
+    v_axis: Vec3<T>,
+}
+
+impl<T: Float> PlaneFrame<T> {
+    /// Create a frame for a plane.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plane was created from a zero length normal.
+    fn new(plane: Plane<T>) -> Self {
+        assert!(
+            plane.normal().magnitude() >= T::epsilon(),
+            "Plane normal can't be a zero length vector."
+        );
+        let (u_axis, v_axis) = plane.normal().orthonormal_basis();
+        Self {
+            plane,
+            u_axis,
+            v_axis,
+        }
+    }
+
+    /// Update the frame from a parameter, ignoring a zero length normal.
+    ///
+    /// Returns true if the parameter was handled.
+    fn set_from_param(&mut self, parameter_name: &str, data: &Data<T>, normal_name: &str) -> bool {
+        let mut origin = self.plane.origin();
+        let mut normal = self.plane.normal();
+        if Parameter::set_vec3_from_param(parameter_name, data, "Origin", &mut origin) {
+            self.plane = Plane::new(origin, normal);
+        } else if Parameter::set_vec3_from_param(parameter_name, data, normal_name, &mut normal) {
+            if normal.magnitude() < T::epsilon() {
+                error!("{} can't be a zero length vector.", normal_name);
+            } else {
+                *self = Self::new(Plane::new(origin, normal));
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Express a point in the local coordinates of the plane, as the two in-plane coordinates and the height above the plane.
+    fn coordinates(&self, x: T, y: T, z: T) -> (T, T, T) {
+        let d = Vec3::new(x, y, z) - self.plane.origin();
+        (
+            d.dot(&self.u_axis),
+            d.dot(&self.v_axis),
+            d.dot(&self.plane.normal()),
+        )
+    }
+}
+
+static EXTRUSION_PARAMETERS: &[Parameter; 3] = &[
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Normal",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Height",
+        data_type: DataType::Value,
+    },
+];
+
+/// Function representing a solid created by extruding a 2D [`Profile`] from a plane along its normal.
+///
+/// The profile x and y axes follow [`Vec3::orthonormal_basis`] of the plane normal. For the global XY plane, these are the global x and y axes.
+/// The solid spans from the plane to the given height, and the distance is exact outside of the solid if the profile distance is exact.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Extrusion<T> {
+    pub profile: Profile<T>,
+    frame: PlaneFrame<T>,
+    pub height: T,
+}
+
+impl<T: Float> Extrusion<T> {
+    /// Create a new extrusion.
+    /// # Arguments
+    ///
+    /// * `profile` - Profile defined in the coordinates of the plane.
+    /// * `plane` - Plane in which the profile lies.
+    /// * `height` - Extrusion distance along the plane normal. Negative values extrude below the plane.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plane was created from a zero length normal.
+    pub fn new(profile: Profile<T>, plane: Plane<T>, height: T) -> Self {
+        Self {
+            profile,
+            frame: PlaneFrame::new(plane),
+            height,
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for Extrusion<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let half = self.height / T::from(2.0).unwrap();
+        let (u, v, w) = self.frame.coordinates(x, y, z);
+        let profile_distance = self.profile.signed_distance(Vec2::new(u, v));
+        let height_distance = (w - half).abs() - half.abs();
+
+        let outside = Vec2::new(
+            profile_distance.max(T::zero()),
+            height_distance.max(T::zero()),
+        )
+        .magnitude();
+        outside + profile_distance.max(height_distance).min(T::zero())
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Extrusion<T> {
+    fn parameters(&self) -> &[Parameter] {
+        EXTRUSION_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !(self.frame.set_from_param(parameter_name, &data, "Normal")
+            || Parameter::set_value_from_param(parameter_name, &data, "Height", &mut self.height))
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.frame.plane.origin())),
+            "Normal" => Some(Data::Vec3(self.frame.plane.normal())),
+            "Height" => Some(Data::Value(self.height)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Extrusion"
+    }
+}
+
+static REVOLUTION_PARAMETERS: &[Parameter; 2] = &[
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Axis",
+        data_type: DataType::Vec3,
+    },
+];
+
+/// Function representing a solid created by revolving a 2D [`Profile`] around an axis.
+///
+/// The profile x-coordinate is the distance from the axis, and the y-coordinate is the height along the axis from the origin.
+/// Only the part of the profile with positive x-coordinates contributes to the solid.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Revolution<T> {
+    pub profile: Profile<T>,
+    frame: PlaneFrame<T>,
+}
+
+impl<T: Float> Revolution<T> {
+    /// Create a new revolution.
+    /// # Arguments
+    ///
+    /// * `profile` - Profile defined in radius and height coordinates.
+    /// * `plane` - Plane whose origin lies on the axis and whose normal is the axis of revolution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plane was created from a zero length normal.
+    pub fn new(profile: Profile<T>, plane: Plane<T>) -> Self {
+        Self {
+            profile,
+            frame: PlaneFrame::new(plane),
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for Revolution<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let (u, v, w) = self.frame.coordinates(x, y, z);
+        let radius = (u * u + v * v).sqrt();
+        self.profile.signed_distance(Vec2::new(radius, w))
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Revolution<T> {
+    fn parameters(&self) -> &[Parameter] {
+        REVOLUTION_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !self.frame.set_from_param(parameter_name, &data, "Axis") {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.frame.plane.origin())),
+            "Axis" => Some(Data::Vec3(self.frame.plane.normal())),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Revolution"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extruded_rectangle_matches_box() {
+        let extrusion = Extrusion::new(
+            Profile::rectangle(Vec2::new(2.0, 3.0), 4.0, 6.0, 0.0),
+            Plane::xy(),
+            5.0,
+        );
+        // Exact distance to the box from {0, 0, 0} to {4, 6, 5}.
+        let box_distance = |x: f64, y: f64, z: f64| {
+            let q = Vec3::new(
+                (x - 2.0).abs() - 2.0,
+                (y - 3.0).abs() - 3.0,
+                (z - 2.5).abs() - 2.5,
+            );
+            let outside = Vec3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).magnitude();
+            outside + q.x.max(q.y).max(q.z).min(0.0)
+        };
+
+        for i in 0..100 {
+            let t = i as f64;
+            let (x, y, z) = (
+                -3.0 + (t * 0.37) % 10.0,
+                -3.0 + (t * 0.71) % 12.0,
+                -3.0 + (t * 0.53) % 11.0,
+            );
+            assert!((extrusion.eval(x, y, z) - box_distance(x, y, z)).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_extrusion_on_rotated_plane() {
+        let extrusion = Extrusion::new(
+            Profile::circle(Vec2::origin(), 2.0),
+            Plane::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(-1.0, 0.0, 0.0)),
+            -4.0,
+        );
+
+        // The cylinder runs from x = 0 to x = 4, around the axis {x, 0, 1}.
+        assert!((extrusion.eval(2.0, 0.0, 1.0) + 2.0).abs() < 1E-9);
+        assert!((extrusion.eval(2.0, 5.0, 1.0) - 3.0).abs() < 1E-9);
+        assert!((extrusion.eval(6.0, 0.0, 1.0) - 2.0).abs() < 1E-9);
+        assert!((extrusion.eval(-1.0, 0.0, 1.0) - 1.0).abs() < 1E-9);
+    }
+
+    #[test]
+    fn test_revolved_circle_is_torus() {
+        let revolution = Revolution::new(
+            Profile::circle(Vec2::new(10.0, 0.0), 2.5),
+            Plane::new(Vec3::new(1.0, 2.0, 3.0), Vec3::z_axis()),
+        );
+        // Exact distance to a torus around the z-axis through {1, 2, 3}.
+        let torus_distance = |x: f64, y: f64, z: f64| {
+            let radius = ((x - 1.0).powi(2) + (y - 2.0).powi(2)).sqrt();
+            ((radius - 10.0).powi(2) + (z - 3.0).powi(2)).sqrt() - 2.5
+        };
+
+        for i in 0..100 {
+            let t = i as f64;
+            let (x, y, z) = (
+                -15.0 + (t * 1.37) % 30.0,
+                -15.0 + (t * 2.71) % 30.0,
+                -5.0 + (t * 0.53) % 10.0,
+            );
+            assert!((revolution.eval(x, y, z) - torus_distance(x, y, z)).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_reject_zero_length_axis() {
+        let mut extrusion = Extrusion::new(Profile::circle(Vec2::origin(), 2.0), Plane::xy(), 4.0);
+        let mut revolution =
+            Revolution::new(Profile::circle(Vec2::new(5.0, 0.0), 1.0), Plane::xy());
+        extrusion.set_parameter("Normal", Data::Vec3(Vec3::origin()));
+        revolution.set_parameter("Axis", Data::Vec3(Vec3::origin()));
+
+        assert!((extrusion.eval(0.0, 0.0, 2.0) + 2.0).abs() < 1E-9);
+        assert!((revolution.eval(5.0, 0.0, 0.0) + 1.0).abs() < 1E-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Plane normal can't be a zero length vector.")]
+    fn test_new_rejects_zero_length_normal() {
+        Revolution::new(
+            Profile::circle(Vec2::new(5.0, 0.0), 1.0),
+            Plane::new(Vec3::origin(), Vec3::origin()),
+        );
+    }
+}
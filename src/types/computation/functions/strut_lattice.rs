@@ -0,0 +1,590 @@
+use std::{fmt::Debug, sync::OnceLock};
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+    },
+    geometry::{Line, Vec3},
+};
+
+/// Tolerance used when classifying unit cell coordinates.
+const CELL_TOLERANCE: f64 = 1E-9;
+
+/// Unit cell topology of a [`StrutLattice`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LatticeTopology {
+    /// Body centered cubic, with struts from the cell center to each corner.
+    Bcc,
+    /// Face centered cubic, with diagonal struts on each cell face.
+    Fcc,
+    /// Octet truss, combining the face diagonals with an octahedron between the face centers.
+    Octet,
+    /// Kelvin foam, made from the edges of space filling truncated octahedra.
+    Kelvin,
+    /// Diamond lattice, with four struts meeting at each node.
+    Diamond,
+}
+
+impl LatticeTopology {
+    const NAMES: [&'static str; 5] = ["BCC", "FCC", "Octet", "Kelvin", "Diamond"];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            LatticeTopology::Bcc => Self::NAMES[0],
+            LatticeTopology::Fcc => Self::NAMES[1],
+            LatticeTopology::Octet => Self::NAMES[2],
+            LatticeTopology::Kelvin => Self::NAMES[3],
+            LatticeTopology::Diamond => Self::NAMES[4],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "BCC" => Some(LatticeTopology::Bcc),
+            "FCC" => Some(LatticeTopology::Fcc),
+            "Octet" => Some(LatticeTopology::Octet),
+            "Kelvin" => Some(LatticeTopology::Kelvin),
+            "Diamond" => Some(LatticeTopology::Diamond),
+            _ => None,
+        }
+    }
+
+    /// Struts and nodes of the unit cell, in unit coordinates.
+    fn unit_cell(&self) -> &'static UnitCell {
+        static CELLS: OnceLock<[UnitCell; 5]> = OnceLock::new();
+        let cells = CELLS.get_or_init(|| {
+            [
+                UnitCell::new(bcc_struts()),
+                UnitCell::new(fcc_struts()),
+                UnitCell::new([fcc_struts(), octahedron_struts()].concat()),
+                UnitCell::new(kelvin_struts()),
+                UnitCell::new(diamond_struts()),
+            ]
+        });
+
+        match self {
+            LatticeTopology::Bcc => &cells[0],
+            LatticeTopology::Fcc => &cells[1],
+            LatticeTopology::Octet => &cells[2],
+            LatticeTopology::Kelvin => &cells[3],
+            LatticeTopology::Diamond => &cells[4],
+        }
+    }
+}
+
+type Point = [f64; 3];
+
+/// Struts and nodes of a unit cell.
+///
+/// Only the struts and nodes owned by the cell are stored, which are the ones with the midpoint in the half open range [0, 1).
+/// This way each strut is represented exactly once when the cell is tiled.
+#[derive(Debug)]
+struct UnitCell {
+    struts: Vec<(Point, Point)>,
+    nodes: Vec<Point>,
+}
+
+impl UnitCell {
+    fn new(struts: Vec<(Point, Point)>) -> Self {
+        let owned = |p: &Point| {
+            p.iter()
+                .all(|&v| v > -CELL_TOLERANCE && v < 1.0 - CELL_TOLERANCE)
+        };
+
+        let mut unique_struts: Vec<(Point, Point)> = Vec::new();
+        for (a, b) in struts {
+            let mid = [
+                (a[0] + b[0]) / 2.0,
+                (a[1] + b[1]) / 2.0,
+                (a[2] + b[2]) / 2.0,
+            ];
+            let duplicate = unique_struts.iter().any(|(u, v)| {
+                (same_point(u, &a) && same_point(v, &b)) || (same_point(u, &b) && same_point(v, &a))
+            });
+            if owned(&mid) && !duplicate {
+                unique_struts.push((a, b));
+            }
+        }
+
+        let mut nodes: Vec<Point> = Vec::new();
+        for p in unique_struts.iter().flat_map(|(a, b)| [*a, *b]) {
+            let wrapped = p.map(|v| v - v.floor());
+            if !nodes.iter().any(|n| same_point(n, &wrapped)) {
+                nodes.push(wrapped);
+            }
+        }
+
+        Self {
+            struts: unique_struts,
+            nodes,
+        }
+    }
+}
+
+fn same_point(a: &Point, b: &Point) -> bool {
+    a.iter()
+        .zip(b.iter())
+        .all(|(u, v)| (u - v).abs() < CELL_TOLERANCE)
+}
+
+fn bcc_struts() -> Vec<(Point, Point)> {
+    let center = [0.5, 0.5, 0.5];
+    (0..8)
+        .map(|i| {
+            let corner = [(i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64];
+            (center, corner)
+        })
+        .collect()
+}
+
+fn fcc_struts() -> Vec<(Point, Point)> {
+    let mut struts = Vec::new();
+    for axis in 0..3 {
+        for side in [0.0, 1.0] {
+            let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+            let mut center = [0.5; 3];
+            center[axis] = side;
+            for corner in 0..4 {
+                let mut p = [0.0; 3];
+                p[axis] = side;
+                p[u] = (corner & 1) as f64;
+                p[v] = ((corner >> 1) & 1) as f64;
+                struts.push((center, p));
+            }
+        }
+    }
+    struts
+}
+
+fn octahedron_struts() -> Vec<(Point, Point)> {
+    let mut face_centers = Vec::new();
+    for axis in 0..3 {
+        for side in [0.0, 1.0] {
+            let mut center = [0.5; 3];
+            center[axis] = side;
+            face_centers.push((axis, center));
+        }
+    }
+
+    let mut struts = Vec::new();
+    for (i, (axis_a, a)) in face_centers.iter().enumerate() {
+        for (axis_b, b) in face_centers.iter().skip(i + 1) {
+            if axis_a != axis_b {
+                struts.push((*a, *b));
+            }
+        }
+    }
+    struts
+}
+
+fn kelvin_struts() -> Vec<(Point, Point)> {
+    // Truncated octahedra centered on the body centered cubic sites. The vertices are all permutations of (0, ±1/4, ±1/2).
+    let mut offsets: Vec<Point> = Vec::new();
+    for &a in &[-0.25, 0.25] {
+        for &b in &[-0.5, 0.5] {
+            for p in [
+                [0.0, a, b],
+                [0.0, b, a],
+                [a, 0.0, b],
+                [b, 0.0, a],
+                [a, b, 0.0],
+                [b, a, 0.0],
+            ] {
+                offsets.push(p);
+            }
+        }
+    }
+
+    let edge_length_sq = 2.0 * 0.25 * 0.25;
+    let mut centers = vec![[0.5, 0.5, 0.5]];
+    centers.extend((0..8).map(|i| [(i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64]));
+
+    let mut struts = Vec::new();
+    for c in centers {
+        let vertices: Vec<Point> = offsets
+            .iter()
+            .map(|o| [c[0] + o[0], c[1] + o[1], c[2] + o[2]])
+            .collect();
+        for (i, a) in vertices.iter().enumerate() {
+            for b in vertices.iter().skip(i + 1) {
+                let d_sq: f64 = a.iter().zip(b.iter()).map(|(u, v)| (u - v) * (u - v)).sum();
+                if (d_sq - edge_length_sq).abs() < CELL_TOLERANCE {
+                    struts.push((*a, *b));
+                }
+            }
+        }
+    }
+    struts
+}
+
+fn diamond_struts() -> Vec<(Point, Point)> {
+    let nodes = [
+        ([0.25, 0.25, 0.25], [0.0, 0.0, 0.0]),
+        ([0.75, 0.75, 0.25], [1.0, 1.0, 0.0]),
+        ([0.75, 0.25, 0.75], [1.0, 0.0, 1.0]),
+        ([0.25, 0.75, 0.75], [0.0, 1.0, 1.0]),
+    ];
+
+    let mut struts = Vec::new();
+    for (inner, corner) in nodes {
+        // Each inner node connects to the closest corner and the three closest face centers.
+        struts.push((inner, corner));
+        for axis in 0..3 {
+            // Face centers around the inner node, at half a cell from the corner along two axes.
+            let mut target = [0.5; 3];
+            target[axis] = corner[axis];
+            struts.push((inner, target));
+        }
+    }
+    struts
+}
+
+/// Polynomial smooth minimum with blend radius `k`.
+#[inline(always)]
+fn smooth_min<T: Float>(a: T, b: T, k: T) -> T {
+    if k <= T::zero() {
+        return a.min(b);
+    }
+    let h = (k - (a - b).abs()).max(T::zero()) / k;
+    a.min(b) - h * h * k / T::from(4.0).unwrap()
+}
+
+/// Function generating a periodic strut lattice from a unit cell topology.
+///
+/// The lattice is made from cylindrical struts with spherical caps, optionally with larger spheres at the nodes.
+/// With a smoothing radius above zero, the struts and nodes are blended using a smooth minimum, giving filleted joints.
+///
+/// Each point is folded into its unit cell, and only the struts of that cell and the neighbouring cells which could be closer are evaluated.
+/// This makes the evaluation cost independent of the size of the lattice.
+///
+/// The output is the signed distance to the strut surface, which is exact when no smoothing is applied.
+///
+/// All struts share the same radius, and all nodes share the same node radius. Varying the radius per node or strut is not supported.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct StrutLattice<T> {
+    pub topology: LatticeTopology,
+    pub cell_size: T,
+    pub strut_radius: T,
+    pub node_radius: T,
+    pub smoothing: T,
+}
+
+impl<T: Float> StrutLattice<T> {
+    /// Create a new strut lattice.
+    /// # Arguments
+    ///
+    /// * `topology` - Unit cell topology.
+    /// * `cell_size` - The size of the cubic unit cell.
+    /// * `strut_radius` - The radius of the struts.
+    pub fn new(topology: LatticeTopology, cell_size: T, strut_radius: T) -> Self {
+        Self {
+            topology,
+            cell_size,
+            strut_radius,
+            node_radius: T::zero(),
+            smoothing: T::zero(),
+        }
+    }
+
+    /// Add spheres at the nodes of the lattice. A node radius smaller than the strut radius has no effect.
+    ///
+    /// The same radius is used for every node in the lattice.
+    pub fn with_node_radius(mut self, node_radius: T) -> Self {
+        self.node_radius = node_radius;
+        self
+    }
+
+    /// Blend the struts at the joints with a smooth minimum of the given radius.
+    pub fn with_smoothing(mut self, smoothing: T) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Compute the struts of the unit cell, scaled to the cell size and positioned at the origin.
+    pub fn unit_cell_struts(&self) -> Vec<Line<T>> {
+        self.topology
+            .unit_cell()
+            .struts
+            .iter()
+            .map(|(a, b)| Line::new(self.cell_point(a), self.cell_point(b)))
+            .collect()
+    }
+
+    #[inline(always)]
+    fn cell_point(&self, p: &Point) -> Vec3<T> {
+        Vec3::new(
+            T::from(p[0]).unwrap(),
+            T::from(p[1]).unwrap(),
+            T::from(p[2]).unwrap(),
+        ) * self.cell_size
+    }
+}
+
+/// Distance from a point to a segment.
+#[inline(always)]
+fn segment_distance<T: Float>(p: &Vec3<T>, a: &Vec3<T>, b: &Vec3<T>) -> T {
+    let ab = *b - *a;
+    let ap = *p - *a;
+    let t = (ap.dot(&ab) / ab.dot(&ab)).clamp(T::zero(), T::one());
+    (ap - ab * t).magnitude()
+}
+
+/// Distance from a point to an axis aligned box.
+#[inline(always)]
+fn box_distance<T: Float>(p: &Vec3<T>, min: &Vec3<T>, size: T) -> T {
+    let dx = (min.x - p.x).max(p.x - (min.x + size)).max(T::zero());
+    let dy = (min.y - p.y).max(p.y - (min.y + size)).max(T::zero());
+    let dz = (min.z - p.z).max(p.z - (min.z + size)).max(T::zero());
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
+
+static STRUT_LATTICE_PARAMETERS: &[Parameter; 5] = &[
+    Parameter {
+        name: "Topology",
+        data_type: DataType::Enum(&LatticeTopology::NAMES),
+    },
+    Parameter {
+        name: "Cell Size",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Strut Radius",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Node Radius",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Smoothing",
+        data_type: DataType::Value,
+    },
+];
+
+impl<T: ModelFloat> ImplicitFunction<T> for StrutLattice<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let cell = self.topology.unit_cell();
+        let size = self.cell_size;
+
+        // Fold the point into the unit cell.
+        let local = Vec3::new(
+            x - (x / size).floor() * size,
+            y - (y / size).floor() * size,
+            z - (z / size).floor() * size,
+        );
+
+        let use_nodes = self.node_radius > self.strut_radius;
+        let max_radius = self.strut_radius.max(self.node_radius);
+        let smoothing = self.smoothing.max(T::zero());
+
+        let mut best = T::max_value();
+        // Start with the cell containing the point, as it is most likely to hold the closest strut.
+        for offset in std::iter::once((0, 0, 0)).chain(
+            (-1..=1)
+                .flat_map(|i| (-1..=1).flat_map(move |j| (-1..=1).map(move |k| (i, j, k))))
+                .filter(|&o| o != (0, 0, 0)),
+        ) {
+            let origin = Vec3::new(
+                T::from(offset.0).unwrap() * size,
+                T::from(offset.1).unwrap() * size,
+                T::from(offset.2).unwrap() * size,
+            );
+
+            // Skip cells where no strut could change the current result.
+            if box_distance(&local, &origin, size) - max_radius >= best + smoothing {
+                continue;
+            }
+
+            let p = local - origin;
+            for (a, b) in cell.struts.iter() {
+                let d = segment_distance(&p, &self.cell_point(a), &self.cell_point(b))
+                    - self.strut_radius;
+                best = smooth_min(best, d, smoothing);
+            }
+            if use_nodes {
+                for n in cell.nodes.iter() {
+                    let d = p.distance_to_vec3(&self.cell_point(n)) - self.node_radius;
+                    best = smooth_min(best, d, smoothing);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for StrutLattice<T> {
+    fn parameters(&self) -> &[Parameter] {
+        STRUT_LATTICE_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(selection) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Topology")
+        {
+            match LatticeTopology::from_name(&selection) {
+                Some(topology) => self.topology = topology,
+                None => error!("Unknown topology: {}", selection),
+            }
+        } else if !(Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Cell Size",
+            &mut self.cell_size,
+        ) || Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Strut Radius",
+            &mut self.strut_radius,
+        ) || Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Node Radius",
+            &mut self.node_radius,
+        ) || Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Smoothing",
+            &mut self.smoothing,
+        )) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Topology" => Some(Data::EnumValue(self.topology.as_str().to_string())),
+            "Cell Size" => Some(Data::Value(self.cell_size)),
+            "Strut Radius" => Some(Data::Value(self.strut_radius)),
+            "Node Radius" => Some(Data::Value(self.node_radius)),
+            "Smoothing" => Some(Data::Value(self.smoothing)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "StrutLattice"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOPOLOGIES: [LatticeTopology; 5] = [
+        LatticeTopology::Bcc,
+        LatticeTopology::Fcc,
+        LatticeTopology::Octet,
+        LatticeTopology::Kelvin,
+        LatticeTopology::Diamond,
+    ];
+
+    /// Reference distance computed from the struts of all cells in a 5x5x5 block around the point.
+    fn brute_force_distance(lattice: &StrutLattice<f64>, p: Vec3<f64>) -> f64 {
+        let struts = lattice.unit_cell_struts();
+        let size = lattice.cell_size;
+        let p = Vec3::new(
+            p.x.rem_euclid(size),
+            p.y.rem_euclid(size),
+            p.z.rem_euclid(size),
+        );
+        let mut best = f64::MAX;
+        for i in -2..=2 {
+            for j in -2..=2 {
+                for k in -2..=2 {
+                    let offset = Vec3::new(i as f64, j as f64, k as f64) * lattice.cell_size;
+                    for s in struts.iter() {
+                        let d = segment_distance(&p, &(s.start + offset), &(s.end + offset));
+                        best = best.min(d - lattice.strut_radius);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_strut_counts() {
+        let counts: Vec<usize> = TOPOLOGIES
+            .iter()
+            .map(|t| t.unit_cell().struts.len())
+            .collect();
+
+        // Kelvin: 36 edges per truncated octahedron, two polyhedra per cell, each edge shared by three polyhedra.
+        assert_eq!(counts, vec![8, 12, 24, 24, 16]);
+    }
+
+    #[test]
+    fn test_nodes_per_topology() {
+        let nodes: Vec<usize> = TOPOLOGIES
+            .iter()
+            .map(|t| t.unit_cell().nodes.len())
+            .collect();
+
+        assert_eq!(nodes, vec![2, 4, 4, 12, 8]);
+    }
+
+    #[test]
+    fn test_distance_matches_brute_force() {
+        for topology in TOPOLOGIES {
+            let lattice = StrutLattice::new(topology, 10.0, 0.5);
+            for i in 0..100 {
+                let t = i as f64;
+                let p = Vec3::new(t * 1.37 - 60.0, t * 0.53 + 2.0, -t * 0.91 + 7.0);
+                let expected = brute_force_distance(&lattice, p);
+                let result = lattice.eval(p.x, p.y, p.z);
+                assert!(
+                    (result - expected).abs() < 1E-9,
+                    "Incorrect distance for {topology:?} at {p}. Was {result} but expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bcc_struts() {
+        let lattice = StrutLattice::new(LatticeTopology::Bcc, 10.0, 1.0);
+
+        // Cell center and corners are on the struts.
+        assert!((lattice.eval(5.0, 5.0, 5.0) + 1.0).abs() < 1E-10);
+        assert!((lattice.eval(20.0, -10.0, 30.0) + 1.0).abs() < 1E-10);
+        // Face center is half a cell from the closest strut axis, measured perpendicular to the diagonal.
+        let expected = 5.0 * (2.0f64 / 3.0).sqrt() - 1.0;
+        assert!((lattice.eval(5.0, 5.0, 0.0) - expected).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_nodes_and_smoothing() {
+        let plain = StrutLattice::new(LatticeTopology::Octet, 10.0, 0.5);
+        let nodes = plain.with_node_radius(1.5);
+        let smooth = plain.with_smoothing(1.0);
+
+        assert!((nodes.eval(0.0, 0.0, 0.0) + 1.5).abs() < 1E-10);
+        // Smoothing only adds material.
+        for i in 0..50 {
+            let t = i as f64;
+            let (x, y, z) = (t * 0.7, t * 0.3 + 1.0, t * 0.1);
+            assert!(smooth.eval(x, y, z) <= plain.eval(x, y, z));
+        }
+    }
+
+    #[test]
+    fn test_get_assigns_params() {
+        let mut lattice = StrutLattice::new(LatticeTopology::Bcc, 10.0, 0.5);
+        lattice.set_parameter("Topology", Data::EnumValue("Kelvin".to_string()));
+        lattice.set_parameter("Cell Size", Data::Value(5.0));
+        lattice.set_parameter("Node Radius", Data::Value(1.0));
+
+        assert_eq!(lattice.topology, LatticeTopology::Kelvin);
+        assert_eq!(lattice.cell_size, 5.0);
+        assert_eq!(lattice.node_radius, 1.0);
+    }
+}
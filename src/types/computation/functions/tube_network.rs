@@ -0,0 +1,464 @@
+use std::error::Error;
+
+use log::error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::{
+        computation::{
+            model::{Data, DataType, Parameter},
+            traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+            ModelError,
+        },
+        geometry::{
+            traits::{Bounded, SpatialQuery},
+            BoundingBox, Line, Octree, Vec3,
+        },
+    },
+    utils::io::parse_tube_network_file,
+};
+
+const MAX_LEAF_SEGMENT_COUNT: usize = 16;
+const MAX_TREE_DEPTH: usize = 10;
+
+/// Segment of a tube, with a radius that varies linearly from the start to the end.
+#[derive(Debug, Clone, Copy)]
+struct TubeSegment<T> {
+    line: Line<T>,
+    start_radius: T,
+    end_radius: T,
+}
+
+impl<T: ModelFloat> TubeSegment<T> {
+    /// Exact distance to the convex hull of the spheres at the segment ends.
+    fn signed_distance(&self, point: &Vec3<T>) -> T {
+        let (a, b) = (self.line.start, self.line.end);
+        let (r1, r2) = (self.start_radius, self.end_radius);
+        let ba = b - a;
+        let l2 = ba.dot(&ba);
+        let rr = r1 - r2;
+        let a2 = l2 - rr * rr;
+
+        // One sphere contains the other, or the segment has no length.
+        if a2 <= T::zero() {
+            return (point.distance_to_vec3(&a) - r1).min(point.distance_to_vec3(&b) - r2);
+        }
+
+        let il2 = T::one() / l2;
+        let pa = *point - a;
+        let y = pa.dot(&ba);
+        let z = y - l2;
+        let perpendicular = pa * l2 - ba * y;
+        let x2 = perpendicular.dot(&perpendicular);
+        let y2 = y * y * l2;
+        let z2 = z * z * l2;
+        let k = rr.signum() * rr * rr * x2;
+
+        if z.signum() * a2 * z2 > k {
+            (x2 + z2).sqrt() * il2 - r2
+        } else if y.signum() * a2 * y2 < k {
+            (x2 + y2).sqrt() * il2 - r1
+        } else {
+            ((x2 * a2 * il2).sqrt() + y * rr) * il2 - r1
+        }
+    }
+}
+
+impl<T: ModelFloat> Bounded<T> for TubeSegment<T> {
+    fn bounds(&self) -> BoundingBox<T> {
+        self.line.bounds()
+    }
+}
+
+impl<T: ModelFloat> SpatialQuery<T> for TubeSegment<T> {
+    fn default() -> Self {
+        Self {
+            line: SpatialQuery::default(),
+            start_radius: T::zero(),
+            end_radius: T::zero(),
+        }
+    }
+
+    fn closest_point(&self, query_point: &Vec3<T>) -> Vec3<T> {
+        self.line.closest_pt(*query_point)
+    }
+}
+
+/// Segments of the network stored in an octree, built from the nodes and edges.
+#[derive(Debug, Clone)]
+struct TubeData<T> {
+    segments: Octree<TubeSegment<T>, T>,
+    max_radius: T,
+}
+
+/// Distance function for a network of tubes, with a radius that varies smoothly between the nodes.
+///
+/// The network is a graph of nodes with a radius each, connected by edges. Each edge is a tapered tube,
+/// which is the convex hull of the spheres at the two nodes, so tubes meeting at a node share the same sphere and blend without gaps.
+/// A polyline is a network where consecutive nodes are connected.
+///
+/// The segments are stored in an [`Octree`], so evaluation only considers segments near the query point.
+///
+/// # Example
+///
+/// ```rust
+/// # use imlet::types::{computation::{functions::TubeNetwork, model::ImplicitModel}, geometry::Vec3};
+///
+/// // Tapered channel along a polyline
+/// let channel = TubeNetwork::from_polyline(
+///     &[Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0), Vec3::new(10.0, 10.0, 5.0)],
+///     &[3.0, 2.0, 1.0],
+/// )
+/// .unwrap();
+///
+/// let mut model = ImplicitModel::new();
+/// model.add_function("Channel", channel).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TubeNetwork<T> {
+    /// Storing the origin of the network, if loaded from a file.
+    pub file_path: Option<String>,
+    nodes: Vec<Vec3<T>>,
+    radii: Vec<T>,
+    edges: Vec<[usize; 2]>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    data: Option<TubeData<T>>,
+}
+
+impl<T: ModelFloat> Default for TubeNetwork<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ModelFloat> TubeNetwork<T> {
+    /// Create a new empty network.
+    pub fn new() -> Self {
+        Self {
+            file_path: None,
+            nodes: Vec::new(),
+            radii: Vec::new(),
+            edges: Vec::new(),
+            data: None,
+        }
+    }
+
+    /// Create a network from nodes, node radii and edges.
+    /// # Arguments
+    ///
+    /// * `nodes` - Positions of the nodes.
+    /// * `radii` - Tube radius at each node.
+    /// * `edges` - Pairs of node indices connected by a tube.
+    ///
+    /// # Returns
+    ///
+    /// An error if the number of radii doesn't match the number of nodes, or if an edge refers to a missing node.
+    pub fn from_graph(
+        nodes: &[Vec3<T>],
+        radii: &[T],
+        edges: &[[usize; 2]],
+    ) -> Result<Self, ModelError> {
+        if nodes.len() != radii.len() {
+            return Err(ModelError::Custom(format!(
+                "Tube network has {} nodes but {} radii.",
+                nodes.len(),
+                radii.len()
+            )));
+        }
+        if let Some(edge) = edges
+            .iter()
+            .find(|edge| edge.iter().any(|&index| index >= nodes.len()))
+        {
+            return Err(ModelError::Custom(format!(
+                "Tube network edge {:?} refers to a node outside of the {} nodes.",
+                edge,
+                nodes.len()
+            )));
+        }
+
+        let mut network = Self {
+            file_path: None,
+            nodes: nodes.to_vec(),
+            radii: radii.to_vec(),
+            edges: edges.to_vec(),
+            data: None,
+        };
+        network.build();
+        Ok(network)
+    }
+
+    /// Create a network where consecutive points of a polyline are connected.
+    /// # Arguments
+    ///
+    /// * `points` - Points along the polyline.
+    /// * `radii` - Tube radius at each point.
+    pub fn from_polyline(points: &[Vec3<T>], radii: &[T]) -> Result<Self, ModelError> {
+        let edges: Vec<[usize; 2]> = (1..points.len()).map(|i| [i - 1, i]).collect();
+        Self::from_graph(points, radii, &edges)
+    }
+
+    /// Create a network from a text or json file of nodes and edges.
+    ///
+    /// See [`parse_tube_network_file`] for the file formats.
+    pub fn from_path(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let (nodes, radii, edges) = parse_tube_network_file(file_path)?;
+        let mut network = Self::from_graph(&nodes, &radii, &edges)?;
+        network.file_path = Some(file_path.to_string());
+        Ok(network)
+    }
+
+    /// Replace the network with the one in a file, logging an error if the file can't be read.
+    pub fn set_network_from_file(&mut self, file_path: &str) {
+        match Self::from_path(file_path) {
+            Ok(network) => *self = network,
+            Err(err) => error!("{}", err),
+        }
+    }
+
+    /// Rebuild the internal octree from the nodes and edges.
+    ///
+    /// Edges which refer to missing nodes or radii are skipped.
+    pub fn build(&mut self) {
+        let segments: Vec<TubeSegment<T>> = self
+            .edges
+            .iter()
+            .filter_map(|&[start, end]| {
+                Some(TubeSegment {
+                    line: Line::new(*self.nodes.get(start)?, *self.nodes.get(end)?),
+                    start_radius: *self.radii.get(start)?,
+                    end_radius: *self.radii.get(end)?,
+                })
+            })
+            .collect();
+
+        self.data = if segments.is_empty() {
+            None
+        } else {
+            let max_radius = segments
+                .iter()
+                .map(|segment| segment.start_radius.max(segment.end_radius))
+                .fold(T::zero(), T::max);
+            Some(TubeData {
+                segments: Octree::new()
+                    .with_objects(&segments)
+                    .with_max_depth(MAX_TREE_DEPTH)
+                    .with_max_leaf_size(MAX_LEAF_SEGMENT_COUNT)
+                    .build(),
+                max_radius,
+            })
+        };
+    }
+
+    /// Positions of the nodes.
+    pub fn nodes(&self) -> &[Vec3<T>] {
+        &self.nodes
+    }
+
+    /// Tube radius at each node.
+    pub fn radii(&self) -> &[T] {
+        &self.radii
+    }
+
+    /// Pairs of node indices connected by a tube.
+    pub fn edges(&self) -> &[[usize; 2]] {
+        &self.edges
+    }
+
+    /// Return the bounds of the network centerlines.
+    pub fn bounds(&self) -> Option<BoundingBox<T>> {
+        self.data.as_ref().and_then(|data| data.segments.bounds())
+    }
+}
+
+static TUBE_NETWORK_PARAMETERS: &[Parameter] = &[Parameter {
+    name: "File Path",
+    data_type: DataType::Text,
+}];
+
+impl<T: ModelFloat> ImplicitFunction<T> for TubeNetwork<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let Some(data) = &self.data else {
+            return T::max_value();
+        };
+        let point = Vec3::new(x, y, z);
+        let Some((_, closest)) = data.segments.closest_point(&point) else {
+            return T::max_value();
+        };
+
+        // A segment can only be closer than the current best if its centerline is within the best distance plus the largest radius.
+        let best = closest.signed_distance(&point);
+        let search_distance = best + data.max_radius + Vec3::default_tolerance();
+        if search_distance <= T::zero() {
+            return best;
+        }
+        data.segments
+            .collect_nearby_objects(&point, search_distance)
+            .iter()
+            .map(|segment| segment.signed_distance(&point))
+            .fold(best, T::min)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for TubeNetwork<T> {
+    fn parameters(&self) -> &[Parameter] {
+        TUBE_NETWORK_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        let mut new_file = String::new();
+        if Parameter::set_text_from_param(parameter_name, &data, "File Path", &mut new_file) {
+            self.set_network_from_file(&new_file);
+        } else {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "File Path" => {
+                if let Some(file_path) = &self.file_path {
+                    Some(Data::File(file_path.clone()))
+                } else {
+                    Some(Data::File("No file set.".to_string()))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "TubeNetwork"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{types::geometry::Capsule, utils::test_helpers::temp_file};
+
+    use super::*;
+
+    #[test]
+    fn test_constant_radius_matches_capsule() {
+        let (start, end) = (Vec3::new(1.0, 2.0, 3.0), Vec3::new(-4.0, 6.0, 8.0));
+        let tube = TubeNetwork::from_polyline(&[start, end], &[1.5, 1.5]).unwrap();
+        let capsule = Capsule::from_points(start, end, 1.5);
+
+        for i in 0..100 {
+            let t = i as f64;
+            let (x, y, z) = ((t * 0.37) % 10.0 - 6.0, (t * 0.71) % 8.0, (t * 0.53) % 12.0);
+            assert!((tube.eval(x, y, z) - capsule.eval(x, y, z)).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_tapered_tube() {
+        let tube = TubeNetwork::from_polyline(
+            &[Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0)],
+            &[2.0_f64, 1.0],
+        )
+        .unwrap();
+
+        // Beyond the ends, the distance is measured to the end spheres.
+        assert!((tube.eval(-5.0, 0.0, 0.0) - 3.0).abs() < 1E-9);
+        assert!((tube.eval(15.0, 0.0, 0.0) - 4.0).abs() < 1E-9);
+        // Along the side, the radius decreases towards the end.
+        assert!(tube.eval(2.0, 1.8, 0.0) < 0.0);
+        assert!(tube.eval(8.0, 1.8, 0.0) > 0.0);
+    }
+
+    #[test]
+    fn test_network_matches_brute_force() {
+        let nodes: Vec<Vec3<f64>> = (0..40)
+            .map(|i| {
+                let t = i as f64;
+                Vec3::new((t * 3.7) % 20.0, (t * 5.3) % 20.0, (t * 2.9) % 20.0)
+            })
+            .collect();
+        let radii: Vec<f64> = (0..40).map(|i| 0.5 + (i % 5) as f64 * 0.4).collect();
+        let edges: Vec<[usize; 2]> = (0..40).map(|i| [i, (i * 7 + 3) % 40]).collect();
+        let network = TubeNetwork::from_graph(&nodes, &radii, &edges).unwrap();
+
+        let segments: Vec<TubeSegment<f64>> = edges
+            .iter()
+            .map(|&[a, b]| TubeSegment {
+                line: Line::new(nodes[a], nodes[b]),
+                start_radius: radii[a],
+                end_radius: radii[b],
+            })
+            .collect();
+
+        for i in 0..200 {
+            let t = i as f64;
+            let point = Vec3::new(
+                (t * 1.31) % 24.0 - 2.0,
+                (t * 0.77) % 24.0 - 2.0,
+                (t * 2.13) % 24.0 - 2.0,
+            );
+            let expected = segments
+                .iter()
+                .map(|segment| segment.signed_distance(&point))
+                .fold(f64::MAX, f64::min);
+            assert!((network.eval(point.x, point.y, point.z) - expected).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_invalid_network() {
+        let nodes = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)];
+
+        assert!(TubeNetwork::from_graph(&nodes, &[1.0], &[[0, 1]]).is_err());
+        assert!(TubeNetwork::from_graph(&nodes, &[1.0, 1.0], &[[0, 2]]).is_err());
+    }
+
+    #[test]
+    fn test_read_text_file() {
+        let path = temp_file(
+            "network.txt",
+            "# Two branches from a root node\nn 0 0 0 2\nn 10 0 0 1\nn 0 10 0 1\n\ne 0 1\ne 0 2\n",
+        );
+        let network = TubeNetwork::<f64>::from_path(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(network.nodes().len(), 3);
+        assert_eq!(network.edges(), &[[0, 1], [0, 2]]);
+        assert!((network.eval(0.0, 0.0, 5.0) - 3.0).abs() < 1E-9);
+        assert!(network.eval(0.0, 9.5, 0.0) < 0.0);
+    }
+
+    #[test]
+    fn test_read_text_file_as_polyline() {
+        let path = temp_file("polyline.txt", "n 0 0 0 1\nn 10 0 0 1\nn 10 10 0 1\n");
+        let network = TubeNetwork::<f64>::from_path(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(network.edges(), &[[0, 1], [1, 2]]);
+    }
+
+    #[test]
+    fn test_read_invalid_text_file() {
+        let path = temp_file("invalid.txt", "n 0 0 0 1\nn 10 0 zero 1\n");
+        let result = TubeNetwork::<f64>::from_path(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("line 2"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_read_json_file() {
+        let path = temp_file(
+            "network.json",
+            r#"{ "nodes": [[0, 0, 0, 2], [10, 0, 0, 1]], "edges": [[0, 1]] }"#,
+        );
+        let network = TubeNetwork::<f64>::from_path(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(network.radii(), &[2.0, 1.0]);
+        assert!((network.eval(-5.0, 0.0, 0.0) - 3.0).abs() < 1E-9);
+    }
+}
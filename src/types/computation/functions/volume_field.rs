@@ -0,0 +1,480 @@
+use log::error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        data::DenseField,
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+        ModelError,
+    },
+    geometry::{BoundingBox, Vec3},
+};
+
+/// Interpolation of the values between the points of a field.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Linear interpolation between the 8 surrounding points.
+    #[default]
+    Trilinear,
+    /// Cubic Catmull-Rom interpolation between the 64 surrounding points, which is smooth across cells.
+    Tricubic,
+}
+
+static VOLUME_FIELD_PARAMETERS: &[Parameter; 3] = &[
+    Parameter {
+        name: "Interpolation",
+        data_type: DataType::Enum(&["Trilinear", "Tricubic"]),
+    },
+    Parameter {
+        name: "Iso Value",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Invert",
+        data_type: DataType::Boolean,
+    },
+];
+
+/// Function which interpolates the values of a [`DenseField`], such as a scan imported with
+/// [`parse_raw_volume`](crate::utils::io::parse_raw_volume) or [`parse_pgm_stack`](crate::utils::io::parse_pgm_stack).
+///
+/// The function returns the interpolated value minus the iso value, so the surface is at the iso value of the field.
+/// For scans where the material has higher values than the background, enable `invert` to get negative values inside the material.
+/// The points of the field are placed from its origin with a separate spacing along each axis, so scans with a different
+/// slice distance than pixel size keep their proportions. By default the spacing is the cell size of the field.
+/// Points outside the field use the value at the closest point of the field bounds.
+/// Along axes with a single point, such as for a single image slice, the field is constant.
+/// The tricubic interpolation extrapolates the field linearly at the borders, so it reproduces linear fields exactly.
+///
+/// # Example
+///
+/// ```rust
+/// # use imlet::types::{computation::{data::DenseField, functions::{Interpolation, VolumeField}, model::ImplicitModel}, geometry::Vec3};
+///
+/// let values: Vec<f64> = (0..27).map(|i| i as f64).collect();
+/// let field = DenseField::from_data(Vec3::origin(), 1.0, (3, 3, 3).into(), values).unwrap();
+/// let volume = VolumeField::from_field(field)
+///     .with_interpolation(Interpolation::Tricubic)
+///     .with_iso_value(13.0);
+///
+/// let mut model = ImplicitModel::new();
+/// model.add_function("Scan", volume).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VolumeField<T> {
+    /// Interpolation between the points of the field.
+    pub interpolation: Interpolation,
+    /// Value of the field at the surface.
+    pub iso_value: T,
+    /// Negate the values, so values above the iso value are inside.
+    pub invert: bool,
+    field: Option<DenseField<T>>,
+    spacing: Vec3<T>,
+}
+
+impl<T: ModelFloat> Default for VolumeField<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ModelFloat> VolumeField<T> {
+    /// Create a new function without field, which evaluates to the maximum value everywhere.
+    pub fn new() -> Self {
+        Self {
+            interpolation: Interpolation::Trilinear,
+            iso_value: T::zero(),
+            invert: false,
+            field: None,
+            spacing: Vec3::new(T::one(), T::one(), T::one()),
+        }
+    }
+
+    /// Create a function which interpolates a field, using the cell size of the field as spacing along all axes.
+    pub fn from_field(field: DenseField<T>) -> Self {
+        let mut volume = Self::new();
+        volume.set_field(field);
+        volume
+    }
+
+    /// Set the distance between the points of the field along each axis. Returns self for chaining.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the spacing is not larger than zero along all axes.
+    pub fn with_spacing(mut self, spacing: Vec3<T>) -> Self {
+        assert!(
+            spacing.x > T::zero() && spacing.y > T::zero() && spacing.z > T::zero(),
+            "Spacing must be larger than zero along all axes."
+        );
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set the interpolation. Returns self for chaining.
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Set the value of the field at the surface. Returns self for chaining.
+    pub fn with_iso_value(mut self, iso_value: T) -> Self {
+        self.iso_value = iso_value;
+        self
+    }
+
+    /// Set whether values above the iso value are inside. Returns self for chaining.
+    pub fn with_invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    /// Replace the field, using the cell size of the field as spacing along all axes.
+    pub fn set_field(&mut self, field: DenseField<T>) {
+        let cell_size = field.cell_size();
+        self.spacing = Vec3::new(cell_size, cell_size, cell_size);
+        self.field = Some(field);
+    }
+
+    /// Returns the interpolated field, if any.
+    ///
+    /// The points are placed with the [`spacing`](VolumeField::spacing) of the function, which can differ from the cell size of the field.
+    pub fn field(&self) -> Option<&DenseField<T>> {
+        self.field.as_ref()
+    }
+
+    /// Returns the distance between the points of the field along each axis.
+    pub fn spacing(&self) -> Vec3<T> {
+        self.spacing
+    }
+
+    /// Check that the field can be interpolated, such as after deserialization.
+    ///
+    /// # Returns
+    ///
+    /// An error if the field has no points along an axis, if the number of values doesn't match the number of points,
+    /// or if the spacing is not larger than zero along all axes.
+    pub fn validate(&self) -> Result<(), ModelError> {
+        let spacing = self.spacing;
+        if !(spacing.x > T::zero() && spacing.y > T::zero() && spacing.z > T::zero()) {
+            return Err(ModelError::Custom(
+                "Volume field spacing must be larger than zero along all axes.".to_string(),
+            ));
+        }
+        let Some(field) = &self.field else {
+            return Ok(());
+        };
+        let n = field.num_points_per_axis();
+        if n.i == 0 || n.j == 0 || n.k == 0 {
+            return Err(ModelError::Custom(format!(
+                "Volume field needs at least one point per axis, found {}x{}x{}.",
+                n.i, n.j, n.k
+            )));
+        }
+        if field.data().len() != n.product() {
+            return Err(ModelError::IncorrectDataSize(
+                field.data().len(),
+                n.product(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return the bounds of the field.
+    pub fn bounds(&self) -> Option<BoundingBox<T>> {
+        self.field.as_ref().map(|field| {
+            let n = field.num_points_per_axis();
+            let size = Vec3::new(
+                T::from(n.i - 1).unwrap() * self.spacing.x,
+                T::from(n.j - 1).unwrap() * self.spacing.y,
+                T::from(n.k - 1).unwrap() * self.spacing.z,
+            );
+            BoundingBox::new(*field.origin(), *field.origin() + size)
+        })
+    }
+
+    /// Interpolate the field at a point.
+    fn interpolate(&self, field: &DenseField<T>, point: Vec3<T>) -> T {
+        let n = field.num_points_per_axis();
+        let local = point - *field.origin();
+        let (i, u) = cell_coordinate(local.x / self.spacing.x, n.i);
+        let (j, v) = cell_coordinate(local.y / self.spacing.y, n.j);
+        let (k, w) = cell_coordinate(local.z / self.spacing.z, n.k);
+        let value = |i: usize, j: usize, k: usize| {
+            field.data()[field.point_index1d(i.min(n.i - 1), j.min(n.j - 1), k.min(n.k - 1))]
+        };
+
+        match self.interpolation {
+            Interpolation::Trilinear => {
+                let lerp = |a: T, b: T, t: T| a + (b - a) * t;
+                let mut planes = [T::zero(); 2];
+                for (dk, plane) in planes.iter_mut().enumerate() {
+                    let row =
+                        |dj: usize| lerp(value(i, j + dj, k + dk), value(i + 1, j + dj, k + dk), u);
+                    *plane = lerp(row(0), row(1), v);
+                }
+                lerp(planes[0], planes[1], w)
+            }
+            Interpolation::Tricubic => {
+                let mut planes = [T::zero(); 4];
+                for (dk, plane) in planes.iter_mut().enumerate() {
+                    let mut rows = [T::zero(); 4];
+                    for (dj, row) in rows.iter_mut().enumerate() {
+                        let points = [0, 1, 2, 3].map(|di| {
+                            extrapolated_value(
+                                field,
+                                [
+                                    (i + di) as isize - 1,
+                                    (j + dj) as isize - 1,
+                                    (k + dk) as isize - 1,
+                                ],
+                            )
+                        });
+                        *row = catmull_rom(points, u);
+                    }
+                    *plane = catmull_rom(rows, v);
+                }
+                catmull_rom(planes, w)
+            }
+        }
+    }
+}
+
+/// Index of the cell containing a local coordinate and the position inside the cell, clamped to the field.
+fn cell_coordinate<T: ModelFloat>(coordinate: T, count: usize) -> (usize, T) {
+    if count < 2 {
+        return (0, T::zero());
+    }
+    let max = T::from(count - 1).unwrap();
+    let clamped = coordinate.max(T::zero()).min(max);
+    let index = clamped.floor().to_usize().unwrap_or(0).min(count - 2);
+    (index, clamped - T::from(index).unwrap())
+}
+
+/// Value of a point of the field, linearly extrapolated for points one step outside the field.
+fn extrapolated_value<T: ModelFloat>(field: &DenseField<T>, index: [isize; 3]) -> T {
+    let n = field.num_points_per_axis();
+    let counts = [n.i as isize, n.j as isize, n.k as isize];
+    let mut index = index;
+    for axis in 0..3 {
+        let (border, inner) = if counts[axis] == 1 {
+            // A single point can't be extrapolated, so the field is constant along the axis.
+            index[axis] = 0;
+            continue;
+        } else if index[axis] < 0 {
+            (0, 1)
+        } else if index[axis] >= counts[axis] {
+            (counts[axis] - 1, counts[axis] - 2)
+        } else {
+            continue;
+        };
+        let mut border_index = index;
+        border_index[axis] = border;
+        let mut inner_index = index;
+        inner_index[axis] = inner;
+        return T::from(2.0).unwrap() * extrapolated_value(field, border_index)
+            - extrapolated_value(field, inner_index);
+    }
+    field.data()[field.point_index1d(index[0] as usize, index[1] as usize, index[2] as usize)]
+}
+
+/// Catmull-Rom spline between the two middle values, at parameter `t` between 0 and 1.
+fn catmull_rom<T: ModelFloat>(values: [T; 4], t: T) -> T {
+    let half = T::from(0.5).unwrap();
+    let [p0, p1, p2, p3] = values;
+    let a = -half * p0 + T::from(1.5).unwrap() * p1 - T::from(1.5).unwrap() * p2 + half * p3;
+    let b = p0 - T::from(2.5).unwrap() * p1 + T::from(2.0).unwrap() * p2 - half * p3;
+    let c = -half * p0 + half * p2;
+    ((a * t + b) * t + c) * t + p1
+}
+
+impl<T: ModelFloat> ImplicitFunction<T> for VolumeField<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let Some(field) = &self.field else {
+            return T::max_value();
+        };
+        let value = self.interpolate(field, Vec3::new(x, y, z)) - self.iso_value;
+        if self.invert {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for VolumeField<T> {
+    fn parameters(&self) -> &[Parameter] {
+        VOLUME_FIELD_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(value) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Interpolation")
+        {
+            match value.as_str() {
+                "Trilinear" => self.interpolation = Interpolation::Trilinear,
+                "Tricubic" => self.interpolation = Interpolation::Tricubic,
+                _ => error!("Unknown interpolation: {}", value),
+            }
+        } else if !Parameter::set_value_from_param(
+            parameter_name,
+            &data,
+            "Iso Value",
+            &mut self.iso_value,
+        ) && !Parameter::set_bool_from_param(
+            parameter_name,
+            &data,
+            "Invert",
+            &mut self.invert,
+        ) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Interpolation" => Some(Data::EnumValue(format!("{:?}", self.interpolation))),
+            "Iso Value" => Some(Data::Value(self.iso_value)),
+            "Invert" => Some(Data::Boolean(self.invert)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "VolumeField"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Field of a linear function, which both interpolations reproduce exactly.
+    fn linear_field() -> DenseField<f64> {
+        let n = 5;
+        let mut values = Vec::new();
+        for k in 0..n {
+            for j in 0..n {
+                for i in 0..n {
+                    values.push(i as f64 + 2.0 * j as f64 - 0.5 * k as f64);
+                }
+            }
+        }
+        DenseField::from_data(Vec3::new(-1.0, -1.0, -1.0), 0.5, (n, n, n).into(), values).unwrap()
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let expected = |x: f64, y: f64, z: f64| 2.0 * (x + 1.0) + 4.0 * (y + 1.0) - (z + 1.0);
+        for interpolation in [Interpolation::Trilinear, Interpolation::Tricubic] {
+            let volume = VolumeField::from_field(linear_field()).with_interpolation(interpolation);
+            for (x, y, z) in [(0.1, 0.2, 0.3), (-1.0, -1.0, -1.0), (0.9, -0.45, 0.65)] {
+                assert!((volume.eval(x, y, z) - expected(x, y, z)).abs() < 1E-9);
+            }
+            // Outside points use the closest point on the bounds.
+            assert!((volume.eval(5.0, 0.0, 0.0) - expected(1.0, 0.0, 0.0)).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_tricubic_passes_through_points() {
+        let values: Vec<f64> = (0..64).map(|i| ((i * 37) % 11) as f64).collect();
+        let field = DenseField::from_data(Vec3::origin(), 1.0, (4, 4, 4).into(), values).unwrap();
+        let volume = VolumeField::from_field(field.clone())
+            .with_interpolation(Interpolation::Tricubic)
+            .with_iso_value(2.0)
+            .with_invert(true);
+        for (index, &value) in field.data().iter().enumerate() {
+            let (i, j, k) = field.point_index3d(index);
+            let result = volume.eval(i as f64, j as f64, k as f64);
+            assert!((result - (2.0 - value)).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_single_point_axes() {
+        let field = DenseField::from_data(
+            Vec3::origin(),
+            1.0,
+            (2, 2, 1).into(),
+            vec![0.0, 1.0, 2.0, 3.0],
+        )
+        .unwrap();
+        for interpolation in [Interpolation::Trilinear, Interpolation::Tricubic] {
+            let volume = VolumeField::from_field(field.clone()).with_interpolation(interpolation);
+            for z in [-1.0f64, 0.0, 2.5] {
+                assert!((volume.eval(0.5, 0.0, z) - 0.5).abs() < 1E-9);
+                assert!((volume.eval(1.0, 1.0, z) - 3.0).abs() < 1E-9);
+            }
+        }
+
+        let single =
+            DenseField::from_data(Vec3::origin(), 1.0, (1, 1, 1).into(), vec![4.0f64]).unwrap();
+        for interpolation in [Interpolation::Trilinear, Interpolation::Tricubic] {
+            let volume = VolumeField::from_field(single.clone()).with_interpolation(interpolation);
+            assert!((volume.eval(0.3, -2.0, 7.0) - 4.0).abs() < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_spacing_per_axis() {
+        let values: Vec<f64> = (0..8).map(|i| i as f64).collect();
+        let field = DenseField::from_data(Vec3::origin(), 1.0, (2, 2, 2).into(), values).unwrap();
+        let volume = VolumeField::from_field(field).with_spacing(Vec3::new(1.0, 2.0, 4.0));
+
+        let bounds = volume.bounds().unwrap();
+        assert!(bounds.max.distance_to_vec3(&Vec3::new(1.0, 2.0, 4.0)) < 1E-9);
+        assert!((volume.eval(0.5, 1.0, 2.0) - 3.5).abs() < 1E-9);
+        assert!((volume.eval(0.0, 2.0, 4.0) - 6.0).abs() < 1E-9);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let mut volume = VolumeField::<f64>::new();
+        assert_eq!(volume.eval(0.0, 0.0, 0.0), f64::MAX);
+
+        volume.set_parameter("Interpolation", Data::EnumValue("Tricubic".to_string()));
+        volume.set_parameter("Iso Value", Data::Value(3.0));
+        volume.set_parameter("Invert", Data::Boolean(true));
+        assert_eq!(volume.interpolation, Interpolation::Tricubic);
+        assert_eq!(volume.iso_value, 3.0);
+        assert!(volume.invert);
+        assert!(matches!(
+            volume.read_parameter("Interpolation"),
+            Some(Data::EnumValue(value)) if value == "Tricubic"
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_volume_field() {
+        use crate::types::computation::model::ImplicitModel;
+
+        let volume = VolumeField::from_field(linear_field())
+            .with_spacing(Vec3::new(0.5, 1.0, 0.25))
+            .with_interpolation(Interpolation::Tricubic)
+            .with_iso_value(1.5);
+        let mut model = ImplicitModel::new();
+        model.add_function("Volume", volume.clone()).unwrap();
+
+        let json = serde_json::to_string(&model).unwrap();
+        let model: ImplicitModel<f64> = serde_json::from_str(&json).unwrap();
+        for (x, y, z) in [(0.1, 0.2, 0.3), (-1.0, 2.5, -0.5), (0.9, -0.45, 0.65)] {
+            let value = model.evaluate_at("Volume", x, y, z).unwrap();
+            assert!((value - volume.eval(x, y, z)).abs() < 1E-9);
+        }
+
+        let extra_value = json.replacen("\"data\":[", "\"data\":[1.0,", 1);
+        let Err(err) = serde_json::from_str::<ImplicitModel<f64>>(&extra_value) else {
+            panic!("Reading a field with the wrong number of values should fail.");
+        };
+        assert!(err.to_string().contains("126"));
+
+        let empty = json.replacen("\"i\":5", "\"i\":0", 1);
+        assert!(serde_json::from_str::<ImplicitModel<f64>>(&empty).is_err());
+    }
+}
@@ -0,0 +1,553 @@
+use std::fmt::Debug;
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
+    },
+    geometry::{BoundingBox, Line, Octree, Vec3},
+};
+
+use super::noise::{hash, unit_value};
+
+const MAX_TREE_DEPTH: usize = 10;
+const MAX_LEAF_POINT_COUNT: usize = 16;
+/// Search radius for lattice edge candidates, relative to the average seed spacing.
+const EDGE_SEARCH_FACTOR: f64 = 3.0;
+
+/// Source of the seed points for a [`Voronoi`] function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub enum VoronoiSeeds<T> {
+    /// Explicit list of seed points.
+    Points(Vec<Vec3<T>>),
+    /// Seeded uniform random distribution of points within a box.
+    Random {
+        bounds: BoundingBox<T>,
+        count: usize,
+        seed: u32,
+    },
+}
+
+/// Output of a [`Voronoi`] function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VoronoiOutput {
+    /// Distance to the closest Voronoi cell wall, which gives a foam when combined with a thickness.
+    CellWalls,
+    /// Distance to the closest edge connecting neighbouring seeds, which gives a strut lattice when offset.
+    DelaunayEdges,
+}
+
+/// Acceleration structures built from the seed points.
+#[derive(Debug, Clone)]
+struct VoronoiData<T> {
+    points: Octree<Vec3<T>, T>,
+    edges: Option<Octree<Line<T>, T>>,
+    search_limit: T,
+    spacing: T,
+}
+
+/// Function returning the distance to the walls or edges of a Voronoi diagram.
+///
+/// The seeds are either provided as points or generated from a seeded random distribution within a [`BoundingBox`].
+/// The seeds are stored in an [`Octree`], so evaluation only considers seeds near the query point.
+///
+/// For [`VoronoiOutput::CellWalls`] the result is the exact distance to the closest bisector wall of the cell containing the point.
+/// For [`VoronoiOutput::DelaunayEdges`] the result is the distance to the closest edge between neighbouring seeds.
+/// The edges are computed as the Gabriel graph, which is the subset of the Delaunay edges whose diametral sphere is empty.
+/// Neighbours are searched within three times the average seed spacing.
+///
+/// Both outputs are unsigned, so they are typically combined with a [`Thickness`](crate::types::computation::operations::shape::Thickness) or an offset.
+///
+/// # Example
+///
+/// ```rust
+/// # use imlet::types::computation::{
+/// #     functions::{Voronoi, VoronoiOutput},
+/// #     model::ImplicitModel,
+/// #     operations::shape::Thickness,
+/// # };
+/// # use imlet::types::geometry::{BoundingBox, Vec3};
+///
+/// let bounds = BoundingBox::new(Vec3::origin(), Vec3::new(50.0, 50.0, 50.0));
+/// let foam = Voronoi::<f64>::random(bounds, 200, 1, VoronoiOutput::CellWalls);
+///
+/// let mut model = ImplicitModel::new();
+/// let foam_tag = model.add_function("Foam", foam).unwrap();
+/// let _ = model
+///     .add_operation("Walls", Thickness::new(1.5), Some(&[&foam_tag]))
+///     .unwrap();
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Voronoi<T> {
+    /// Source of the seed points.
+    pub seeds: VoronoiSeeds<T>,
+    /// Distance measure to return.
+    pub output: VoronoiOutput,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    data: Option<VoronoiData<T>>,
+}
+
+impl<T: Float> Voronoi<T> {
+    /// Create a new Voronoi function from a list of seed points.
+    /// # Arguments
+    ///
+    /// * `points` - Seed points of the cells.
+    /// * `output` - Distance measure to return.
+    pub fn from_points(points: Vec<Vec3<T>>, output: VoronoiOutput) -> Self {
+        let mut voronoi = Self {
+            seeds: VoronoiSeeds::Points(points),
+            output,
+            data: None,
+        };
+        voronoi.build();
+        voronoi
+    }
+
+    /// Create a new Voronoi function from uniformly distributed random seed points.
+    /// # Arguments
+    ///
+    /// * `bounds` - Box in which the seeds are distributed.
+    /// * `count` - Number of seeds.
+    /// * `seed` - Seed for the random distribution.
+    /// * `output` - Distance measure to return.
+    pub fn random(bounds: BoundingBox<T>, count: usize, seed: u32, output: VoronoiOutput) -> Self {
+        let mut voronoi = Self {
+            seeds: VoronoiSeeds::Random {
+                bounds,
+                count,
+                seed,
+            },
+            output,
+            data: None,
+        };
+        voronoi.build();
+        voronoi
+    }
+
+    /// Compute the seed points of the cells.
+    pub fn seed_points(&self) -> Vec<Vec3<T>> {
+        match &self.seeds {
+            VoronoiSeeds::Points(points) => points.clone(),
+            VoronoiSeeds::Random {
+                bounds,
+                count,
+                seed,
+            } => {
+                let (dx, dy, dz) = bounds.dimensions();
+                (0..*count)
+                    .map(|i| {
+                        let i = i as i32;
+                        Vec3::new(
+                            bounds.min.x + dx * unit_value::<T>(hash(i, 0, 0, *seed)),
+                            bounds.min.y + dy * unit_value::<T>(hash(i, 1, 0, *seed)),
+                            bounds.min.z + dz * unit_value::<T>(hash(i, 2, 0, *seed)),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Rebuild the internal search structures from the seeds.
+    ///
+    /// This is done automatically on creation and when parameters change, but is needed after deserialization.
+    pub fn build(&mut self) {
+        let points = self.seed_points();
+        if points.is_empty() {
+            self.data = None;
+            return;
+        }
+
+        let points_tree = Octree::new()
+            .with_objects(&points)
+            .with_max_depth(MAX_TREE_DEPTH)
+            .with_max_leaf_size(MAX_LEAF_POINT_COUNT)
+            .build();
+
+        let bounds = BoundingBox::from_objects(&points);
+        let (dx, dy, dz) = bounds.dimensions();
+        let extent = dx.max(dy).max(dz);
+        let volume = dx * dy * dz;
+        let count = T::from(points.len()).unwrap();
+        let spacing = if volume > T::zero() {
+            (volume / count).cbrt()
+        } else {
+            extent / count.cbrt()
+        }
+        .max(T::epsilon());
+
+        let edges = match self.output {
+            VoronoiOutput::CellWalls => None,
+            VoronoiOutput::DelaunayEdges => {
+                let lines = gabriel_edges(&points, &points_tree, spacing);
+                if lines.is_empty() {
+                    None
+                } else {
+                    Some(
+                        Octree::new()
+                            .with_objects(&lines)
+                            .with_max_depth(MAX_TREE_DEPTH)
+                            .with_max_leaf_size(MAX_LEAF_POINT_COUNT)
+                            .build(),
+                    )
+                }
+            }
+        };
+
+        self.data = Some(VoronoiData {
+            points: points_tree,
+            edges,
+            search_limit: (dx * dx + dy * dy + dz * dz).sqrt(),
+            spacing,
+        });
+    }
+
+    /// Returns the bounds of the seed points if any.
+    pub fn bounds(&self) -> Option<BoundingBox<T>> {
+        self.data.as_ref().and_then(|data| data.points.bounds())
+    }
+
+    fn random_settings(&self) -> (BoundingBox<T>, usize, u32) {
+        match &self.seeds {
+            VoronoiSeeds::Points(points) => {
+                let bounds = if points.is_empty() {
+                    BoundingBox::zero()
+                } else {
+                    BoundingBox::from_objects(points)
+                };
+                (bounds, points.len(), 0)
+            }
+            VoronoiSeeds::Random {
+                bounds,
+                count,
+                seed,
+            } => (*bounds, *count, *seed),
+        }
+    }
+}
+
+/// Compute the distance from a point to the closest wall of the voronoi cell containing it.
+fn wall_distance<T: Float>(data: &VoronoiData<T>, point: &Vec3<T>) -> T {
+    let two = T::from(2.0).unwrap();
+    let Some((closest, _)) = data.points.closest_point(point) else {
+        return T::max_value();
+    };
+    let distance = point.distance_to_vec3(&closest);
+    let max_radius = distance + data.search_limit + data.spacing;
+
+    // The wall towards a seed b is at least (|p - b| - |p - a|) / 2 away, so only seeds within
+    // |p - a| + 2 * best can be closer. Widen the search until that criterion is met.
+    let mut radius = distance + two * data.spacing;
+    loop {
+        let mut best = T::infinity();
+        for other in data.points.collect_nearby_objects(point, radius) {
+            let separation = closest.distance_to_vec3(&other);
+            if separation <= T::zero() {
+                continue;
+            }
+            let wall =
+                (point.distance_to_vec3_squared(&other) - distance * distance) / (two * separation);
+            best = best.min(wall);
+        }
+
+        let required = distance + two * best;
+        if required <= radius || radius >= max_radius {
+            return if best.is_finite() {
+                best.max(T::zero())
+            } else {
+                T::max_value()
+            };
+        }
+        radius = required.min(max_radius);
+    }
+}
+
+/// Compute the Gabriel graph of a set of points, using the octree to find neighbours within a search radius.
+fn gabriel_edges<T: Float>(
+    points: &[Vec3<T>],
+    tree: &Octree<Vec3<T>, T>,
+    spacing: T,
+) -> Vec<Line<T>> {
+    let search = spacing * T::from(EDGE_SEARCH_FACTOR).unwrap();
+    let half = T::from(0.5).unwrap();
+    let mut edges = Vec::new();
+
+    for a in points {
+        // Only consider each pair once. Points on octree cell borders are returned more than once.
+        let mut neighbours: Vec<Vec3<T>> = tree
+            .collect_nearby_objects(a, search)
+            .into_iter()
+            .filter(|b| precedes(a, b))
+            .collect();
+        neighbours.sort_by(|u, v| {
+            (u.x, u.y, u.z)
+                .partial_cmp(&(v.x, v.y, v.z))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        neighbours.dedup_by(|u, v| same_point(u, v));
+
+        for b in neighbours {
+            let center = (*a + b) * half;
+            let radius = a.distance_to_vec3(&b) * half;
+            let blocked = tree
+                .collect_nearby_objects(&center, radius)
+                .iter()
+                .any(|c| !same_point(c, a) && !same_point(c, &b));
+            if !blocked {
+                edges.push(Line::new(*a, b));
+            }
+        }
+    }
+
+    edges
+}
+
+/// Lexicographic ordering of points.
+fn precedes<T: Float>(a: &Vec3<T>, b: &Vec3<T>) -> bool {
+    (a.x, a.y, a.z) < (b.x, b.y, b.z)
+}
+
+fn same_point<T: Float>(a: &Vec3<T>, b: &Vec3<T>) -> bool {
+    a.x == b.x && a.y == b.y && a.z == b.z
+}
+
+static VORONOI_PARAMETERS: &[Parameter; 5] = &[
+    Parameter {
+        name: "Output",
+        data_type: DataType::Enum(&["Cell Walls", "Delaunay Edges"]),
+    },
+    Parameter {
+        name: "Seed",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Count",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Min",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Max",
+        data_type: DataType::Vec3,
+    },
+];
+
+impl<T: ModelFloat> ImplicitFunction<T> for Voronoi<T> {
+    fn eval(&self, x: T, y: T, z: T) -> T {
+        let Some(data) = &self.data else {
+            return T::zero();
+        };
+        let point = Vec3::new(x, y, z);
+        match self.output {
+            VoronoiOutput::CellWalls => wall_distance(data, &point),
+            VoronoiOutput::DelaunayEdges => data
+                .edges
+                .as_ref()
+                .and_then(|edges| edges.closest_point(&point))
+                .map_or(T::max_value(), |(closest, _)| {
+                    closest.distance_to_vec3(&point)
+                }),
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Voronoi<T> {
+    fn parameters(&self) -> &[Parameter] {
+        VORONOI_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(selection) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Output")
+        {
+            match selection.as_str() {
+                "Cell Walls" => self.output = VoronoiOutput::CellWalls,
+                "Delaunay Edges" => self.output = VoronoiOutput::DelaunayEdges,
+                _ => error!("Unknown output option: {}", selection),
+            }
+            self.build();
+            return;
+        }
+
+        let (mut bounds, count, seed) = self.random_settings();
+        let mut count_value = T::from(count).unwrap();
+        let mut seed_value = T::from(seed).unwrap();
+        if Parameter::set_value_from_param(parameter_name, &data, "Seed", &mut seed_value)
+            || Parameter::set_value_from_param(parameter_name, &data, "Count", &mut count_value)
+            || Parameter::set_vec3_from_param(parameter_name, &data, "Min", &mut bounds.min)
+            || Parameter::set_vec3_from_param(parameter_name, &data, "Max", &mut bounds.max)
+        {
+            if let VoronoiSeeds::Points(_) = self.seeds {
+                error!(
+                    "Parameter {} only applies to random seeds. The seed points are kept.",
+                    parameter_name
+                );
+                return;
+            }
+            self.seeds = VoronoiSeeds::Random {
+                bounds,
+                count: count_value
+                    .max(T::zero())
+                    .round()
+                    .to_usize()
+                    .unwrap_or(count),
+                seed: seed_value.max(T::zero()).round().to_u32().unwrap_or(seed),
+            };
+            self.build();
+        } else {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        let (bounds, count, seed) = self.random_settings();
+        match parameter_name {
+            "Output" => match self.output {
+                VoronoiOutput::CellWalls => Some(Data::EnumValue("Cell Walls".to_string())),
+                VoronoiOutput::DelaunayEdges => Some(Data::EnumValue("Delaunay Edges".to_string())),
+            },
+            "Seed" => Some(Data::Value(T::from(seed).unwrap())),
+            "Count" => Some(Data::Value(T::from(count).unwrap())),
+            "Min" => Some(Data::Vec3(bounds.min)),
+            "Max" => Some(Data::Vec3(bounds.max)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Voronoi"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force_wall_distance(points: &[Vec3<f64>], p: Vec3<f64>) -> f64 {
+        let closest = points
+            .iter()
+            .min_by(|a, b| {
+                p.distance_to_vec3(a)
+                    .partial_cmp(&p.distance_to_vec3(b))
+                    .unwrap()
+            })
+            .unwrap();
+        let d = p.distance_to_vec3(closest);
+        points
+            .iter()
+            .filter(|b| !same_point(b, closest))
+            .map(|b| (p.distance_to_vec3_squared(b) - d * d) / (2.0 * closest.distance_to_vec3(b)))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn test_random_seeds_are_deterministic() {
+        let bounds = BoundingBox::new(Vec3::origin(), Vec3::new(10.0, 20.0, 30.0));
+        let a = Voronoi::<f64>::random(bounds, 50, 3, VoronoiOutput::CellWalls);
+        let b = Voronoi::<f64>::random(bounds, 50, 3, VoronoiOutput::CellWalls);
+
+        let points = a.seed_points();
+        assert_eq!(points.len(), 50);
+        for (pa, pb) in points.iter().zip(b.seed_points().iter()) {
+            assert!(same_point(pa, pb));
+            assert!(bounds.contains(pa));
+        }
+    }
+
+    #[test]
+    fn test_wall_distance_matches_brute_force() {
+        let bounds = BoundingBox::new(Vec3::origin(), Vec3::new(10.0, 10.0, 10.0));
+        let voronoi = Voronoi::<f64>::random(bounds, 200, 11, VoronoiOutput::CellWalls);
+        let points = voronoi.seed_points();
+
+        for i in 0..200 {
+            let t = i as f64;
+            let p = Vec3::new(
+                (t * 0.37) % 12.0 - 1.0,
+                (t * 0.73) % 12.0 - 1.0,
+                (t * 0.19) % 12.0 - 1.0,
+            );
+            let expected = brute_force_wall_distance(&points, p);
+            let result = voronoi.eval(p.x, p.y, p.z);
+            assert!(
+                (result - expected).abs() < 1E-9,
+                "Incorrect wall distance at {p}. Was {result} but expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_wall_distance_two_seeds() {
+        let voronoi = Voronoi::from_points(
+            vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0)],
+            VoronoiOutput::CellWalls,
+        );
+
+        assert!((voronoi.eval(0.0, 0.0, 0.0) - 5.0).abs() < 1E-10);
+        assert!((voronoi.eval(2.0, 3.0, 0.0) - 3.0).abs() < 1E-10);
+        assert!(voronoi.eval(5.0, 7.0, 1.0).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_delaunay_edges_on_grid() {
+        let mut points = Vec::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                for k in 0..3 {
+                    points.push(Vec3::new(i as f64, j as f64, k as f64) * 10.0);
+                }
+            }
+        }
+        let lattice = Voronoi::from_points(points, VoronoiOutput::DelaunayEdges);
+
+        assert!(lattice.eval(5.0, 0.0, 0.0).abs() < 1E-10);
+        assert!(lattice.eval(10.0, 10.0, 15.0).abs() < 1E-10);
+        assert!((lattice.eval(-3.0, 0.0, 0.0) - 3.0).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_get_assigns_params() {
+        let mut voronoi = Voronoi::random(
+            BoundingBox::new(Vec3::origin(), Vec3::new(10.0, 10.0, 10.0)),
+            5,
+            1,
+            VoronoiOutput::CellWalls,
+        );
+
+        voronoi.set_parameter("Count", Data::Value(20.0));
+        voronoi.set_parameter("Max", Data::Vec3(Vec3::new(5.0, 5.0, 5.0)));
+        voronoi.set_parameter("Output", Data::EnumValue("Delaunay Edges".to_string()));
+
+        assert_eq!(voronoi.seed_points().len(), 20);
+        assert_eq!(voronoi.output, VoronoiOutput::DelaunayEdges);
+        assert!(voronoi.bounds().is_some());
+    }
+
+    #[test]
+    fn test_random_params_keep_seed_points() {
+        let points = vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0)];
+        let mut voronoi = Voronoi::from_points(points.clone(), VoronoiOutput::CellWalls);
+
+        for name in ["Seed", "Count", "Min", "Max"] {
+            let value = voronoi.read_parameter(name).unwrap();
+            voronoi.set_parameter(name, value);
+        }
+        voronoi.set_parameter("Count", Data::Value(20.0));
+
+        let seed_points = voronoi.seed_points();
+        assert_eq!(seed_points.len(), points.len());
+        for (a, b) in seed_points.iter().zip(&points) {
+            assert!(a.distance_to_vec3(b) < 1E-10);
+        }
+    }
+}
@@ -4,8 +4,10 @@
 //! [`ModelComponent`]: crate::types::computation::model::ModelComponent
 //! [`Functions`]: crate::types::computation::functions::FunctionComponent
 //! [`Operations`]: crate::types::computation::operations::OperationComponent
+//! [`Transforms`]: crate::types::computation::transforms::TransformComponent
 //! [`ImplicitFunction`]: crate::types::computation::traits::ImplicitFunction
 //! [`ImplicitOperation`]: crate::types::computation::traits::ImplicitOperation
+//! [`ImplicitTransform`]: crate::types::computation::traits::ImplicitTransform
 //!
 //! [`ModelError`]: crate::types::computation::ModelError
 //!
@@ -21,7 +23,7 @@
 //!
 //! ## Core Components
 //!
-//! The computation graph in an [`ImplicitModel`] consists of three types of nodes, described by the [`ModelComponent`]:
+//! The computation graph in an [`ImplicitModel`] consists of four types of nodes, described by the [`ModelComponent`]:
 //!
 //! 1. **[`Functions`]**: Transform spatial coordinates to scalar values
 //!    - Implement [`ImplicitFunction`] trait
@@ -35,7 +37,13 @@
 //!    - Examples: boolean operations, arithmetic
 //!    - Evaluated as `f(inputs[]) -> scalar`
 //!
-//! 3. **`Constants`**: Provide fixed scalar values
+//! 3. **[`Transforms`]**: Evaluate an upstream part of the graph in a transformed domain
+//!    - Implement [`ImplicitTransform`] trait
+//!    - Depend on a single input, which is evaluated at the transformed coordinate
+//!    - Examples: linear and polar arrays, mirroring, twisting and bending
+//!    - Evaluated as `input(t(x, y, z)) -> scalar`
+//!
+//! 4. **`Constants`**: Provide fixed scalar values
 //!    - Examples: thresholds, scale factors
 //!    - No dependencies
 //!
@@ -96,10 +104,14 @@
 //!    - Implement [`ImplicitOperation`] trait
 //!    - Define input requirements
 //!
-//! In both cases the struct has to be thread safe.
+//! 3. **Custom Transforms**:
+//!    - Implement [`ImplicitTransform`] trait
+//!    - Define coordinate mapping
+//!
+//! In all cases the struct has to be thread safe.
 //!
 //! > ⚠️ **Note**: Due to Rust's lack of runtime reflection, when using the `serde` feature, deserialization of custom implementations
-//! > of [`ImplicitFunction`] and [`ImplicitOperation`] is not currently supported.
+//! > of [`ImplicitFunction`], [`ImplicitOperation`] and [`ImplicitTransform`] is not currently supported.
 //!
 
 /// Error types related to model computation.
@@ -120,4 +132,7 @@ pub mod operations;
 /// Traits for defining custom implicit functions and operations.
 pub mod traits;
 
+/// Domain transforms such as repetition, mirroring and deformations.
+pub mod transforms;
+
 pub use model_error::*;
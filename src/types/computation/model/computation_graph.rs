@@ -2,7 +2,10 @@ use std::cell::RefCell;
 
 use smallvec::SmallVec;
 
-use crate::types::computation::traits::ModelFloat;
+use crate::types::{
+    computation::traits::{ImplicitTransform, ModelFloat},
+    geometry::Vec3,
+};
 
 use super::{ComponentId, ComponentValues, ModelComponent};
 
@@ -10,12 +13,29 @@ use super::{ComponentId, ComponentValues, ModelComponent};
 /// If a components has more inputs, they will be on the heap. It's allowed but will probably slow things down a bit.
 const INPUT_STACK_BUFFER_SIZE: usize = 8;
 
+/// Number of transformed domains that are stack-allocated during evaluation.
+const DOMAIN_STACK_BUFFER_SIZE: usize = 8;
+
+/// Identifier of a coordinate domain in a [`ComputationGraph`]. The global domain always has id 0.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct DomainId(pub usize);
+
+impl DomainId {
+    /// The untransformed, global, domain.
+    pub const GLOBAL: DomainId = DomainId(0);
+}
+
 /// Defines a set of components which should be computed to generate an output.
 ///
 /// The components are extracted from the model based on the target output.
+///
+/// Each component is evaluated in a domain. Components upstream of a [`ModelComponent::Transform`] are placed
+/// in a separate domain, for which the coordinates are computed from the parent domain before the components are evaluated.
 pub(crate) struct ComputationGraph<'a, T: ModelFloat + 'static> {
     components: Vec<&'a ModelComponent<T>>,
     inputs: Vec<Vec<ComponentId>>,
+    component_domains: Vec<DomainId>,
+    domains: Vec<(DomainId, &'a dyn ImplicitTransform<T>)>,
 }
 
 impl<'a, T: ModelFloat + 'static> ComputationGraph<'a, T> {
@@ -24,13 +44,39 @@ impl<'a, T: ModelFloat + 'static> ComputationGraph<'a, T> {
         Self {
             components: Vec::new(),
             inputs: Vec::new(),
+            component_domains: Vec::new(),
+            domains: Vec::new(),
         }
     }
 
-    /// Add the reference to a [`ModelComponent`] from the main model, which should be computed.
-    pub fn add_component(&mut self, component: &'a ModelComponent<T>, inputs: Vec<ComponentId>) {
+    /// Add the reference to a [`ModelComponent`] from the main model, which should be computed in a specific domain.
+    ///
+    /// Returns the index of the component in the graph.
+    pub fn add_component(
+        &mut self,
+        component: &'a ModelComponent<T>,
+        inputs: Vec<ComponentId>,
+        domain: DomainId,
+    ) -> usize {
         self.components.push(component);
         self.inputs.push(inputs);
+        self.component_domains.push(domain);
+        self.components.len() - 1
+    }
+
+    /// Add a new domain, which is computed by applying a transform to the coordinates of a parent domain.
+    pub fn add_domain(
+        &mut self,
+        transform: &'a dyn ImplicitTransform<T>,
+        parent: DomainId,
+    ) -> DomainId {
+        self.domains.push((parent, transform));
+        DomainId(self.domains.len())
+    }
+
+    /// Total number of components in the graph, across all domains.
+    pub fn num_components(&self) -> usize {
+        self.components.len()
     }
 }
 
@@ -41,19 +87,33 @@ impl<T: ModelFloat + 'static> ComputationGraph<'_, T> {
 
     /// Evaluate the computation graph at a specific coordinate.
     pub fn evaluate_at_coord(&self, x: T, y: T, z: T) -> T {
+        let coords = self.domain_coords(x, y, z);
         Self::COMPONENT_VALUES.with(|values| {
             let mut values = values.borrow_mut();
             values.resize(self.components.len());
 
             for (index, &component) in self.components.iter().enumerate() {
                 let inputs = self.inputs(index, &values);
-                let val = component.compute(x, y, z, &inputs);
+                let coord = coords[self.component_domains[index].0];
+                let val = component.compute(coord.x, coord.y, coord.z, &inputs);
                 values.set(index, val);
             }
             values.last()
         })
     }
 
+    /// Compute the coordinates in each domain of the graph. Parent domains are always added before child domains.
+    #[inline(always)]
+    fn domain_coords(&self, x: T, y: T, z: T) -> SmallVec<[Vec3<T>; DOMAIN_STACK_BUFFER_SIZE]> {
+        let mut coords = SmallVec::<[Vec3<T>; DOMAIN_STACK_BUFFER_SIZE]>::new();
+        coords.push(Vec3::new(x, y, z));
+        for (parent, transform) in self.domains.iter() {
+            let coord = coords[parent.0];
+            coords.push(transform.transform(coord.x, coord.y, coord.z));
+        }
+        coords
+    }
+
     /// Retrieve the values for the inputs of a component.
     #[inline(always)]
     fn inputs(
@@ -96,7 +156,7 @@ mod tests {
             size * 0.45,
         )));
 
-        graph.add_component(&binding, vec![]);
+        graph.add_component(&binding, vec![], DomainId::GLOBAL);
 
         // Discretize
         let mut field = DenseField::new(
@@ -129,7 +189,7 @@ mod tests {
             size * 0.50,
         )));
 
-        model.add_component(&sphere, vec![]);
+        model.add_component(&sphere, vec![], DomainId::GLOBAL);
 
         // Discretize
         let mut field = DenseField::new(
@@ -159,9 +219,13 @@ mod tests {
 
         let difference_component = ModelComponent::Operation(Box::new(BooleanDifference::new()));
 
-        model.add_component(&sphere_component, vec![]);
-        model.add_component(&sphere_component2, vec![]);
-        model.add_component(&difference_component, vec![0.into(), 1.into()]);
+        model.add_component(&sphere_component, vec![], DomainId::GLOBAL);
+        model.add_component(&sphere_component2, vec![], DomainId::GLOBAL);
+        model.add_component(
+            &difference_component,
+            vec![0.into(), 1.into()],
+            DomainId::GLOBAL,
+        );
 
         assert!(0.5 - model.evaluate_at_coord(0.0, 0.0, 0.0) < 0.001);
         assert!(model.evaluate_at_coord(0.5, 0.0, 0.0) < 0.001);
@@ -176,9 +240,13 @@ mod tests {
     fn test_evaluate_model_constant_operation() {
         let mut model = ComputationGraph::new();
 
-        model.add_component(&ModelComponent::Constant(1.0), vec![]);
+        model.add_component(&ModelComponent::Constant(1.0), vec![], DomainId::GLOBAL);
         let addition_component = ModelComponent::Operation(Box::new(Add::new()));
-        model.add_component(&addition_component, vec![0.into(), 0.into()]);
+        model.add_component(
+            &addition_component,
+            vec![0.into(), 0.into()],
+            DomainId::GLOBAL,
+        );
 
         let result = model.evaluate_at_coord(0.0, 0.0, 0.0);
         assert!((2.0 - result).abs() < 0.0001);
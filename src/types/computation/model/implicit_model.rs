@@ -1,4 +1,6 @@
-use crate::types::computation::traits::{ImplicitFunction, ImplicitOperation, ModelFloat};
+use crate::types::computation::traits::{
+    ImplicitFunction, ImplicitOperation, ImplicitTransform, ModelFloat,
+};
 use crate::types::computation::ModelError;
 use crate::IMLET_VERSION;
 use log::{debug, info};
@@ -6,8 +8,8 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{self, Debug, Display};
 use std::time::Instant;
 
-use super::ComputationGraph;
 use super::{ComponentId, ModelComponent};
+use super::{ComputationGraph, DomainId};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -188,6 +190,37 @@ impl<T: ModelFloat> ImplicitModel<T> {
         Ok(tag_string)
     }
 
+    /// Add a transform component to the model, optionally with an input.
+    ///
+    /// The input, and all components it depends on, will be evaluated in the domain defined by the transform.
+    /// # Arguments
+    ///
+    /// * `tag` - The tag of the transform component added. This is used to reference the component for input and output assignments.
+    /// * `transform` - The transform to add.
+    /// * `input` - Optional tag for the component which provides the input.
+    /// # Returns
+    ///      
+    /// * `Result<String, ModelError>` - Returns `Ok(String)` with the tag if the transform is added successfully, or `Err(ModelError)` if something goes wrong.
+    pub fn add_transform<F: ImplicitTransform<T> + 'static>(
+        &mut self,
+        tag: &str,
+        transform: F,
+        input: Option<&str>,
+    ) -> Result<String, ModelError> {
+        let tag_string = tag.to_string();
+        self.verify_tag_is_free(&tag_string)?;
+
+        self.inputs
+            .insert(tag_string.clone(), vec![input.map(|s| s.to_string())]);
+        self.components.insert(
+            tag_string.clone(),
+            ModelComponent::Transform(Box::new(transform)),
+        );
+
+        self.default_output = Some(tag_string.clone());
+        Ok(tag_string)
+    }
+
     /// Add a tagged constant value to the model, which can be processed in other components.
     /// # Arguments
     ///
@@ -341,6 +374,9 @@ impl<T: ModelFloat> ImplicitModel<T> {
                 self.inputs
                     .insert(valid_tag.clone(), vec![None; operation.inputs().len()]);
             }
+            ModelComponent::Transform(_) => {
+                self.inputs.insert(valid_tag.clone(), vec![None]);
+            }
         }
 
         self.components.insert(valid_tag.clone(), component);
@@ -467,7 +503,9 @@ impl<T: ModelFloat> ImplicitModel<T> {
         Ok(())
     }
 
-    /// Return all the sources upon which a component depends.
+    /// Return all the sources upon which a component depends, within the same domain.
+    ///
+    /// The inputs of transform components are not traversed, as they are evaluated in a separate domain.
     ///
     /// Returns a HashMap with all dependends by tag and index if valid.
     fn gather_dependencies_for_component(
@@ -485,7 +523,7 @@ impl<T: ModelFloat> ImplicitModel<T> {
             visited.insert(node.clone());
 
             // Add all direct inputs of this node to the stack
-            if let Some(inputs) = self.inputs.get(&node) {
+            if let Some(inputs) = self.domain_inputs(&node) {
                 for input in inputs.iter().filter_map(|opt| opt.as_ref()) {
                     stack.push(input.clone());
                 }
@@ -512,7 +550,7 @@ impl<T: ModelFloat> ImplicitModel<T> {
         // Initialize graph and in-degree for relevant nodes
         for node in relevant_nodes.iter() {
             in_degree.insert(node.clone(), 0);
-            if let Some(deps) = self.inputs.get(node) {
+            if let Some(deps) = self.domain_inputs(node) {
                 for dep in deps.iter().filter_map(|opt| opt.as_ref()) {
                     if relevant_nodes.contains(dep) {
                         graph
@@ -555,17 +593,21 @@ impl<T: ModelFloat> ImplicitModel<T> {
         }
     }
 
-    fn assemble_computation_graph(
-        &self,
+    /// Append the sorted components to the computation graph, evaluated in the specified domain.
+    ///
+    /// For each transform component, the subgraph feeding the input is compiled into a new domain.
+    /// The tags of the transforms currently being expanded are tracked to detect cyclic dependencies.
+    ///
+    /// Returns the index of the last added component, which is the target of the sorted sources.
+    fn assemble_computation_graph<'a>(
+        &'a self,
         sorted_sources: &[String],
-    ) -> Result<ComputationGraph<T>, ModelError> {
-        let mut graph = ComputationGraph::new();
-
-        let tag_to_index: HashMap<String, usize> = sorted_sources
-            .iter()
-            .enumerate()
-            .map(|(i, tag)| (tag.clone(), i))
-            .collect();
+        domain: DomainId,
+        graph: &mut ComputationGraph<'a, T>,
+        active_transforms: &mut Vec<String>,
+    ) -> Result<usize, ModelError> {
+        let mut tag_to_index: HashMap<&str, usize> = HashMap::new();
+        let mut last_index = 0;
 
         for component_tag in sorted_sources.iter() {
             let component = self
@@ -575,24 +617,45 @@ impl<T: ModelFloat> ImplicitModel<T> {
 
             let component_inputs = self.valid_inputs(component_tag)?;
 
-            let inputs_indices = component_inputs
-                .iter()
-                .map(|s| {
-                    tag_to_index
-                        .get(s)
-                        .map(|&idx| ComponentId(idx))
-                        .ok_or_else(|| ModelError::MissingTag(s.clone()))
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+            let inputs_indices = if let ModelComponent::Transform(transform) = component {
+                if active_transforms.contains(component_tag) {
+                    return Err(ModelError::CyclicDependency(component_tag.clone()));
+                }
+                active_transforms.push(component_tag.clone());
+
+                let transformed_domain = graph.add_domain(transform.as_ref(), domain);
+                let sources = self.gather_dependencies_for_component(&component_inputs[0])?;
+                let sorted = self.topological_sort_subset(sources)?;
+                let index = self.assemble_computation_graph(
+                    &sorted,
+                    transformed_domain,
+                    graph,
+                    active_transforms,
+                )?;
+
+                active_transforms.pop();
+                vec![ComponentId(index)]
+            } else {
+                component_inputs
+                    .iter()
+                    .map(|s| {
+                        tag_to_index
+                            .get(s.as_str())
+                            .map(|&idx| ComponentId(idx))
+                            .ok_or_else(|| ModelError::MissingTag(s.clone()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            };
 
             // Add the component and its inputs to the graph
-            graph.add_component(component, inputs_indices);
+            last_index = graph.add_component(component, inputs_indices, domain);
+            tag_to_index.insert(component_tag, last_index);
         }
 
-        Ok(graph)
+        Ok(last_index)
     }
 
-    pub(crate) fn compile(&self, target: &str) -> Result<ComputationGraph<T>, ModelError> {
+    pub(crate) fn compile(&self, target: &str) -> Result<ComputationGraph<'_, T>, ModelError> {
         let before = Instant::now();
         let target_output = target.to_string();
 
@@ -600,17 +663,33 @@ impl<T: ModelFloat> ImplicitModel<T> {
 
         let sorted_sources = self.topological_sort_subset(sources)?;
 
-        let graph = self.assemble_computation_graph(&sorted_sources)?;
+        let mut graph = ComputationGraph::new();
+        self.assemble_computation_graph(
+            &sorted_sources,
+            DomainId::GLOBAL,
+            &mut graph,
+            &mut Vec::new(),
+        )?;
 
         log::info!(
             "Computation graph with {} components compiled in {:.2?}",
-            sorted_sources.len(),
+            graph.num_components(),
             before.elapsed()
         );
 
         Ok(graph)
     }
 
+    /// Returns the inputs of a component which are evaluated in the same domain as the component itself.
+    ///
+    /// This is all inputs for operations, and none for transforms.
+    fn domain_inputs(&self, component: &str) -> Option<&Vec<Option<String>>> {
+        match self.components.get(component) {
+            Some(ModelComponent::Transform(_)) => None,
+            _ => self.inputs.get(component),
+        }
+    }
+
     fn valid_inputs(&self, component: &str) -> Result<Vec<String>, ModelError> {
         let default = Vec::new();
         let option_inputs = self.inputs.get(component).unwrap_or(&default);
@@ -4,7 +4,9 @@ use num_traits::Float;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::types::computation::traits::{ImplicitFunction, ImplicitOperation, ModelFloat};
+use crate::types::computation::traits::{
+    ImplicitFunction, ImplicitOperation, ImplicitTransform, ModelFloat,
+};
 
 use super::{Data, DataType, Parameter};
 
@@ -27,13 +29,18 @@ impl From<usize> for ComponentId {
 /// * `Constant` - Representing a constant value across the entire model domain.
 /// * `Function` - Represents a function in 3d space `f(x,y,z)`. This takes no inputs from other components, and is variable across the domain.
 /// * `Operation` - Represents an operation on some values in the model. The operation does not depend on the evaluation coordinate directly, but instead operates on the output of other components.
+/// * `Transform` - Represents a transformation of the domain. The single input, and everything it depends on, is evaluated at the transformed coordinate.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ModelComponent<T: ModelFloat + 'static> {
     Constant(T),
     Function(Box<dyn ImplicitFunction<T>>),
     Operation(Box<dyn ImplicitOperation<T>>),
+    Transform(Box<dyn ImplicitTransform<T>>),
 }
 
+/// Input names for all [`ModelComponent::Transform`] components, which always take a single input.
+pub(crate) static TRANSFORM_INPUT_NAMES: [&str; 1] = ["Shape"];
+
 impl<T: ModelFloat> ModelComponent<T> {
     /// Evaluate the output of the compoP1nent
     ///
@@ -42,12 +49,13 @@ impl<T: ModelFloat> ModelComponent<T> {
     /// * `x` - The current x coordinate. Used when the type is [`ModelComponent::Function`].
     /// * `y` - The current y coordinate. Used when the type is [`ModelComponent::Function`].
     /// * `z` - The current z coordinate. Used when the type is [`ModelComponent::Function`].
-    /// * `inputs` - The outputs of other components which feed the inputs of this one. Used when the type is [`ModelComponent::Operation`].
+    /// * `inputs` - The outputs of other components which feed the inputs of this one. Used when the type is [`ModelComponent::Operation`] or [`ModelComponent::Transform`].
     pub fn compute(&self, x: T, y: T, z: T, inputs: &[T]) -> T {
         match self {
             ModelComponent::Constant(value) => *value,
             ModelComponent::Function(function) => function.eval(x, y, z),
             ModelComponent::Operation(operation) => operation.eval(inputs),
+            ModelComponent::Transform(_) => inputs[0],
         }
     }
 
@@ -57,6 +65,7 @@ impl<T: ModelFloat> ModelComponent<T> {
             ModelComponent::Constant(_) => "Constant",
             ModelComponent::Function(function) => function.name(),
             ModelComponent::Operation(operation) => operation.name(),
+            ModelComponent::Transform(transform) => transform.name(),
         }
     }
 
@@ -91,6 +100,18 @@ impl<T: ModelFloat> ModelComponent<T> {
                     )
                 })
                 .collect(),
+            ModelComponent::Transform(transform) => transform
+                .parameters()
+                .iter()
+                .map(|p| {
+                    (
+                        p.clone(),
+                        transform.read_parameter(p.name).unwrap_or_else(|| panic!("Parameter {} returned None from transform {}, but it should be valid",
+                            p.name,
+                            transform.name())),
+                    )
+                })
+                .collect(),
         }
     }
 
@@ -102,6 +123,7 @@ impl<T: ModelFloat> ModelComponent<T> {
             }
             ModelComponent::Function(function) => function.set_parameter(parameter_name, data),
             ModelComponent::Operation(operation) => operation.set_parameter(parameter_name, data),
+            ModelComponent::Transform(transform) => transform.set_parameter(parameter_name, data),
         }
     }
 
@@ -111,6 +133,7 @@ impl<T: ModelFloat> ModelComponent<T> {
             ModelComponent::Constant(_) => &[],
             ModelComponent::Function(_) => &[],
             ModelComponent::Operation(operation) => operation.inputs(),
+            ModelComponent::Transform(_) => &TRANSFORM_INPUT_NAMES,
         }
     }
 }
@@ -6,8 +6,9 @@ use serde::Deserialize;
 use crate::types::{
     computation::{
         functions::*,
-        operations::{math::*, shape::*},
-        traits::{ImplicitFunction, ImplicitOperation, ModelFloat},
+        operations::{math::*, shape::*, tpms::*},
+        traits::{ImplicitFunction, ImplicitOperation, ImplicitTransform, ModelFloat},
+        transforms::{deformation::*, repetition::*, TransformComponent},
     },
     geometry::*,
 };
@@ -38,6 +39,19 @@ impl<T: ModelFloat> serde::Serialize for dyn ImplicitOperation<T> {
     }
 }
 
+impl<T: ModelFloat> serde::Serialize for dyn ImplicitTransform<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut ser = serializer.serialize_map(Some(1))?;
+        let type_info = self.name();
+        ser.serialize_entry(type_info, &Wrap(self))?;
+        ser.end()
+    }
+}
+
 // Deserialize function
 struct Wrap<'a, T: ?Sized>(pub &'a T);
 impl<'a, T> serde::Serialize for Wrap<'a, T>
@@ -151,6 +165,73 @@ fn function_runtime_reflection<'de, T: ModelFloat + Deserialize<'de> + 'static>(
                 };
                 Some(deserialize_fn)
             }
+            FunctionComponent::PerlinNoise => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: PerlinNoise<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::SimplexNoise => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: SimplexNoise<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::WorleyNoise => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: WorleyNoise<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::Voronoi => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let mut s: Voronoi<T> = erased_serde::deserialize(deserializer)?;
+                    s.build();
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::StrutLattice => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: StrutLattice<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::TubeNetwork => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let mut s: TubeNetwork<T> = erased_serde::deserialize(deserializer)?;
+                    s.build();
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::MeshLattice => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let mut s: MeshLattice<T> = erased_serde::deserialize(deserializer)?;
+                    s.build();
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::ConformalTpms => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: ConformalTpms<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
             FunctionComponent::XDomain => {
                 let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
                     let s: XDomain<T> = erased_serde::deserialize(deserializer)?;
@@ -183,6 +264,30 @@ fn function_runtime_reflection<'de, T: ModelFloat + Deserialize<'de> + 'static>(
                 };
                 Some(deserialize_fn)
             }
+            FunctionComponent::CylindricalValue => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: CylindricalValue<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::SphericalValue => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: SphericalValue<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::PlaneValue => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: PlaneValue<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
             FunctionComponent::Sphere => {
                 let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
                     let s: Sphere<T> = erased_serde::deserialize(deserializer)?;
@@ -223,15 +328,60 @@ fn function_runtime_reflection<'de, T: ModelFloat + Deserialize<'de> + 'static>(
                 };
                 Some(deserialize_fn)
             }
+            FunctionComponent::Extrusion => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: Extrusion<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::Revolution => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: Revolution<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
             FunctionComponent::MeshFile => {
                 let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
                     let mut s: MeshFile<T> = erased_serde::deserialize(deserializer)?;
+                    s.build()
+                        .map_err(<erased_serde::Error as serde::de::Error>::custom)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::PointCloud => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let mut s: PointCloud<T> = erased_serde::deserialize(deserializer)?;
                     s.build();
                     let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
                     Ok(boxed_trait_object)
                 };
                 Some(deserialize_fn)
             }
+            FunctionComponent::HrbfSurface => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let mut s: HrbfSurface<T> = erased_serde::deserialize(deserializer)?;
+                    s.build();
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            FunctionComponent::VolumeField => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: VolumeField<T> = erased_serde::deserialize(deserializer)?;
+                    s.validate()
+                        .map_err(<erased_serde::Error as serde::de::Error>::custom)?;
+                    let boxed_trait_object: Box<dyn ImplicitFunction<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
         },
         Err(_) => None,
     }
@@ -341,16 +491,16 @@ fn operation_runtime_reflection<'de, T: ModelFloat + Deserialize<'de> + 'static>
             Ok(boxed_trait_object)
         };
         Some(deserialize_fn)
-    } else if type_info == "LinearInterpolation" {
+    } else if type_info == "Lerp" {
         let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
-            let s: LinearInterpolation<T> = erased_serde::deserialize(deserializer)?;
+            let s: Lerp<T> = erased_serde::deserialize(deserializer)?;
             let boxed_trait_object: Box<dyn ImplicitOperation<T>> = Box::new(s);
             Ok(boxed_trait_object)
         };
         Some(deserialize_fn)
-    } else if type_info == "VariableLinearInterpolation" {
+    } else if type_info == "VariableLerp" {
         let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
-            let s: VariableLinearInterpolation = erased_serde::deserialize(deserializer)?;
+            let s: VariableLerp = erased_serde::deserialize(deserializer)?;
             let boxed_trait_object: Box<dyn ImplicitOperation<T>> = Box::new(s);
             Ok(boxed_trait_object)
         };
@@ -397,15 +547,163 @@ fn operation_runtime_reflection<'de, T: ModelFloat + Deserialize<'de> + 'static>
             Ok(boxed_trait_object)
         };
         Some(deserialize_fn)
+    } else if type_info == "GradedTpms" {
+        let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+            let s: GradedTpms<T> = erased_serde::deserialize(deserializer)?;
+            let boxed_trait_object: Box<dyn ImplicitOperation<T>> = Box::new(s);
+            Ok(boxed_trait_object)
+        };
+        Some(deserialize_fn)
     } else {
         None
     }
 }
 
+// Deserialize transforms
+
+impl<'de, T: ModelFloat + Deserialize<'de> + 'static> serde::Deserialize<'de>
+    for Box<dyn ImplicitTransform<T>>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = TransformVisitor::new();
+        deserializer.deserialize_map(visitor)
+    }
+}
+
+struct TransformVisitor<T: ModelFloat> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: ModelFloat> TransformVisitor<T> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: ModelFloat + Deserialize<'de> + 'static> serde::de::Visitor<'de>
+    for TransformVisitor<T>
+{
+    type Value = Box<dyn ImplicitTransform<T>>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "Trait object 'dyn ImplicitTransform'")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let type_info = map.next_key::<String>()?.ok_or(serde::de::Error::custom(
+            "Expected externally tagged 'dyn ImplicitTransform'",
+        ))?;
+        let deserialize_fn =
+            transform_runtime_reflection(&type_info).ok_or(serde::de::Error::custom(format!(
+                "Unknown type for 'dyn ImplicitTransform': {type_info}"
+            )))?;
+        let boxed_trait_object: Box<dyn ImplicitTransform<T>> =
+            map.next_value_seed(TransformTypeVisitor { deserialize_fn })?;
+        Ok(boxed_trait_object)
+    }
+}
+
+struct TransformTypeVisitor<'de, T: Float> {
+    deserialize_fn: DeserializeTransformFn<'de, T>,
+}
+
+impl<'de, T: Float> serde::de::DeserializeSeed<'de> for TransformTypeVisitor<'de, T> {
+    type Value = Box<dyn ImplicitTransform<T>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        let deserialize_fn = self.deserialize_fn;
+        deserialize_fn(&mut erased).map_err(serde::de::Error::custom)
+    }
+}
+
+type DeserializeTransformFn<'de, T> = fn(
+    &mut dyn erased_serde::Deserializer<'de>,
+) -> erased_serde::Result<Box<dyn ImplicitTransform<T>>>;
+
+fn transform_runtime_reflection<'de, T: ModelFloat + Deserialize<'de> + 'static>(
+    type_info: &str,
+) -> Option<DeserializeTransformFn<'de, T>> {
+    match TransformComponent::from_str(type_info) {
+        Ok(component) => match component {
+            TransformComponent::LinearArray => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: LinearArray<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitTransform<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            TransformComponent::PolarArray => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: PolarArray<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitTransform<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            TransformComponent::Mirror => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: Mirror<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitTransform<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            TransformComponent::Twist => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: Twist<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitTransform<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            TransformComponent::Bend => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: Bend<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitTransform<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            TransformComponent::Taper => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: Taper<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitTransform<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+            TransformComponent::Shear => {
+                let deserialize_fn = |deserializer: &mut dyn erased_serde::Deserializer<'de>| {
+                    let s: Shear<T> = erased_serde::deserialize(deserializer)?;
+                    let boxed_trait_object: Box<dyn ImplicitTransform<T>> = Box::new(s);
+                    Ok(boxed_trait_object)
+                };
+                Some(deserialize_fn)
+            }
+        },
+        Err(_) => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
-    use crate::types::computation::{model::ImplicitModel, operations::OPERATION_COMPONENTS};
+    use crate::types::computation::{
+        model::ImplicitModel, operations::OPERATION_COMPONENTS, transforms::TRANSFORM_COMPONENTS,
+    };
 
     use super::FUNCTION_COMPONENTS;
 
@@ -456,4 +754,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_serialize_deserialize_transforms() {
+        let mut model: ImplicitModel<f32> = ImplicitModel::new();
+        let mut tags: Vec<String> = Vec::new();
+
+        for transform in TRANSFORM_COMPONENTS {
+            let component = transform.create_default();
+            let tag = model
+                .add_component(component.type_name(), component)
+                .unwrap();
+            tags.push(tag);
+        }
+
+        let model_json = serde_json::to_string_pretty(&model).unwrap();
+        let deserialized_model: ImplicitModel<f32> = serde_json::from_str(&model_json).unwrap();
+
+        for tag in &tags {
+            assert!(
+                deserialized_model.get_component(tag).is_some(),
+                "Component with tag '{tag}' is missing in the deserialized model"
+            );
+        }
+    }
 }
@@ -35,6 +35,11 @@ pub enum ModelError {
     IncorrectDataSize(usize, usize),
     /// Model has no default output assigned.
     NoDefaultOutput,
+    /// A file referenced by a component could not be read.
+    FileRead {
+        path: String,
+        message: String,
+    },
     /// A generic error with a custom message.
     Custom(String),
 }
@@ -87,6 +92,9 @@ impl std::fmt::Display for ModelError {
             ModelError::MissingRequiredParam(param) => {
                 write!(f, "Required parameter {param} is missing from the builder.")
             }
+            ModelError::FileRead { path, message } => {
+                write!(f, "Failed to read file {path}: {message}")
+            }
             ModelError::Custom(message) => write!(f, "{message}"),
             ModelError::IncorrectDataSize(data_size, field_size) => {
                 write!(f, "The provided data buffer of size (size={data_size}) is not matching the point count of the field (n={field_size}).")
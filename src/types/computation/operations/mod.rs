@@ -2,6 +2,8 @@
 pub mod math;
 /// Shape operations such as booleans and offsets.
 pub mod shape;
+/// Triply periodic surfaces driven by input fields.
+pub mod tpms;
 
 mod operation_components;
 pub use operation_components::*;
@@ -2,10 +2,12 @@
 use serde::{Deserialize, Serialize};
 
 use crate::types::computation::{
+    functions::TpmsSurface,
     model::ModelComponent,
     operations::{
         math::{Add, Divide, Lerp, Multiply, Remap, Subtract, VariableLerp},
         shape::{BooleanDifference, BooleanIntersection, BooleanUnion, Offset, Thickness},
+        tpms::GradedTpms,
     },
     traits::{ImplicitOperation, ModelFloat},
 };
@@ -26,6 +28,7 @@ pub enum OperationComponent {
     BooleanIntersection,
     Offset,
     Thickness,
+    GradedTpms,
 }
 
 impl OperationComponent {
@@ -48,6 +51,10 @@ impl OperationComponent {
             OperationComponent::BooleanDifference => Box::new(BooleanDifference::new()),
             OperationComponent::Offset => Box::new(Offset::new(T::zero())),
             OperationComponent::Thickness => Box::new(Thickness::new(T::one())),
+            // Lattice
+            OperationComponent::GradedTpms => {
+                Box::new(GradedTpms::new(TpmsSurface::Gyroid, T::from(15).unwrap()))
+            }
         };
 
         ModelComponent::Operation(op)
@@ -70,6 +77,8 @@ pub const OPERATION_COMPONENTS: &[OperationComponent] = &[
     OperationComponent::BooleanDifference,
     OperationComponent::Offset,
     OperationComponent::Thickness,
+    // Lattice
+    OperationComponent::GradedTpms,
 ];
 
 #[cfg(test)]
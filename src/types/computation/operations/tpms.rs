@@ -0,0 +1,274 @@
+use std::fmt::Debug;
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::computation::{
+    functions::TpmsSurface,
+    model::{Data, DataType, Parameter},
+    traits::{ImplicitComponent, ImplicitOperation, ModelFloat},
+};
+
+/// Operation to generate a triply periodic surface with a cell size and wall thickness driven by input fields.
+///
+/// Scaling the coordinates directly by a varying period, e.g. *sin(x / period(x))*, compresses and tears the cells where the period changes,
+/// since the phase no longer grows with the distance travelled. Instead, the surface is evaluated at two fixed period levels,
+/// *base_period · ratio^k* and *base_period · ratio^(k+1)*, which bracket the local period, and the two distances are blended by the
+/// logarithmic position of the period between them. Each level is a regular surface with a constant phase, so the result stays continuous
+/// for any period field. With an integer ratio the levels are commensurate, so the cells of neighbouring levels line up at the cell boundaries.
+///
+/// A constant period field equal to one of the levels reproduces the regular surface exactly.
+///
+/// This operation takes five inputs.
+/// * X-coordinate
+/// * Y-coordinate
+/// * Z-coordinate
+/// * Cell size (period)
+/// * Wall thickness
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct GradedTpms<T> {
+    pub surface: TpmsSurface,
+    pub base_period: T,
+    pub period_ratio: T,
+    pub linear: bool,
+}
+
+static GRADED_TPMS_INPUT_NAMES: [&str; 5] = ["X", "Y", "Z", "Period", "Thickness"];
+
+static GRADED_TPMS_PARAMETERS: &[Parameter; 4] = &[
+    Parameter {
+        name: "Surface",
+        data_type: DataType::Enum(&TpmsSurface::NAMES),
+    },
+    Parameter {
+        name: "Base Period",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Period Ratio",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Linearize",
+        data_type: DataType::Boolean,
+    },
+];
+
+impl<T: Float> GradedTpms<T> {
+    /// Create a new graded TPMS operation, with period levels that double in size.
+    /// # Arguments
+    ///
+    /// * `surface` - Type of periodic surface.
+    /// * `base_period` - Period which is reproduced exactly, and from which the other period levels are derived.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base period is not larger than zero.
+    pub fn new(surface: TpmsSurface, base_period: T) -> Self {
+        assert!(
+            base_period > T::zero(),
+            "Base period must be larger than zero."
+        );
+        Self {
+            surface,
+            base_period,
+            period_ratio: T::from(2.0).unwrap(),
+            linear: false,
+        }
+    }
+
+    /// Set the ratio between two consecutive period levels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ratio is not larger than one.
+    pub fn with_period_ratio(mut self, period_ratio: T) -> Self {
+        assert!(
+            period_ratio > T::one(),
+            "Period ratio must be larger than one."
+        );
+        self.period_ratio = period_ratio;
+        self
+    }
+
+    /// Linearize the values of the periodic surface.
+    pub fn with_linear(mut self, linear: bool) -> Self {
+        self.linear = linear;
+        self
+    }
+}
+
+impl<T: ModelFloat> GradedTpms<T> {
+    /// Approximate distance to the surface at a fixed period.
+    fn eval_level(&self, x: T, y: T, z: T, period: T) -> T {
+        period
+            * self
+                .surface
+                .eval_unit_cell(x / period, y / period, z / period, self.linear)
+    }
+}
+
+impl<T: ModelFloat> ImplicitOperation<T> for GradedTpms<T> {
+    fn eval(&self, inputs: &[T]) -> T {
+        let (x, y, z) = (inputs[0], inputs[1], inputs[2]);
+        let period = inputs[3].max(T::epsilon());
+        let half_thickness = inputs[4] / T::from(2.0).unwrap();
+
+        let level = (period / self.base_period).ln() / self.period_ratio.ln();
+        let lower_level = level.floor();
+        let factor = level - lower_level;
+
+        let lower_period = self.base_period * self.period_ratio.powf(lower_level);
+        let lower = self.eval_level(x, y, z, lower_period);
+        let distance = if factor > T::zero() {
+            let upper = self.eval_level(x, y, z, lower_period * self.period_ratio);
+            lower + factor * (upper - lower)
+        } else {
+            lower
+        };
+
+        distance.abs() - half_thickness
+    }
+
+    fn inputs(&self) -> &[&str] {
+        &GRADED_TPMS_INPUT_NAMES
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for GradedTpms<T> {
+    fn parameters(&self) -> &[Parameter] {
+        GRADED_TPMS_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if let Some(selection) =
+            Parameter::get_string_from_enum_param(parameter_name, &data, "Surface")
+        {
+            match TpmsSurface::from_name(&selection) {
+                Some(surface) => self.surface = surface,
+                None => error!("Unknown surface: {}", selection),
+            }
+        } else if parameter_name == "Base Period" {
+            let mut period = self.base_period;
+            Parameter::set_value_from_param(parameter_name, &data, "Base Period", &mut period);
+            if period > T::zero() {
+                self.base_period = period;
+            } else {
+                error!("Base period must be larger than zero.");
+            }
+        } else if parameter_name == "Period Ratio" {
+            let mut ratio = self.period_ratio;
+            Parameter::set_value_from_param(parameter_name, &data, "Period Ratio", &mut ratio);
+            if ratio > T::one() {
+                self.period_ratio = ratio;
+            } else {
+                error!("Period ratio must be larger than one.");
+            }
+        } else if !Parameter::set_bool_from_param(
+            parameter_name,
+            &data,
+            "Linearize",
+            &mut self.linear,
+        ) {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Surface" => Some(Data::EnumValue(self.surface.as_str().to_string())),
+            "Base Period" => Some(Data::Value(self.base_period)),
+            "Period Ratio" => Some(Data::Value(self.period_ratio)),
+            "Linearize" => Some(Data::Boolean(self.linear)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "GradedTpms"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::computation::{
+        functions::{Gyroid, XYZValue},
+        model::ImplicitModel,
+        operations::shape::Thickness,
+        traits::ImplicitFunction,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_constant_period_matches_regular_surface() {
+        let graded = GradedTpms::new(TpmsSurface::Gyroid, 10.0);
+        let thickness = Thickness::new(2.0);
+
+        for &period in &[10.0, 20.0, 40.0] {
+            let gyroid = Gyroid::with_equal_spacing(period / 2.0, false);
+            for i in 0..50 {
+                let (x, y, z) = (i as f64 * 1.3, i as f64 * -0.7, i as f64 * 0.4);
+                let expected = thickness.eval(&[gyroid.eval(x, y, z)]);
+                let actual = graded.eval(&[x, y, z, period, 2.0]);
+                assert!((expected - actual).abs() < 1E-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_graded_period_is_continuous() {
+        let graded = GradedTpms::new(TpmsSurface::SchwarzP, 5.0).with_period_ratio(3.0);
+
+        // Period grows from 5 to 60 along x, so the samples cross two levels.
+        let period = |x: f64| 5.0 + 0.55 * x;
+        let step = 0.01;
+        let mut previous = graded.eval(&[0.0, 1.0, 2.0, period(0.0), 1.0]);
+        for i in 1..10000 {
+            let x = i as f64 * step;
+            let value = graded.eval(&[x, 1.0, 2.0, period(x), 1.0]);
+            assert!((value - previous).abs() < 0.1, "Jump at x = {}", x);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn test_reject_invalid_periods() {
+        let mut graded = GradedTpms::new(TpmsSurface::Gyroid, 10.0);
+        graded.set_parameter("Base Period", Data::Value(0.0));
+        graded.set_parameter("Period Ratio", Data::Value(1.0));
+
+        assert_eq!(graded.base_period, 10.0);
+        assert_eq!(graded.period_ratio, 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Base period must be larger than zero.")]
+    fn test_new_rejects_zero_base_period() {
+        GradedTpms::new(TpmsSurface::Gyroid, 0.0);
+    }
+
+    #[test]
+    fn test_graded_tpms_in_model() {
+        let mut model = ImplicitModel::new();
+        model.add_function("X", XYZValue::x()).unwrap();
+        model.add_function("Y", XYZValue::y()).unwrap();
+        model.add_function("Z", XYZValue::z()).unwrap();
+        model.add_constant("Period", 12.0).unwrap();
+        model.add_constant("Thickness", 1.5).unwrap();
+        model
+            .add_operation(
+                "Lattice",
+                GradedTpms::new(TpmsSurface::Neovius, 12.0),
+                Some(&["X", "Y", "Z", "Period", "Thickness"]),
+            )
+            .unwrap();
+
+        let graded = GradedTpms::new(TpmsSurface::Neovius, 12.0);
+        let value = model.evaluate_at("Lattice", 3.0, 4.0, 5.0).unwrap();
+        assert!((value - graded.eval(&[3.0, 4.0, 5.0, 12.0, 1.5])).abs() < 1E-9);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::computation::model::{Data, Parameter};
+use crate::types::geometry::Vec3;
 use std::any::type_name;
 
 /// Trait to define a distance function in 3d space.
@@ -79,6 +80,42 @@ pub trait ImplicitOperation<T>: ImplicitComponent<T> {
     /// Communicates to the model the names of and number of inputs to this operation.
     fn inputs(&self) -> &[&str];
 }
+
+/// Trait to define a transformation of the domain in which an upstream part of an implicit model is evaluated.
+///
+/// A transform takes a single input. When the model is compiled, the entire subgraph feeding the input is evaluated at the transformed coordinate instead of the sample coordinate,
+/// which makes it possible to repeat, mirror or deform any combination of functions and operations at the cost of a single evaluation.
+/// A struct that implements this trait can be passed to the [`ImplicitModel`](crate::types::computation::model::ImplicitModel) via the [`add_transform`](crate::types::computation::model::ImplicitModel::add_transform) method.
+///
+/// # Example
+///
+/// Examples can be found in the [`computation::transforms`](crate::types::computation::transforms) module, for example a simple translation would look like this:
+///
+/// ```rust
+/// # use imlet::types::computation::traits::{ModelFloat, ImplicitTransform, ImplicitComponent};
+/// # use imlet::types::geometry::Vec3;
+/// # #[derive(Debug, Clone, Copy)]
+/// # pub struct Translate<T>{ offset: Vec3<T> };
+///
+/// // Default implementation of base trait.
+/// impl<T: ModelFloat> ImplicitComponent<T> for Translate<T>{};
+///
+/// impl<T: ModelFloat> ImplicitTransform<T> for Translate<T> {
+///     fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+///         Vec3::new(x - self.offset.x, y - self.offset.y, z - self.offset.z)
+///     }
+/// }
+/// ```
+pub trait ImplicitTransform<T>: ImplicitComponent<T> {
+    /// Map a coordinate in the global domain to the coordinate at which the input should be evaluated.
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate to transform.
+    /// * `y` - Y coordinate to transform.
+    /// * `z` - Z coordinate to transform.
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T>;
+}
+
 /// Trait for general functionality of an implicit component.
 ///
 /// The trait offers the ability to expose parameters, which can be manipulated at runtime.
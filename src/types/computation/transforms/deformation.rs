@@ -0,0 +1,692 @@
+use std::fmt::Debug;
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitTransform, ModelFloat},
+    },
+    geometry::Vec3,
+};
+
+static AXIAL_DEFORMATION_PARAMETERS: &[Parameter; 5] = &[
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Axis",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Amount",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Start",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "End",
+        data_type: DataType::Value,
+    },
+];
+
+static DIRECTIONAL_DEFORMATION_PARAMETERS: &[Parameter; 6] = &[
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Axis",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Direction",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Amount",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Start",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "End",
+        data_type: DataType::Value,
+    },
+];
+
+/// Helper method to assign a direction parameter, ensuring the stored direction is a unit vector.
+fn set_direction_from_param<T: Float>(
+    parameter_name: &str,
+    data: &Data<T>,
+    target_name: &str,
+    target: &mut Vec3<T>,
+) -> bool {
+    let mut direction = *target;
+    if !Parameter::set_vec3_from_param(parameter_name, data, target_name, &mut direction) {
+        return false;
+    }
+    if direction.magnitude() < T::epsilon() {
+        error!("{} can't be a zero length vector.", target_name);
+    } else {
+        *target = direction.normalize();
+    }
+    true
+}
+
+/// Helper method to normalize a direction passed to a constructor.
+///
+/// # Panics
+///
+/// Panics if the direction has zero length.
+fn unit_direction<T: Float>(direction: Vec3<T>, name: &str) -> Vec3<T> {
+    assert!(
+        direction.magnitude() >= T::epsilon(),
+        "{} can't be a zero length vector.",
+        name
+    );
+    direction.normalize()
+}
+
+/// Height along the deformation axis relative to `start`, clamped to the range in which the deformation is applied.
+#[inline(always)]
+fn height_in_range<T: Float>(height: T, start: T, end: T) -> T {
+    height.clamp(start.min(end), start.max(end)) - start
+}
+
+/// Component of a direction perpendicular to an axis. Falls back to an arbitrary perpendicular direction if the two are parallel.
+#[inline(always)]
+fn perpendicular<T: Float>(direction: Vec3<T>, axis: Vec3<T>) -> Vec3<T> {
+    let projected = direction - axis * axis.dot(&direction);
+    if projected.magnitude() < T::epsilon() {
+        axis.orthonormal_basis().0
+    } else {
+        projected.normalize()
+    }
+}
+
+/// Transform to twist the input around an axis.
+///
+/// The input is rotated around the axis by an angle proportional to the height above `start`.
+///
+/// The twist does not preserve distances. At a radial distance `r` from the axis the Lipschitz factor is `sqrt(1 + (amount * r)^2)`,
+/// so the result underestimates the gradient of a true distance field by up to this factor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct Twist<T> {
+    origin: Vec3<T>,
+    axis: Vec3<T>,
+    amount: T,
+    start: T,
+    end: T,
+}
+
+impl<T: Float> Twist<T> {
+    /// Create a new twist deformation.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - A point on the twist axis.
+    /// * `axis` - The direction of the twist axis.
+    /// * `amount` - The rotation in radians per unit length along the axis.
+    /// * `start` - Height along the axis from which the twist is applied.
+    /// * `end` - Height along the axis up to which the twist is applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis has zero length.
+    pub fn new(origin: Vec3<T>, axis: Vec3<T>, amount: T, start: T, end: T) -> Self {
+        Self {
+            origin,
+            axis: unit_direction(axis, "Axis"),
+            amount,
+            start,
+            end,
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitTransform<T> for Twist<T> {
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+        let local = Vec3::new(x, y, z) - self.origin;
+        let height = local.dot(&self.axis);
+        let radial = local - self.axis * height;
+
+        let angle = -self.amount * height_in_range(height, self.start, self.end);
+        let rotated = radial * angle.cos() + self.axis.cross(&radial) * angle.sin();
+
+        self.origin + self.axis * height + rotated
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Twist<T> {
+    fn parameters(&self) -> &[Parameter] {
+        AXIAL_DEFORMATION_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !(Parameter::set_vec3_from_param(parameter_name, &data, "Origin", &mut self.origin)
+            || set_direction_from_param(parameter_name, &data, "Axis", &mut self.axis)
+            || Parameter::set_value_from_param(parameter_name, &data, "Amount", &mut self.amount)
+            || Parameter::set_value_from_param(parameter_name, &data, "Start", &mut self.start)
+            || Parameter::set_value_from_param(parameter_name, &data, "End", &mut self.end))
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Amount" => Some(Data::Value(self.amount)),
+            "Start" => Some(Data::Value(self.start)),
+            "End" => Some(Data::Value(self.end)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Twist"
+    }
+}
+
+/// Transform to taper the input along an axis.
+///
+/// The cross section perpendicular to the axis is scaled by `1 + amount * (h - start)`, where `h` is the height along the axis.
+/// The scale is limited to a small positive value, to avoid inverting the geometry.
+///
+/// The taper does not preserve distances. With a smallest scale `s` and a radial distance `r` from the axis the Lipschitz factor is approximately `(1 + |amount| * r) / s`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct Taper<T> {
+    origin: Vec3<T>,
+    axis: Vec3<T>,
+    amount: T,
+    start: T,
+    end: T,
+}
+
+impl<T: Float> Taper<T> {
+    /// Create a new taper deformation.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - A point on the taper axis.
+    /// * `axis` - The direction of the taper axis.
+    /// * `amount` - The change in scale per unit length along the axis. Negative values will narrow the input.
+    /// * `start` - Height along the axis from which the taper is applied.
+    /// * `end` - Height along the axis up to which the taper is applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis has zero length.
+    pub fn new(origin: Vec3<T>, axis: Vec3<T>, amount: T, start: T, end: T) -> Self {
+        Self {
+            origin,
+            axis: unit_direction(axis, "Axis"),
+            amount,
+            start,
+            end,
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitTransform<T> for Taper<T> {
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+        let min_scale = T::from(1E-3).unwrap();
+        let local = Vec3::new(x, y, z) - self.origin;
+        let height = local.dot(&self.axis);
+        let radial = local - self.axis * height;
+
+        let scale =
+            (T::one() + self.amount * height_in_range(height, self.start, self.end)).max(min_scale);
+
+        self.origin + self.axis * height + radial * (T::one() / scale)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Taper<T> {
+    fn parameters(&self) -> &[Parameter] {
+        AXIAL_DEFORMATION_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !(Parameter::set_vec3_from_param(parameter_name, &data, "Origin", &mut self.origin)
+            || set_direction_from_param(parameter_name, &data, "Axis", &mut self.axis)
+            || Parameter::set_value_from_param(parameter_name, &data, "Amount", &mut self.amount)
+            || Parameter::set_value_from_param(parameter_name, &data, "Start", &mut self.start)
+            || Parameter::set_value_from_param(parameter_name, &data, "End", &mut self.end))
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Amount" => Some(Data::Value(self.amount)),
+            "Start" => Some(Data::Value(self.start)),
+            "End" => Some(Data::Value(self.end)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Taper"
+    }
+}
+
+/// Transform to bend the input along an axis.
+///
+/// The part of the input between `start` and `end` along the axis is bent into a circular arc with curvature `amount` (one over the bend radius),
+/// curving towards the bend direction. Beyond `end`, the input continues straight along the tangent of the arc.
+/// Points further from the arc centre than the bend radius on the opposite side of the bend can fold over, so the bend radius should be large compared to the input.
+///
+/// The bend does not preserve distances. At a distance `d` from the axis, in the bend direction, the Lipschitz factor is approximately `1 / (1 - |amount| * d)` on the inside of the bend.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct Bend<T> {
+    origin: Vec3<T>,
+    axis: Vec3<T>,
+    direction: Vec3<T>,
+    amount: T,
+    start: T,
+    end: T,
+}
+
+impl<T: Float> Bend<T> {
+    /// Create a new bend deformation.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - A point on the axis of the input which is bent.
+    /// * `axis` - The direction along the input which is bent.
+    /// * `direction` - The direction towards which the axis curves. Only the component perpendicular to the axis is used.
+    /// * `amount` - The curvature of the bend, which is one over the bend radius.
+    /// * `start` - Height along the axis from which the bend is applied.
+    /// * `end` - Height along the axis up to which the bend is applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis or the direction has zero length.
+    pub fn new(
+        origin: Vec3<T>,
+        axis: Vec3<T>,
+        direction: Vec3<T>,
+        amount: T,
+        start: T,
+        end: T,
+    ) -> Self {
+        Self {
+            origin,
+            axis: unit_direction(axis, "Axis"),
+            direction: unit_direction(direction, "Direction"),
+            amount,
+            start,
+            end,
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitTransform<T> for Bend<T> {
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+        let point = Vec3::new(x, y, z);
+        if self.amount.abs() < T::epsilon() {
+            return point;
+        }
+
+        let local = point - self.origin;
+        let (direction, curvature) = if self.amount > T::zero() {
+            (perpendicular(self.direction, self.axis), self.amount)
+        } else {
+            (
+                perpendicular(self.direction, self.axis) * -T::one(),
+                -self.amount,
+            )
+        };
+
+        let radius = T::one() / curvature;
+        let a = local.dot(&direction);
+        let h = local.dot(&self.axis);
+        let remainder = local - direction * a - self.axis * h;
+
+        // Vector from the centre of the bend, which lies at the start height, in the plane spanned by the direction and axis.
+        let da = a - radius;
+        let dh = h - self.start;
+        let angle = dh.atan2(-da);
+        let length = self.end - self.start;
+        let clamped = angle.clamp(
+            curvature * length.min(T::zero()),
+            curvature * length.max(T::zero()),
+        );
+
+        let (sin, cos) = clamped.sin_cos();
+        let unbent_a = radius + da * cos - dh * sin;
+        let unbent_h = self.start + radius * clamped + da * sin + dh * cos;
+
+        self.origin + direction * unbent_a + self.axis * unbent_h + remainder
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Bend<T> {
+    fn parameters(&self) -> &[Parameter] {
+        DIRECTIONAL_DEFORMATION_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !(Parameter::set_vec3_from_param(parameter_name, &data, "Origin", &mut self.origin)
+            || set_direction_from_param(parameter_name, &data, "Axis", &mut self.axis)
+            || set_direction_from_param(parameter_name, &data, "Direction", &mut self.direction)
+            || Parameter::set_value_from_param(parameter_name, &data, "Amount", &mut self.amount)
+            || Parameter::set_value_from_param(parameter_name, &data, "Start", &mut self.start)
+            || Parameter::set_value_from_param(parameter_name, &data, "End", &mut self.end))
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Direction" => Some(Data::Vec3(self.direction)),
+            "Amount" => Some(Data::Value(self.amount)),
+            "Start" => Some(Data::Value(self.start)),
+            "End" => Some(Data::Value(self.end)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Bend"
+    }
+}
+
+/// Transform to shear the input along a direction.
+///
+/// The input is shifted in the shear direction by `amount` times the height above `start`.
+///
+/// The shear does not preserve distances. The Lipschitz factor is `(|amount| + sqrt(amount^2 + 4)) / 2`, which is approximately `1 + |amount| / 2` for small amounts.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct Shear<T> {
+    origin: Vec3<T>,
+    axis: Vec3<T>,
+    direction: Vec3<T>,
+    amount: T,
+    start: T,
+    end: T,
+}
+
+impl<T: Float> Shear<T> {
+    /// Create a new shear deformation.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - A point on the axis along which the height is measured.
+    /// * `axis` - The direction along which the height is measured.
+    /// * `direction` - The direction of the shear. Only the component perpendicular to the axis is used.
+    /// * `amount` - The shift in the shear direction per unit length along the axis.
+    /// * `start` - Height along the axis from which the shear is applied.
+    /// * `end` - Height along the axis up to which the shear is applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis or the direction has zero length.
+    pub fn new(
+        origin: Vec3<T>,
+        axis: Vec3<T>,
+        direction: Vec3<T>,
+        amount: T,
+        start: T,
+        end: T,
+    ) -> Self {
+        Self {
+            origin,
+            axis: unit_direction(axis, "Axis"),
+            direction: unit_direction(direction, "Direction"),
+            amount,
+            start,
+            end,
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitTransform<T> for Shear<T> {
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+        let point = Vec3::new(x, y, z);
+        let height = self.axis.dot(&(point - self.origin));
+        let shift = self.amount * height_in_range(height, self.start, self.end);
+
+        point - perpendicular(self.direction, self.axis) * shift
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Shear<T> {
+    fn parameters(&self) -> &[Parameter] {
+        DIRECTIONAL_DEFORMATION_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        if !(Parameter::set_vec3_from_param(parameter_name, &data, "Origin", &mut self.origin)
+            || set_direction_from_param(parameter_name, &data, "Axis", &mut self.axis)
+            || set_direction_from_param(parameter_name, &data, "Direction", &mut self.direction)
+            || Parameter::set_value_from_param(parameter_name, &data, "Amount", &mut self.amount)
+            || Parameter::set_value_from_param(parameter_name, &data, "Start", &mut self.start)
+            || Parameter::set_value_from_param(parameter_name, &data, "End", &mut self.end))
+        {
+            error!("Unknown parameter name: {}", parameter_name);
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Direction" => Some(Data::Vec3(self.direction)),
+            "Amount" => Some(Data::Value(self.amount)),
+            "Start" => Some(Data::Value(self.start)),
+            "End" => Some(Data::Value(self.end)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Shear"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn test_twist_quarter_turn() {
+        let twist = Twist::new(Vec3::origin(), Vec3::z_axis(), 0.5 * PI, 0.0, 10.0);
+
+        // At height 1 a point on the y-axis maps back to the x-axis.
+        let p = twist.transform(0.0, 1.0, 1.0);
+        assert!((p.x - 1.0).abs() < 0.001);
+        assert!(p.y.abs() < 0.001);
+        assert!((p.z - 1.0).abs() < 0.001);
+
+        // Below the range the input is not rotated.
+        let p = twist.transform(0.0, 1.0, -1.0);
+        assert!(p.x.abs() < 0.001);
+        assert!((p.y - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_taper_scales_cross_section() {
+        let taper = Taper::new(Vec3::origin(), Vec3::z_axis(), 1.0, 0.0, 10.0);
+
+        let p = taper.transform(2.0, 0.0, 1.0);
+        assert!((p.x - 1.0).abs() < 0.001);
+        assert!((p.z - 1.0).abs() < 0.001);
+
+        let p = taper.transform(2.0, 0.0, -1.0);
+        assert!((p.x - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_bend_maps_arc_to_axis() {
+        let radius = 10.0;
+        let bend = Bend::new(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            Vec3::x_axis(),
+            1.0 / radius,
+            0.0,
+            100.0,
+        );
+
+        // A point on the quarter arc maps back to the straight axis at the arc length.
+        let p = bend.transform(radius, 0.0, radius);
+        assert!(p.x.abs() < 0.001, "Expected x = 0, but was {}", p.x);
+        assert!((p.z - 0.5 * PI * radius).abs() < 0.001);
+
+        // Points below the range are not affected.
+        let p = bend.transform(1.0, 2.0, -3.0);
+        assert!((p.x - 1.0).abs() < 0.001);
+        assert!((p.y - 2.0).abs() < 0.001);
+        assert!((p.z + 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_bend_continues_along_tangent() {
+        let radius = 10.0;
+        let bend = Bend::new(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            Vec3::x_axis(),
+            1.0 / radius,
+            0.0,
+            0.5 * PI * radius,
+        );
+
+        // After a quarter turn the tangent points in the x-direction.
+        let p = bend.transform(radius + 2.0, 0.0, radius);
+        assert!(p.x.abs() < 0.001);
+        assert!((p.z - (0.5 * PI * radius + 2.0)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_bend_with_offset_start() {
+        let radius = 10.0;
+        let start = 5.0;
+        let bend = Bend::new(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            Vec3::x_axis(),
+            1.0 / radius,
+            start,
+            100.0,
+        );
+
+        // Points below the start of the bend are not affected.
+        for z in [-3.0, 0.0, 4.9] {
+            let p = bend.transform(1.0, 2.0, z);
+            assert!((p.x - 1.0).abs() < 0.001, "Expected x = 1, but was {}", p.x);
+            assert!((p.y - 2.0).abs() < 0.001);
+            assert!(
+                (p.z - z).abs() < 0.001,
+                "Expected z = {}, but was {}",
+                z,
+                p.z
+            );
+        }
+
+        // The quarter arc starts at the start height.
+        let p = bend.transform(radius, 0.0, start + radius);
+        assert!(p.x.abs() < 0.001, "Expected x = 0, but was {}", p.x);
+        assert!((p.z - (start + 0.5 * PI * radius)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_deformations_are_zero_at_start() {
+        let start = 2.0;
+        let end = 6.0;
+        let twist = Twist::new(Vec3::origin(), Vec3::z_axis(), 0.5 * PI, start, end);
+        let taper = Taper::new(Vec3::origin(), Vec3::z_axis(), 1.0, start, end);
+        let shear = Shear::new(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            Vec3::x_axis(),
+            0.5,
+            start,
+            end,
+        );
+
+        for z in [0.0, start] {
+            for p in [
+                twist.transform(1.0, 0.0, z),
+                taper.transform(1.0, 0.0, z),
+                shear.transform(1.0, 0.0, z),
+            ] {
+                assert!((p.x - 1.0).abs() < 0.001, "Expected x = 1, but was {}", p.x);
+                assert!(p.y.abs() < 0.001, "Expected y = 0, but was {}", p.y);
+            }
+        }
+
+        // One unit above the start a point on the y-axis maps back to the x-axis.
+        let p = twist.transform(0.0, 1.0, start + 1.0);
+        assert!((p.x - 1.0).abs() < 0.001);
+        assert!(p.y.abs() < 0.001);
+
+        let p = shear.transform(0.0, 0.0, start + 2.0);
+        assert!((p.x + 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "Axis can't be a zero length vector.")]
+    fn test_twist_rejects_zero_axis() {
+        Twist::new(Vec3::origin(), Vec3::origin(), 1.0, 0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Direction can't be a zero length vector.")]
+    fn test_bend_rejects_zero_direction() {
+        Bend::new(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            Vec3::origin(),
+            1.0,
+            0.0,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn test_shear() {
+        let shear = Shear::new(
+            Vec3::origin(),
+            Vec3::z_axis(),
+            Vec3::x_axis(),
+            0.5,
+            0.0,
+            5.0,
+        );
+
+        let p = shear.transform(1.0, 0.0, 2.0);
+        assert!(p.x.abs() < 0.001);
+        assert!((p.z - 2.0).abs() < 0.001);
+
+        let p = shear.transform(0.0, 0.0, 10.0);
+        assert!((p.x + 2.5).abs() < 0.001);
+
+        let p = shear.transform(0.0, 0.0, -10.0);
+        assert!(p.x.abs() < 0.001);
+    }
+}
@@ -0,0 +1,11 @@
+/// Space deformations such as twisting, bending, tapering and shearing.
+///
+/// Each deformation acts along an axis through an origin, and is applied between the heights `start` and `end` along that axis.
+/// The deformation is zero at `start`, so the input is unchanged on the side of `start` facing away from `end`.
+/// Beyond `end` the deformation reached at `end` is kept constant.
+pub mod deformation;
+/// Domain repetition such as linear arrays, polar arrays and mirroring.
+pub mod repetition;
+
+mod transform_components;
+pub use transform_components::*;
@@ -0,0 +1,476 @@
+use std::fmt::Debug;
+
+use log::error;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::{Data, DataType, Parameter},
+        traits::{ImplicitComponent, ImplicitTransform, ModelFloat},
+    },
+    geometry::Vec3,
+};
+
+/// Transform to repeat the input in a linear array along a direction.
+///
+/// The domain is folded so that each sample point is mapped back to the closest copy, which means the cost of evaluation is independent of the number of copies.
+/// The first copy is located at the original position of the input, and the following copies are placed at multiples of the spacing along the direction.
+///
+/// For the result to be a correct distance field, the input geometry should fit within one spacing along the direction.
+///
+/// Multiple arrays can be chained to create grids.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct LinearArray<T> {
+    direction: Vec3<T>,
+    spacing: T,
+    count: usize,
+}
+
+impl<T: Float> LinearArray<T> {
+    /// Create a new finite linear array.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The direction in which the input is repeated.
+    /// * `spacing` - The distance between each copy.
+    /// * `count` - The total number of copies, including the original. A count of 0 will create an infinite array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the direction has zero length or the spacing is not larger than zero.
+    pub fn new(direction: Vec3<T>, spacing: T, count: usize) -> Self {
+        assert!(
+            direction.magnitude() >= T::epsilon(),
+            "Direction can't be a zero length vector."
+        );
+        assert!(spacing > T::zero(), "Spacing must be larger than zero.");
+        Self {
+            direction: direction.normalize(),
+            spacing,
+            count,
+        }
+    }
+
+    /// Create a new infinite linear array, repeating the input in both the positive and negative direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The direction in which the input is repeated.
+    /// * `spacing` - The distance between each copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the direction has zero length or the spacing is not larger than zero.
+    pub fn infinite(direction: Vec3<T>, spacing: T) -> Self {
+        Self::new(direction, spacing, 0)
+    }
+}
+
+static LINEAR_ARRAY_PARAMETERS: &[Parameter; 3] = &[
+    Parameter {
+        name: "Direction",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Spacing",
+        data_type: DataType::Value,
+    },
+    Parameter {
+        name: "Count",
+        data_type: DataType::Value,
+    },
+];
+
+impl<T: ModelFloat> ImplicitTransform<T> for LinearArray<T> {
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+        let position = self.direction.dot_coord(x, y, z) / self.spacing;
+        let index = if self.count > 0 {
+            let last = T::from(self.count - 1).unwrap();
+            position.round().clamp(T::zero(), last)
+        } else {
+            position.round()
+        };
+        Vec3::new(x, y, z) - self.direction * (self.spacing * index)
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for LinearArray<T> {
+    fn parameters(&self) -> &[Parameter] {
+        LINEAR_ARRAY_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        match parameter_name {
+            "Direction" => {
+                let mut direction = self.direction;
+                Parameter::set_vec3_from_param(parameter_name, &data, "Direction", &mut direction);
+                if direction.magnitude() < T::epsilon() {
+                    error!("Direction can't be a zero length vector.");
+                } else {
+                    self.direction = direction.normalize();
+                }
+            }
+            "Spacing" => {
+                let mut spacing = self.spacing;
+                Parameter::set_value_from_param(parameter_name, &data, "Spacing", &mut spacing);
+                if spacing <= T::zero() {
+                    error!("Spacing must be larger than zero.");
+                } else {
+                    self.spacing = spacing;
+                }
+            }
+            "Count" => {
+                let mut count = T::from(self.count).unwrap();
+                Parameter::set_value_from_param(parameter_name, &data, "Count", &mut count);
+                self.count = count
+                    .max(T::zero())
+                    .round()
+                    .to_usize()
+                    .unwrap_or(self.count);
+            }
+            _ => error!("Unknown parameter name: {}", parameter_name),
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Direction" => Some(Data::Vec3(self.direction)),
+            "Spacing" => Some(Data::Value(self.spacing)),
+            "Count" => Some(Data::Value(T::from(self.count).unwrap())),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "LinearArray"
+    }
+}
+
+/// Transform to repeat the input in a polar array around an axis.
+///
+/// The domain is folded into a single angular sector, so the cost of evaluation is independent of the number of copies.
+/// The sectors are centered around the reference direction of the axis, which is the projection of the global x-axis onto the plane perpendicular to the axis
+/// (or the y-axis if the axis is parallel to x), see [`Vec3::orthonormal_basis`]. The input geometry should be placed within the sector around this direction.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct PolarArray<T> {
+    origin: Vec3<T>,
+    axis: Vec3<T>,
+    u_axis: Vec3<T>,
+    v_axis: Vec3<T>,
+    count: usize,
+}
+
+impl<T: Float> PolarArray<T> {
+    /// Create a new polar array.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - A point on the axis of rotation.
+    /// * `axis` - The direction of the axis of rotation.
+    /// * `count` - The total number of copies around the axis, including the original.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the axis has zero length.
+    pub fn new(origin: Vec3<T>, axis: Vec3<T>, count: usize) -> Self {
+        assert!(
+            axis.magnitude() >= T::epsilon(),
+            "Axis can't be a zero length vector."
+        );
+        let mut array = Self {
+            origin,
+            axis: Vec3::origin(),
+            u_axis: Vec3::origin(),
+            v_axis: Vec3::origin(),
+            count: count.max(1),
+        };
+        array.set_axis(axis);
+        array
+    }
+
+    /// Set the normalized axis together with the reference directions in the plane perpendicular to it.
+    fn set_axis(&mut self, axis: Vec3<T>) {
+        self.axis = axis.normalize();
+        (self.u_axis, self.v_axis) = self.axis.orthonormal_basis();
+    }
+}
+
+static POLAR_ARRAY_PARAMETERS: &[Parameter; 3] = &[
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Axis",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Count",
+        data_type: DataType::Value,
+    },
+];
+
+impl<T: ModelFloat> ImplicitTransform<T> for PolarArray<T> {
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+        let two = T::from(2.0).unwrap();
+        let (u, v) = (self.u_axis, self.v_axis);
+        let local = Vec3::new(x, y, z) - self.origin;
+
+        let height = local.dot(&self.axis);
+        let a = local.dot(&u);
+        let b = local.dot(&v);
+        let radius = a.hypot(b);
+
+        let sector = two * T::pi() / T::from(self.count).unwrap();
+        let angle = b.atan2(a);
+        let folded = angle - sector * (angle / sector).round();
+
+        self.origin + self.axis * height + u * (radius * folded.cos()) + v * (radius * folded.sin())
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for PolarArray<T> {
+    fn parameters(&self) -> &[Parameter] {
+        POLAR_ARRAY_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        match parameter_name {
+            "Origin" => {
+                Parameter::set_vec3_from_param(parameter_name, &data, "Origin", &mut self.origin);
+            }
+            "Axis" => {
+                let mut axis = self.axis;
+                Parameter::set_vec3_from_param(parameter_name, &data, "Axis", &mut axis);
+                if axis.magnitude() < T::epsilon() {
+                    error!("Axis can't be a zero length vector.");
+                } else {
+                    self.set_axis(axis);
+                }
+            }
+            "Count" => {
+                let mut count = T::from(self.count).unwrap();
+                Parameter::set_value_from_param(parameter_name, &data, "Count", &mut count);
+                self.count = count.max(T::one()).round().to_usize().unwrap_or(self.count);
+            }
+            _ => error!("Unknown parameter name: {}", parameter_name),
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Axis" => Some(Data::Vec3(self.axis)),
+            "Count" => Some(Data::Value(T::from(self.count).unwrap())),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "PolarArray"
+    }
+}
+
+/// Transform to mirror the input across a plane.
+///
+/// Points behind the plane are reflected to the front side, so that the part of the input in front of the plane, in the direction of the normal, is mirrored.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct Mirror<T> {
+    origin: Vec3<T>,
+    normal: Vec3<T>,
+}
+
+impl<T: Float> Mirror<T> {
+    /// Create a new mirror transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - A point on the mirror plane.
+    /// * `normal` - The normal of the mirror plane, pointing towards the side which is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the normal has zero length.
+    pub fn new(origin: Vec3<T>, normal: Vec3<T>) -> Self {
+        assert!(
+            normal.magnitude() >= T::epsilon(),
+            "Normal can't be a zero length vector."
+        );
+        Self {
+            origin,
+            normal: normal.normalize(),
+        }
+    }
+}
+
+static MIRROR_PARAMETERS: &[Parameter; 2] = &[
+    Parameter {
+        name: "Origin",
+        data_type: DataType::Vec3,
+    },
+    Parameter {
+        name: "Normal",
+        data_type: DataType::Vec3,
+    },
+];
+
+impl<T: ModelFloat> ImplicitTransform<T> for Mirror<T> {
+    fn transform(&self, x: T, y: T, z: T) -> Vec3<T> {
+        let point = Vec3::new(x, y, z);
+        let distance = self.normal.dot(&(point - self.origin));
+        if distance < T::zero() {
+            point - self.normal * (distance + distance)
+        } else {
+            point
+        }
+    }
+}
+
+impl<T: ModelFloat> ImplicitComponent<T> for Mirror<T> {
+    fn parameters(&self) -> &[Parameter] {
+        MIRROR_PARAMETERS
+    }
+
+    fn set_parameter(&mut self, parameter_name: &str, data: Data<T>) {
+        match parameter_name {
+            "Origin" => {
+                Parameter::set_vec3_from_param(parameter_name, &data, "Origin", &mut self.origin);
+            }
+            "Normal" => {
+                let mut normal = self.normal;
+                Parameter::set_vec3_from_param(parameter_name, &data, "Normal", &mut normal);
+                if normal.magnitude() < T::epsilon() {
+                    error!("Normal can't be a zero length vector.");
+                } else {
+                    self.normal = normal.normalize();
+                }
+            }
+            _ => error!("Unknown parameter name: {}", parameter_name),
+        }
+    }
+
+    fn read_parameter(&self, parameter_name: &str) -> Option<Data<T>> {
+        match parameter_name {
+            "Origin" => Some(Data::Vec3(self.origin)),
+            "Normal" => Some(Data::Vec3(self.normal)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Mirror"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{computation::model::ImplicitModel, geometry::Sphere};
+
+    use super::*;
+
+    #[test]
+    fn test_finite_linear_array() {
+        let array = LinearArray::new(Vec3::x_axis(), 10.0, 3);
+
+        let p = array.transform(21.0, 1.0, 0.0);
+        assert!((p.x - 1.0).abs() < 0.001);
+        assert!((p.y - 1.0).abs() < 0.001);
+
+        // Beyond the last copy the domain is not folded further.
+        let p = array.transform(42.0, 0.0, 0.0);
+        assert!((p.x - 22.0).abs() < 0.001);
+
+        let p = array.transform(-5.0, 0.0, 0.0);
+        assert!((p.x + 5.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_infinite_linear_array() {
+        let array = LinearArray::infinite(Vec3::y_axis(), 2.0);
+
+        let p = array.transform(0.0, 100.5, 0.0);
+        assert!((p.y - 0.5).abs() < 0.001);
+
+        let p = array.transform(0.0, -99.5, 0.0);
+        assert!((p.y - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "Spacing must be larger than zero.")]
+    fn test_linear_array_rejects_zero_spacing() {
+        LinearArray::new(Vec3::x_axis(), 0.0, 3);
+    }
+
+    #[test]
+    fn test_polar_array() {
+        let array = PolarArray::new(Vec3::origin(), Vec3::z_axis(), 4);
+
+        let p = array.transform(0.0, 5.0, 2.0);
+        assert!((p.x - 5.0).abs() < 0.001);
+        assert!(p.y.abs() < 0.001);
+        assert!((p.z - 2.0).abs() < 0.001);
+
+        let p = array.transform(-5.0, 0.0, 0.0);
+        assert!((p.x - 5.0).abs() < 0.001);
+        assert!(p.y.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_polar_array_set_axis() {
+        let mut array = PolarArray::<f64>::new(Vec3::origin(), Vec3::z_axis(), 4);
+        array.set_parameter("Axis", Data::Vec3(Vec3::origin()));
+        assert!((array.axis.z - 1.0).abs() < 0.001);
+
+        array.set_parameter("Axis", Data::Vec3(Vec3::new(2.0, 0.0, 0.0)));
+        let p = array.transform(1.0, 0.0, -5.0);
+        assert!((p.x - 1.0).abs() < 0.001);
+        assert!((p.y - 5.0).abs() < 0.001);
+        assert!(p.z.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mirror = Mirror::new(Vec3::new(1.0, 0.0, 0.0), Vec3::x_axis());
+
+        let p = mirror.transform(-1.0, 2.0, 3.0);
+        assert!((p.x - 3.0).abs() < 0.001);
+        assert!((p.y - 2.0).abs() < 0.001);
+        assert!((p.z - 3.0).abs() < 0.001);
+
+        let p = mirror.transform(2.0, 0.0, 0.0);
+        assert!((p.x - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_evaluate_linear_array_in_model() {
+        let mut model = ImplicitModel::new();
+        let sphere = model
+            .add_function("Sphere", Sphere::new(Vec3::origin(), 1.0))
+            .unwrap();
+        let array = model
+            .add_transform(
+                "Array",
+                LinearArray::new(Vec3::x_axis(), 5.0, 10),
+                Some(&sphere),
+            )
+            .unwrap();
+
+        let value = model.evaluate_at(&array, 45.0, 0.0, 0.0).unwrap();
+        assert!((value + 1.0).abs() < 0.001);
+
+        let value = model.evaluate_at(&array, 47.0, 0.0, 0.0).unwrap();
+        assert!((value - 1.0).abs() < 0.001);
+
+        let value = model.evaluate_at(&array, 50.0, 0.0, 0.0).unwrap();
+        assert!((value - 4.0).abs() < 0.001);
+
+        // The untransformed sphere is still available in the global domain.
+        let value = model.evaluate_at(&sphere, 45.0, 0.0, 0.0).unwrap();
+        assert!((value - 44.0).abs() < 0.001);
+    }
+}
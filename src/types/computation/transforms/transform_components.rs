@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    computation::{
+        model::ModelComponent,
+        traits::{ImplicitTransform, ModelFloat},
+        transforms::{
+            deformation::{Bend, Shear, Taper, Twist},
+            repetition::{LinearArray, Mirror, PolarArray},
+        },
+    },
+    geometry::Vec3,
+};
+
+/// Enum listing valid transform components.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone)]
+pub enum TransformComponent {
+    /// Repeat the input along a direction.
+    LinearArray,
+    /// Repeat the input around an axis.
+    PolarArray,
+    /// Mirror the input across a plane.
+    Mirror,
+    /// Twist the input around an axis.
+    Twist,
+    /// Bend the input along an axis.
+    Bend,
+    /// Taper the input along an axis.
+    Taper,
+    /// Shear the input along a direction.
+    Shear,
+}
+
+impl TransformComponent {
+    /// Create an instance of the component with default values.
+    ///
+    /// Used when creating components from a UI or other interface.
+    pub fn create_default<T: ModelFloat + 'static>(&self) -> ModelComponent<T> {
+        let default_value = T::from(45.).unwrap();
+        let transform: Box<dyn ImplicitTransform<T>> = match self {
+            // Repetition
+            TransformComponent::LinearArray => {
+                Box::new(LinearArray::new(Vec3::x_axis(), default_value, 3))
+            }
+            TransformComponent::PolarArray => {
+                Box::new(PolarArray::new(Vec3::origin(), Vec3::z_axis(), 6))
+            }
+            TransformComponent::Mirror => Box::new(Mirror::new(Vec3::origin(), Vec3::x_axis())),
+            // Deformation
+            TransformComponent::Twist => Box::new(Twist::new(
+                Vec3::origin(),
+                Vec3::z_axis(),
+                T::from(0.02).unwrap(),
+                T::zero(),
+                default_value,
+            )),
+            TransformComponent::Bend => Box::new(Bend::new(
+                Vec3::origin(),
+                Vec3::z_axis(),
+                Vec3::x_axis(),
+                T::from(0.01).unwrap(),
+                T::zero(),
+                default_value,
+            )),
+            TransformComponent::Taper => Box::new(Taper::new(
+                Vec3::origin(),
+                Vec3::z_axis(),
+                T::from(-0.01).unwrap(),
+                T::zero(),
+                default_value,
+            )),
+            TransformComponent::Shear => Box::new(Shear::new(
+                Vec3::origin(),
+                Vec3::z_axis(),
+                Vec3::x_axis(),
+                T::from(0.25).unwrap(),
+                T::zero(),
+                default_value,
+            )),
+        };
+
+        ModelComponent::Transform(transform)
+    }
+}
+
+impl FromStr for TransformComponent {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<TransformComponent, Self::Err> {
+        match input {
+            "LinearArray" => Ok(TransformComponent::LinearArray),
+            "PolarArray" => Ok(TransformComponent::PolarArray),
+            "Mirror" => Ok(TransformComponent::Mirror),
+            "Twist" => Ok(TransformComponent::Twist),
+            "Bend" => Ok(TransformComponent::Bend),
+            "Taper" => Ok(TransformComponent::Taper),
+            "Shear" => Ok(TransformComponent::Shear),
+            _ => Err(()),
+        }
+    }
+}
+
+/// List of available transforms
+pub const TRANSFORM_COMPONENTS: &[TransformComponent] = &[
+    // Repetition
+    TransformComponent::LinearArray,
+    TransformComponent::PolarArray,
+    TransformComponent::Mirror,
+    // Deformation
+    TransformComponent::Twist,
+    TransformComponent::Bend,
+    TransformComponent::Taper,
+    TransformComponent::Shear,
+];
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_all_transform_components_params() {
+        let all_transforms = TRANSFORM_COMPONENTS;
+
+        for &transform in all_transforms {
+            let mut component = transform.create_default::<f32>();
+            let params = component.read_parameters();
+
+            for (param, data) in params {
+                component.set_parameter(param.name, data);
+            }
+        }
+    }
+}
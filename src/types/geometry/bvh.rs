@@ -0,0 +1,339 @@
+use num_traits::Float;
+
+use super::{traits::Bounded, BoundingBox, Triangle, Vec3};
+
+const MAX_LEAF_TRIANGLE_COUNT: usize = 8;
+
+/// Nodes further away than this factor times their radius use the far field approximation of the winding number.
+const WINDING_NUMBER_ACCURACY: f64 = 2.0;
+
+/// Ray directions used for the parity test. They avoid the axes and diagonals, which are likely to hit edges of regular meshes.
+const RAY_DIRECTIONS: [[f64; 3]; 3] = [
+    [0.5773, 0.6172, 0.5345],
+    [-0.7035, 0.3015, -0.6436],
+    [0.2357, -0.8729, -0.4272],
+];
+
+/// Node of the hierarchy, with the data needed for the far field winding number.
+#[derive(Debug, Clone)]
+struct BvhNode<T> {
+    bounds: BoundingBox<T>,
+    /// Area weighted centroid of the triangles.
+    center: Vec3<T>,
+    /// Largest distance from the center to a vertex.
+    radius: T,
+    /// Sum of the triangle normals scaled by their areas.
+    area_normal: Vec3<T>,
+    content: BvhContent,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BvhContent {
+    Leaf { start: usize, end: usize },
+    Internal { left: usize, right: usize },
+}
+
+/// Bounding volume hierarchy of triangles, used to classify points as inside or outside of a mesh without relying on normals.
+///
+/// Unlike the [`Octree`](super::Octree), each triangle is stored exactly once, so sums over all triangles can be computed hierarchically.
+/// This allows for two inside tests which are robust to holes and flipped faces:
+/// - The generalized winding number, computed with a far field approximation for distant nodes.
+/// - The parity of ray crossings.
+#[derive(Debug, Clone)]
+pub struct TriangleBvh<T> {
+    triangles: Vec<Triangle<T>>,
+    nodes: Vec<BvhNode<T>>,
+}
+
+impl<T: Float> TriangleBvh<T> {
+    /// Build a hierarchy from a list of triangles.
+    /// # Arguments
+    ///
+    /// * `triangles` - Triangles of the mesh, oriented with outward normals for the winding number.
+    pub fn new(triangles: &[Triangle<T>]) -> Self {
+        let mut bvh = Self {
+            triangles: triangles.to_vec(),
+            nodes: Vec::new(),
+        };
+        if !bvh.triangles.is_empty() {
+            bvh.build_node(0, bvh.triangles.len());
+        }
+        bvh
+    }
+
+    fn build_node(&mut self, start: usize, end: usize) -> usize {
+        let half = T::from(0.5).unwrap();
+        let triangles = &mut self.triangles[start..end];
+
+        let mut bounds = triangles[0].bounds();
+        let mut area_normal = Vec3::origin();
+        let mut weighted_center = Vec3::origin();
+        let mut area_sum = T::zero();
+        for triangle in triangles.iter() {
+            bounds = bounds.union(&triangle.bounds());
+            let normal =
+                (triangle.p2() - triangle.p1()).cross(&(triangle.p3() - triangle.p1())) * half;
+            let area = normal.magnitude();
+            area_normal = area_normal + normal;
+            weighted_center = weighted_center + centroid(triangle) * area;
+            area_sum = area_sum + area;
+        }
+        let center = if area_sum > T::zero() {
+            weighted_center * (T::one() / area_sum)
+        } else {
+            bounds.centroid()
+        };
+        let radius = triangles
+            .iter()
+            .flat_map(|triangle| triangle.p.iter())
+            .map(|p| p.distance_to_vec3(&center))
+            .fold(T::zero(), T::max);
+
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounds,
+            center,
+            radius,
+            area_normal,
+            content: BvhContent::Leaf { start, end },
+        });
+
+        if end - start > MAX_LEAF_TRIANGLE_COUNT {
+            // Split at the median centroid along the longest axis.
+            let (dx, dy, dz) = bounds.dimensions();
+            let axis = if dx >= dy && dx >= dz {
+                0
+            } else if dy >= dz {
+                1
+            } else {
+                2
+            };
+            let key = |triangle: &Triangle<T>| {
+                let c = centroid(triangle);
+                [c.x, c.y, c.z][axis]
+            };
+            let middle = (end - start) / 2;
+            triangles.select_nth_unstable_by(middle, |a, b| {
+                key(a)
+                    .partial_cmp(&key(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            let left = self.build_node(start, start + middle);
+            let right = self.build_node(start + middle, end);
+            self.nodes[index].content = BvhContent::Internal { left, right };
+        }
+
+        index
+    }
+
+    /// Returns the number of triangles in the hierarchy.
+    pub fn num_triangles(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Compute the generalized winding number of the triangles around a point.
+    ///
+    /// The winding number is 1 inside and 0 outside of a closed mesh with outward normals. For open or imperfect meshes,
+    /// it varies smoothly in between, so values above 0.5 can be considered inside.
+    /// # Arguments
+    ///
+    /// * `point` - Point to compute the winding number for.
+    pub fn winding_number(&self, point: &Vec3<T>) -> T {
+        let accuracy = T::from(WINDING_NUMBER_ACCURACY).unwrap();
+        let mut solid_angle = T::zero();
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let offset = node.center - *point;
+            let distance = offset.magnitude();
+            if distance > accuracy * node.radius {
+                // Far field dipole approximation of the solid angle.
+                solid_angle = solid_angle + offset.dot(&node.area_normal) / distance.powi(3);
+                continue;
+            }
+            match node.content {
+                BvhContent::Leaf { start, end } => {
+                    for triangle in &self.triangles[start..end] {
+                        solid_angle = solid_angle + triangle_solid_angle(triangle, point);
+                    }
+                }
+                BvhContent::Internal { left, right } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+
+        solid_angle / T::from(4.0 * std::f64::consts::PI).unwrap()
+    }
+
+    /// Count the number of triangles crossed by a ray.
+    /// # Arguments
+    ///
+    /// * `origin` - Start point of the ray.
+    /// * `direction` - Direction of the ray.
+    pub fn ray_crossings(&self, origin: &Vec3<T>, direction: &Vec3<T>) -> usize {
+        let inverse = Vec3::new(
+            T::one() / direction.x,
+            T::one() / direction.y,
+            T::one() / direction.z,
+        );
+        let mut crossings = 0;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !ray_hits_box(&node.bounds, origin, &inverse) {
+                continue;
+            }
+            match node.content {
+                BvhContent::Leaf { start, end } => {
+                    crossings += self.triangles[start..end]
+                        .iter()
+                        .filter(|triangle| ray_hits_triangle(triangle, origin, direction))
+                        .count();
+                }
+                BvhContent::Internal { left, right } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+
+        crossings
+    }
+
+    /// Check if a point is inside using the parity of ray crossings.
+    ///
+    /// Rays are cast in three directions, and the point is inside if at least two of them cross the mesh an odd number of times.
+    /// This ignores the orientation of the triangles.
+    /// # Arguments
+    ///
+    /// * `point` - Point to classify.
+    pub fn is_inside_by_parity(&self, point: &Vec3<T>) -> bool {
+        let odd_count = RAY_DIRECTIONS
+            .iter()
+            .filter(|d| {
+                let direction = Vec3::new(
+                    T::from(d[0]).unwrap(),
+                    T::from(d[1]).unwrap(),
+                    T::from(d[2]).unwrap(),
+                );
+                self.ray_crossings(point, &direction) % 2 == 1
+            })
+            .count();
+        odd_count >= 2
+    }
+}
+
+fn centroid<T: Float>(triangle: &Triangle<T>) -> Vec3<T> {
+    (triangle.p1() + triangle.p2() + triangle.p3()) * T::from(1.0 / 3.0).unwrap()
+}
+
+/// Signed solid angle of a triangle seen from a point, using the formula by Van Oosterom and Strackee.
+fn triangle_solid_angle<T: Float>(triangle: &Triangle<T>, point: &Vec3<T>) -> T {
+    let a = triangle.p1() - *point;
+    let b = triangle.p2() - *point;
+    let c = triangle.p3() - *point;
+    let (la, lb, lc) = (a.magnitude(), b.magnitude(), c.magnitude());
+
+    let numerator = a.dot(&b.cross(&c));
+    let denominator = la * lb * lc + a.dot(&b) * lc + b.dot(&c) * la + c.dot(&a) * lb;
+    T::from(2.0).unwrap() * numerator.atan2(denominator)
+}
+
+/// Slab test between a ray and a box, given the inverse of the ray direction.
+fn ray_hits_box<T: Float>(bounds: &BoundingBox<T>, origin: &Vec3<T>, inverse: &Vec3<T>) -> bool {
+    let mut t_min = T::zero();
+    let mut t_max = T::infinity();
+    for (min, max, o, inv) in [
+        (bounds.min.x, bounds.max.x, origin.x, inverse.x),
+        (bounds.min.y, bounds.max.y, origin.y, inverse.y),
+        (bounds.min.z, bounds.max.z, origin.z, inverse.z),
+    ] {
+        let t1 = (min - o) * inv;
+        let t2 = (max - o) * inv;
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+    }
+    t_min <= t_max
+}
+
+/// Möller-Trumbore intersection between a ray and a triangle, counting only hits in front of the origin.
+fn ray_hits_triangle<T: Float>(
+    triangle: &Triangle<T>,
+    origin: &Vec3<T>,
+    direction: &Vec3<T>,
+) -> bool {
+    let edge1 = triangle.p2() - triangle.p1();
+    let edge2 = triangle.p3() - triangle.p1();
+    let p = direction.cross(&edge2);
+    let determinant = edge1.dot(&p);
+    if determinant.abs() <= T::epsilon() {
+        return false;
+    }
+
+    let inverse = T::one() / determinant;
+    let s = *origin - triangle.p1();
+    let u = s.dot(&p) * inverse;
+    if u < T::zero() || u > T::one() {
+        return false;
+    }
+    let q = s.cross(&edge1);
+    let v = direction.dot(&q) * inverse;
+    if v < T::zero() || u + v > T::one() {
+        return false;
+    }
+    edge2.dot(&q) * inverse > T::zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::io::parse_obj_file;
+
+    use super::*;
+
+    #[test]
+    fn test_winding_number_of_closed_mesh() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/sphere.obj", false, false).unwrap();
+        let bvh = TriangleBvh::new(&mesh.as_triangles());
+        let center = mesh.bounds().centroid();
+
+        assert_eq!(bvh.num_triangles(), mesh.num_faces());
+        assert!((bvh.winding_number(&center) - 1.0).abs() < 1E-2);
+        let (dx, _, _) = mesh.bounds().dimensions();
+        let outside = center + Vec3::new(dx, 0.3 * dx, 0.0);
+        assert!(bvh.winding_number(&outside).abs() < 1E-2);
+        assert!(bvh.is_inside_by_parity(&center));
+        assert!(!bvh.is_inside_by_parity(&outside));
+    }
+
+    #[test]
+    fn test_winding_number_matches_exact_sum() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/bunny.obj", false, false).unwrap();
+        let triangles = mesh.as_triangles();
+        let bvh = TriangleBvh::new(&triangles);
+        let bounds = mesh.bounds();
+
+        for i in 0..20 {
+            let t = i as f64 / 20.0;
+            let point = Vec3::interpolate(&bounds.min, &bounds.max, t)
+                + Vec3::new(0.0, (t * 7.3).sin(), (t * 3.1).cos());
+            let exact: f64 = triangles
+                .iter()
+                .map(|triangle| triangle_solid_angle(triangle, &point))
+                .sum::<f64>()
+                / (4.0 * std::f64::consts::PI);
+            // The far field approximation is accurate enough to classify points with a threshold of 0.5.
+            assert!((bvh.winding_number(&point) - exact).abs() < 5E-2);
+        }
+    }
+}
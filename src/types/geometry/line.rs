@@ -10,7 +10,10 @@ use crate::types::computation::{
     traits::{ImplicitComponent, ImplicitFunction, ModelFloat},
 };
 
-use super::{traits::SignedDistance, Vec3};
+use super::{
+    traits::{Bounded, SignedDistance, SpatialQuery},
+    BoundingBox, Vec3,
+};
 
 static LINE_PARAMS: &[Parameter; 2] = &[
     Parameter {
@@ -109,6 +112,22 @@ impl<T: ModelFloat> ImplicitComponent<T> for Line<T> {
     }
 }
 
+impl<T: Float> Bounded<T> for Line<T> {
+    fn bounds(&self) -> BoundingBox<T> {
+        BoundingBox::new(self.start.min(&self.end), self.start.max(&self.end))
+    }
+}
+
+impl<T: Float> SpatialQuery<T> for Line<T> {
+    fn default() -> Self {
+        Line::new(Vec3::origin(), Vec3::origin())
+    }
+
+    fn closest_point(&self, query_point: &Vec3<T>) -> Vec3<T> {
+        self.closest_pt(*query_point)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -101,20 +101,27 @@ impl<T: Float> Mesh<T> {
         }
     }
 
-    /// Returns the unique edges of the mesh.
-    pub fn edges(&self) -> Vec<Line<T>> {
-        let mut edges_i = HashSet::with_capacity(self.num_faces());
+    /// Returns the vertex indices of the unique edges of the mesh, with the lowest index first.
+    ///
+    /// Edges shared by two faces are only returned once. The edges are sorted by their indices.
+    pub fn edge_indices(&self) -> Vec<[usize; 2]> {
+        let mut edges_i = HashSet::with_capacity(self.num_faces() * 2);
         for f in self.faces.iter() {
-            let mut sorted = *f;
-            sorted.sort();
-            edges_i.insert((f[0], f[1]));
-            edges_i.insert((f[1], f[2]));
-            edges_i.insert((f[2], f[0]));
+            for (i, j) in [(f[0], f[1]), (f[1], f[2]), (f[2], f[0])] {
+                edges_i.insert([i.min(j), i.max(j)]);
+            }
         }
 
-        edges_i
+        let mut edges: Vec<[usize; 2]> = edges_i.into_iter().collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Returns the unique edges of the mesh.
+    pub fn edges(&self) -> Vec<Line<T>> {
+        self.edge_indices()
             .iter()
-            .map(|(i, j)| Line::new(self.vertices[*i], self.vertices[*j]))
+            .map(|&[i, j]| Line::new(self.vertices[i], self.vertices[j]))
             .collect()
     }
 
@@ -202,12 +209,12 @@ impl<T: Float> Mesh<T> {
             let v1 = self.vertices[face[0]] - vertex;
             let v2 = self.vertices[face[2]] - vertex;
 
-            return v1.angle(&v2).unwrap_or(T::zero());
+            v1.angle(&v2).unwrap_or(T::zero())
         } else if face[2] == vertex_index {
             let v1 = self.vertices[face[0]] - vertex;
             let v2 = self.vertices[face[1]] - vertex;
 
-            return v1.angle(&v2).unwrap_or(T::zero());
+            v1.angle(&v2).unwrap_or(T::zero())
         } else {
             panic!("Vertex not found in adjacent face. Mesh topology must be corrupt.")
         }
@@ -1,14 +1,17 @@
 mod bounding_box;
+mod bvh;
 mod capsule;
 mod hash_grid;
 mod line;
 mod mesh;
 mod octree;
 mod plane;
+mod profile;
 mod sphere;
 mod torus;
 mod transform;
 mod triangle;
+mod vec2f;
 mod vec3f;
 mod vec3i;
 
@@ -16,15 +19,18 @@ mod vec3i;
 pub mod traits;
 
 pub use bounding_box::*;
+pub use bvh::*;
 pub use capsule::*;
 use hash_grid::*;
 pub use line::*;
 pub use mesh::*;
 pub use octree::*;
 pub use plane::*;
+pub use profile::*;
 pub use sphere::*;
 pub use torus::*;
 pub use transform::*;
 pub use triangle::*;
+pub use vec2f::*;
 pub use vec3f::*;
 pub use vec3i::*;
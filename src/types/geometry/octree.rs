@@ -35,6 +35,11 @@ impl<Q, T: Float> Octree<Q, T> {
         }
     }
 
+    /// Returns the objects stored in the tree.
+    pub fn objects(&self) -> &[Q] {
+        &self.objects
+    }
+
     /// Collect all the nested bounding boxes in the tree.
     ///
     /// # Returns
@@ -230,26 +235,25 @@ impl<T: Float> OctreeNode<T> {
         ];
 
         for (i, child) in children.iter_mut().enumerate() {
-            let offset = Vec3::<T>::new(
-                if i & 1 == 0 {
-                    T::zero()
-                } else {
-                    center.x - bounds.min.x
-                },
-                if i & 2 == 0 {
-                    T::zero()
-                } else {
-                    center.y - bounds.min.y
-                },
-                if i & 4 == 0 {
-                    T::zero()
-                } else {
-                    center.z - bounds.min.z
-                },
-            );
+            // Split exactly at the center, so adjacent children share the same boundary values.
+            let (min_x, max_x) = if i & 1 == 0 {
+                (bounds.min.x, center.x)
+            } else {
+                (center.x, bounds.max.x)
+            };
+            let (min_y, max_y) = if i & 2 == 0 {
+                (bounds.min.y, center.y)
+            } else {
+                (center.y, bounds.max.y)
+            };
+            let (min_z, max_z) = if i & 4 == 0 {
+                (bounds.min.z, center.z)
+            } else {
+                (center.z, bounds.max.z)
+            };
 
-            let child_min = bounds.min + offset;
-            let child_max = center + offset;
+            let child_min = Vec3::new(min_x, min_y, min_z);
+            let child_max = Vec3::new(max_x, max_y, max_z);
             let child_bounds = BoundingBox::new(child_min, child_max);
 
             let child_indices = object_indices
@@ -0,0 +1,494 @@
+use std::fmt::Debug;
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Vec2;
+
+/// Planar shape which can be evaluated as a 2D signed distance function.
+///
+/// Profiles are defined in the local coordinates of a plane and are lifted into 3D by the
+/// [`Extrusion`](crate::types::computation::functions::Extrusion) and
+/// [`Revolution`](crate::types::computation::functions::Revolution) functions.
+/// Distances are negative inside the profile and positive outside.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub enum Profile<T> {
+    /// Disc with a center and radius.
+    Circle { center: Vec2<T>, radius: T },
+    /// Closed polygon, where every corner is rounded with a fillet of the corner radius.
+    ///
+    /// The fillet radius is reduced at corners where the adjacent edges are too short to fit it.
+    Polygon {
+        points: Vec<Vec2<T>>,
+        corner_radius: T,
+    },
+    /// Open polyline with a stroke radius, giving rounded joints and end caps.
+    Polyline { points: Vec<Vec2<T>>, radius: T },
+    /// Circular arc with a stroke thickness, starting at an angle and sweeping counter-clockwise for positive sweep angles.
+    Arc {
+        center: Vec2<T>,
+        radius: T,
+        start_angle: T,
+        sweep_angle: T,
+        thickness: T,
+    },
+    /// Union of two profiles.
+    Union(Box<Profile<T>>, Box<Profile<T>>),
+    /// Intersection of two profiles.
+    Intersection(Box<Profile<T>>, Box<Profile<T>>),
+    /// Difference of two profiles, removing the second profile from the first.
+    Difference(Box<Profile<T>>, Box<Profile<T>>),
+}
+
+/// Fillet at a polygon corner, between the tangent points `start` and `end`.
+struct Corner<T> {
+    start: Vec2<T>,
+    end: Vec2<T>,
+    center: Vec2<T>,
+    radius: T,
+    convex: bool,
+}
+
+impl<T: Float> Profile<T> {
+    /// Create a circular profile.
+    /// # Arguments
+    ///
+    /// * `center` - Center of the circle.
+    /// * `radius` - Radius of the circle.
+    pub fn circle(center: Vec2<T>, radius: T) -> Self {
+        Profile::Circle { center, radius }
+    }
+
+    /// Create a closed polygon profile with sharp corners. The points can be ordered clockwise or counter-clockwise.
+    /// # Arguments
+    ///
+    /// * `points` - Corner points of the polygon, without repeating the first point.
+    pub fn polygon(points: Vec<Vec2<T>>) -> Self {
+        Self::rounded_polygon(points, T::zero())
+    }
+
+    /// Create a closed polygon profile with rounded corners. The points can be ordered clockwise or counter-clockwise.
+    /// # Arguments
+    ///
+    /// * `points` - Corner points of the polygon, without repeating the first point.
+    /// * `corner_radius` - Fillet radius applied to all corners.
+    pub fn rounded_polygon(points: Vec<Vec2<T>>, corner_radius: T) -> Self {
+        Profile::Polygon {
+            points,
+            corner_radius: corner_radius.max(T::zero()),
+        }
+    }
+
+    /// Create a rectangular profile centered at a point, with rounded corners.
+    /// # Arguments
+    ///
+    /// * `center` - Center of the rectangle.
+    /// * `width` - Size in the x-direction.
+    /// * `height` - Size in the y-direction.
+    /// * `corner_radius` - Fillet radius applied to all corners.
+    pub fn rectangle(center: Vec2<T>, width: T, height: T, corner_radius: T) -> Self {
+        let two = T::from(2.0).unwrap();
+        let (hw, hh) = (width / two, height / two);
+        Self::rounded_polygon(
+            vec![
+                Vec2::new(center.x - hw, center.y - hh),
+                Vec2::new(center.x + hw, center.y - hh),
+                Vec2::new(center.x + hw, center.y + hh),
+                Vec2::new(center.x - hw, center.y + hh),
+            ],
+            corner_radius,
+        )
+    }
+
+    /// Create an open polyline profile with a stroke radius.
+    /// # Arguments
+    ///
+    /// * `points` - Points along the polyline.
+    /// * `radius` - Half of the stroke width.
+    pub fn polyline(points: Vec<Vec2<T>>, radius: T) -> Self {
+        Profile::Polyline { points, radius }
+    }
+
+    /// Create a circular arc profile with a stroke thickness.
+    /// # Arguments
+    ///
+    /// * `center` - Center of the arc.
+    /// * `radius` - Radius of the arc centerline.
+    /// * `start_angle` - Angle of the start point in radians, measured from the x-axis.
+    /// * `sweep_angle` - Angle spanned by the arc in radians. Negative values sweep clockwise.
+    /// * `thickness` - Width of the stroke.
+    pub fn arc(center: Vec2<T>, radius: T, start_angle: T, sweep_angle: T, thickness: T) -> Self {
+        Profile::Arc {
+            center,
+            radius,
+            start_angle,
+            sweep_angle,
+            thickness,
+        }
+    }
+
+    /// Combine this profile with another, keeping the area covered by either.
+    pub fn union(self, other: Profile<T>) -> Self {
+        Profile::Union(Box::new(self), Box::new(other))
+    }
+
+    /// Combine this profile with another, keeping the area covered by both.
+    pub fn intersection(self, other: Profile<T>) -> Self {
+        Profile::Intersection(Box::new(self), Box::new(other))
+    }
+
+    /// Remove the area of another profile from this profile.
+    pub fn difference(self, other: Profile<T>) -> Self {
+        Profile::Difference(Box::new(self), Box::new(other))
+    }
+
+    /// Compute the signed distance from a point in the profile plane.
+    ///
+    /// The distance is exact for the primitives, and a bound of the distance for booleans.
+    /// # Arguments
+    ///
+    /// * `point` - Point to compute the distance from.
+    pub fn signed_distance(&self, point: Vec2<T>) -> T {
+        match self {
+            Profile::Circle { center, radius } => point.distance_to(center) - *radius,
+            Profile::Polygon {
+                points,
+                corner_radius,
+            } => {
+                if *corner_radius > T::zero() {
+                    rounded_polygon_distance(points, *corner_radius, point)
+                } else {
+                    polygon_distance(points, point)
+                }
+            }
+            Profile::Polyline { points, radius } => polyline_distance(points, point) - *radius,
+            Profile::Arc {
+                center,
+                radius,
+                start_angle,
+                sweep_angle,
+                thickness,
+            } => {
+                arc_distance(*center, *radius, *start_angle, *sweep_angle, point)
+                    - *thickness / T::from(2.0).unwrap()
+            }
+            Profile::Union(a, b) => a.signed_distance(point).min(b.signed_distance(point)),
+            Profile::Intersection(a, b) => a.signed_distance(point).max(b.signed_distance(point)),
+            Profile::Difference(a, b) => a.signed_distance(point).max(-b.signed_distance(point)),
+        }
+    }
+}
+
+/// Distance from a point to a line segment.
+fn segment_distance<T: Float>(start: Vec2<T>, end: Vec2<T>, point: Vec2<T>) -> T {
+    let edge = end - start;
+    let w = point - start;
+    let length_squared = edge.dot(&edge);
+    if length_squared <= T::zero() {
+        return w.magnitude();
+    }
+    let t = (w.dot(&edge) / length_squared).max(T::zero()).min(T::one());
+    (w - edge * t).magnitude()
+}
+
+/// Signed distance to a polygon with sharp corners, with the sign from the crossing number.
+fn polygon_distance<T: Float>(points: &[Vec2<T>], point: Vec2<T>) -> T {
+    if points.len() < 3 {
+        return T::max_value();
+    }
+
+    let mut distance = T::max_value();
+    let mut inside = false;
+    let mut previous = points[points.len() - 1];
+    for &current in points {
+        distance = distance.min(segment_distance(previous, current, point));
+        if (current.y > point.y) != (previous.y > point.y) {
+            let x = current.x
+                + (point.y - current.y) * (previous.x - current.x) / (previous.y - current.y);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+        previous = current;
+    }
+
+    if inside {
+        -distance
+    } else {
+        distance
+    }
+}
+
+/// Twice the signed area of a polygon, positive for counter-clockwise polygons.
+fn polygon_area<T: Float>(points: &[Vec2<T>]) -> T {
+    let mut area = T::zero();
+    let mut previous = points[points.len() - 1];
+    for &current in points {
+        area = area + previous.cross(&current);
+        previous = current;
+    }
+    area
+}
+
+/// Compute the fillet at each corner of a polygon, reducing the radius where the adjacent edges are too short.
+fn polygon_corners<T: Float>(points: &[Vec2<T>], radius: T, ccw: bool) -> Vec<Corner<T>> {
+    let n = points.len();
+    let two = T::from(2.0).unwrap();
+    let tolerance = T::from(1E-9).unwrap();
+
+    (0..n)
+        .map(|i| {
+            let previous = points[(i + n - 1) % n];
+            let current = points[i];
+            let next = points[(i + 1) % n];
+            let sharp = Corner {
+                start: current,
+                end: current,
+                center: current,
+                radius: T::zero(),
+                convex: true,
+            };
+
+            let (to_previous, to_next) = (previous - current, next - current);
+            let (length_previous, length_next) = (to_previous.magnitude(), to_next.magnitude());
+            if length_previous <= T::zero() || length_next <= T::zero() {
+                return sharp;
+            }
+            let (u_previous, u_next) = (
+                to_previous * (T::one() / length_previous),
+                to_next * (T::one() / length_next),
+            );
+            let cosine = u_previous.dot(&u_next).max(-T::one()).min(T::one());
+            // Straight corners don't need a fillet.
+            if cosine < tolerance - T::one() {
+                return sharp;
+            }
+
+            let half_angle = cosine.acos() / two;
+            let max_tangent = length_previous.min(length_next) / two;
+            let mut tangent = radius / half_angle.tan();
+            let mut fillet_radius = radius;
+            if tangent > max_tangent {
+                tangent = max_tangent;
+                fillet_radius = tangent * half_angle.tan();
+            }
+
+            let bisector = (u_previous + u_next).normalize();
+            Corner {
+                start: current + u_previous * tangent,
+                end: current + u_next * tangent,
+                center: current + bisector * (fillet_radius / half_angle.sin()),
+                radius: fillet_radius,
+                convex: ((current - previous).cross(&(next - current)) > T::zero()) == ccw,
+            }
+        })
+        .collect()
+}
+
+/// Signed distance to a polygon with filleted corners.
+///
+/// The boundary is tangent continuous, so the sign can be taken from the side of the closest segment or fillet.
+fn rounded_polygon_distance<T: Float>(points: &[Vec2<T>], radius: T, point: Vec2<T>) -> T {
+    if points.len() < 3 {
+        return T::max_value();
+    }
+    let ccw = polygon_area(points) > T::zero();
+    let corners = polygon_corners(points, radius, ccw);
+
+    let mut distance = T::max_value();
+    let mut inside = false;
+    for (i, corner) in corners.iter().enumerate() {
+        // Straight edge from the end of this fillet to the start of the next.
+        let start = corner.end;
+        let end = corners[(i + 1) % corners.len()].start;
+        let edge = end - start;
+        if edge.dot(&edge) > T::zero() {
+            let edge_distance = segment_distance(start, end, point);
+            if edge_distance < distance {
+                distance = edge_distance;
+                let left = edge.cross(&(point - start)) > T::zero();
+                inside = left == ccw;
+            }
+        }
+
+        if corner.radius > T::zero() {
+            let to_start = corner.start - corner.center;
+            let to_end = corner.end - corner.center;
+            let to_point = point - corner.center;
+            let turn = to_start.cross(&to_end);
+            let within = to_start.cross(&to_point) * turn >= T::zero()
+                && to_point.cross(&to_end) * turn >= T::zero();
+            if within {
+                let from_center = to_point.magnitude();
+                let arc_distance = (from_center - corner.radius).abs();
+                if arc_distance < distance {
+                    distance = arc_distance;
+                    inside = (from_center < corner.radius) == corner.convex;
+                }
+            }
+        }
+    }
+
+    if inside {
+        -distance
+    } else {
+        distance
+    }
+}
+
+/// Unsigned distance to an open polyline.
+fn polyline_distance<T: Float>(points: &[Vec2<T>], point: Vec2<T>) -> T {
+    match points.len() {
+        0 => T::max_value(),
+        1 => point.distance_to(&points[0]),
+        _ => points
+            .windows(2)
+            .map(|segment| segment_distance(segment[0], segment[1], point))
+            .fold(T::max_value(), T::min),
+    }
+}
+
+/// Unsigned distance to the centerline of a circular arc.
+fn arc_distance<T: Float>(
+    center: Vec2<T>,
+    radius: T,
+    start_angle: T,
+    sweep_angle: T,
+    point: Vec2<T>,
+) -> T {
+    let two_pi = T::from(std::f64::consts::TAU).unwrap();
+    let (start_angle, sweep_angle) = if sweep_angle < T::zero() {
+        (start_angle + sweep_angle, -sweep_angle)
+    } else {
+        (start_angle, sweep_angle)
+    };
+
+    let to_point = point - center;
+    let relative = to_point.angle() - start_angle;
+    let relative = relative - (relative / two_pi).floor() * two_pi;
+    if sweep_angle >= two_pi || relative <= sweep_angle {
+        return (to_point.magnitude() - radius).abs();
+    }
+
+    let end_angle = start_angle + sweep_angle;
+    let start = center + Vec2::new(start_angle.cos(), start_angle.sin()) * radius;
+    let end = center + Vec2::new(end_angle.cos(), end_angle.sin()) * radius;
+    point.distance_to(&start).min(point.distance_to(&end))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+
+    #[test]
+    fn test_circle_distance() {
+        let circle = Profile::circle(Vec2::new(1.0, 1.0), 2.0);
+
+        assert!((circle.signed_distance(Vec2::new(1.0, 1.0)) + 2.0).abs() < 1E-10);
+        assert!((circle.signed_distance(Vec2::new(4.0, 5.0)) - 3.0).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_polygon_distance_independent_of_orientation() {
+        let ccw = Profile::rectangle(Vec2::origin(), 4.0, 2.0, 0.0);
+        let cw = Profile::polygon(vec![
+            Vec2::new(-2.0, -1.0),
+            Vec2::new(-2.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(2.0, -1.0),
+        ]);
+
+        for (point, expected) in [
+            (Vec2::new(0.0, 0.0), -1.0),
+            (Vec2::new(1.5, 0.0), -0.5),
+            (Vec2::new(5.0, 0.0), 3.0),
+            (Vec2::new(5.0, 5.0), 5.0),
+        ] {
+            assert!((ccw.signed_distance(point) - expected).abs() < 1E-10);
+            assert!((cw.signed_distance(point) - expected).abs() < 1E-10);
+        }
+    }
+
+    #[test]
+    fn test_rounded_polygon_distance() {
+        let rounded = Profile::rectangle(Vec2::origin(), 4.0, 4.0, 1.0);
+
+        // Straight edges are unchanged by the fillets.
+        assert!((rounded.signed_distance(Vec2::new(3.0, 0.0)) - 1.0).abs() < 1E-10);
+        assert!((rounded.signed_distance(Vec2::new(0.0, 0.0)) + 2.0).abs() < 1E-10);
+
+        // Corners are rounded around the fillet center at {1, 1}.
+        let outside = Vec2::new(3.0, 3.0);
+        let expected = outside.distance_to(&Vec2::new(1.0, 1.0)) - 1.0;
+        assert!((rounded.signed_distance(outside) - expected).abs() < 1E-10);
+        let inside = Vec2::new(1.5, 1.5);
+        let expected = inside.distance_to(&Vec2::new(1.0, 1.0)) - 1.0;
+        assert!((rounded.signed_distance(inside) - expected).abs() < 1E-10);
+    }
+
+    #[test]
+    fn test_rounded_polygon_concave_corner() {
+        // L-shape with a concave corner at {1, 1}.
+        let shape = Profile::rounded_polygon(
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(3.0, 0.0),
+                Vec2::new(3.0, 1.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(1.0, 3.0),
+                Vec2::new(0.0, 3.0),
+            ],
+            0.25,
+        );
+
+        // The concave fillet is centered at {1.25, 1.25} and adds material to the corner.
+        let point = Vec2::new(1.05, 1.05);
+        let expected = 0.25 - point.distance_to(&Vec2::new(1.25, 1.25));
+        assert!((shape.signed_distance(point) - expected).abs() < 1E-10);
+        assert!(shape.signed_distance(point) < 0.0);
+        assert!(shape.signed_distance(Vec2::new(1.1, 1.1)) > 0.0);
+        assert!(shape.signed_distance(Vec2::new(2.0, 2.0)) > 0.0);
+        assert!(shape.signed_distance(Vec2::new(0.5, 2.0)) < 0.0);
+    }
+
+    #[test]
+    fn test_polyline_and_arc_distance() {
+        let polyline = Profile::polyline(
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(2.0, 2.0),
+            ],
+            0.5,
+        );
+        assert!((polyline.signed_distance(Vec2::new(1.0, 1.0)) - 0.5).abs() < 1E-10);
+        assert!((polyline.signed_distance(Vec2::new(-1.0, 0.0)) - 0.5).abs() < 1E-10);
+
+        let arc = Profile::arc(Vec2::origin(), 2.0, 0.0, FRAC_PI_2, 0.5);
+        assert!((arc.signed_distance(Vec2::new(0.0, 3.0)) - 0.75).abs() < 1E-10);
+        assert!((arc.signed_distance(Vec2::new(2.0, -1.0)) - 0.75).abs() < 1E-10);
+
+        let clockwise = Profile::arc(Vec2::origin(), 2.0, PI, -FRAC_PI_2, 0.5);
+        assert!((clockwise.signed_distance(Vec2::new(0.0, 3.0)) - 0.75).abs() < 1E-10);
+        assert!(clockwise.signed_distance(Vec2::new(0.0, -2.0)) > 1.0);
+    }
+
+    #[test]
+    fn test_profile_booleans() {
+        let a = Profile::circle(Vec2::new(-1.0, 0.0), 2.0);
+        let b = Profile::circle(Vec2::new(1.0, 0.0), 2.0);
+
+        let union = a.clone().union(b.clone());
+        let intersection = a.clone().intersection(b.clone());
+        let difference = a.difference(b);
+
+        assert!((union.signed_distance(Vec2::new(3.0, 0.0)) + 0.0).abs() < 1E-10);
+        assert!((intersection.signed_distance(Vec2::new(0.0, 0.0)) + 1.0).abs() < 1E-10);
+        assert!(difference.signed_distance(Vec2::new(0.0, 0.0)) > 0.0);
+        assert!(difference.signed_distance(Vec2::new(-2.0, 0.0)) < 0.0);
+    }
+}
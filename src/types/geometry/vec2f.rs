@@ -0,0 +1,120 @@
+use std::{
+    fmt::{self, Debug, Display},
+    ops,
+};
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Vector or Point with 2 coordinates, used for planar profiles.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    /// Create a new Vec2 from coordinates.
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate.
+    /// * `y` - Y coordinate.
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Float> Vec2<T> {
+    /// Construct a new point at {0,0}
+    pub fn origin() -> Vec2<T> {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+        }
+    }
+
+    /// Computes the distance between two points.
+    /// # Arguments
+    ///
+    /// * `pt` - Point to compute distance to.
+    #[inline(always)]
+    pub fn distance_to(&self, pt: &Vec2<T>) -> T {
+        (*self - *pt).magnitude()
+    }
+
+    /// Computes the dot product between two Vec2 values.
+    /// # Arguments
+    ///
+    /// * `rhs` - Vec to compute dot product with.
+    #[inline(always)]
+    pub fn dot(&self, rhs: &Vec2<T>) -> T {
+        (self.x * rhs.x) + (self.y * rhs.y)
+    }
+
+    /// Computes the z-component of the cross product between two Vec2 values.
+    ///
+    /// The result is positive if `rhs` is counter-clockwise from this vector.
+    /// # Arguments
+    ///
+    /// * `rhs` - Vec to compute cross product with.
+    #[inline(always)]
+    pub fn cross(&self, rhs: &Vec2<T>) -> T {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    /// Compute the total length of a vector (distance to origin).
+    #[inline(always)]
+    pub fn magnitude(&self) -> T {
+        self.dot(self).sqrt()
+    }
+
+    /// Normalize the vector, giving it a unit length.
+    #[inline(always)]
+    pub fn normalize(&self) -> Vec2<T> {
+        *self * (T::one() / self.magnitude())
+    }
+
+    /// Angle of the vector from the positive x-axis, in the range [-pi, pi].
+    #[inline(always)]
+    pub fn angle(&self) -> T {
+        self.y.atan2(self.x)
+    }
+}
+
+impl<T: Float> ops::Add<Vec2<T>> for Vec2<T> {
+    type Output = Vec2<T>;
+    fn add(self, rhs: Vec2<T>) -> Vec2<T> {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Float> ops::Sub<Vec2<T>> for Vec2<T> {
+    type Output = Vec2<T>;
+    fn sub(self, rhs: Vec2<T>) -> Vec2<T> {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Float> ops::Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Display> fmt::Display for Vec2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{}, {}}}", self.x, self.y)
+    }
+}
@@ -7,7 +7,10 @@ use num_traits::Float;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use super::Transform;
+use super::{
+    traits::{Bounded, SpatialQuery},
+    BoundingBox, Transform,
+};
 
 /// Vector or Point with 3 coordinates.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -226,6 +229,22 @@ impl<T: Float> Vec3<T> {
         *self * (T::one() / self.magnitude())
     }
 
+    /// Compute two unit vectors which together with this vector form a right-handed orthonormal basis.
+    ///
+    /// The first vector is the projection of the global x-axis onto the plane perpendicular to this vector,
+    /// or the projection of the global y-axis if this vector is parallel to the x-axis.
+    pub fn orthonormal_basis(&self) -> (Vec3<T>, Vec3<T>) {
+        let w = self.normalize();
+        let reference = if w.x.abs() > T::from(0.9).unwrap() {
+            Vec3::y_axis()
+        } else {
+            Vec3::x_axis()
+        };
+        let u = (reference - w * w.dot(&reference)).normalize();
+        let v = w.cross(&u);
+        (u, v)
+    }
+
     /// Compute the [Spherical Linear Interpolation](https://en.wikipedia.org/wiki/Slerp) of two vectors.
     ///
     /// *This performs a constant-speed motion along a unit-radius great circle arc, given the ends and an interpolation parameter between 0 and 1*
@@ -350,6 +369,22 @@ impl<T: Display> fmt::Display for Vec3<T> {
     }
 }
 
+impl<T: Float> Bounded<T> for Vec3<T> {
+    fn bounds(&self) -> BoundingBox<T> {
+        BoundingBox::new(*self, *self)
+    }
+}
+
+impl<T: Float> SpatialQuery<T> for Vec3<T> {
+    fn default() -> Self {
+        Vec3::origin()
+    }
+
+    fn closest_point(&self, _query_point: &Vec3<T>) -> Vec3<T> {
+        *self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::f64::consts::PI;
@@ -0,0 +1,238 @@
+use std::io;
+
+use num_traits::Float;
+
+use crate::types::geometry::{BoundingBox, Vec3};
+
+/// Magic bytes at the start of a binary dense field.
+pub(crate) const DENSE_FIELD_MAGIC: &[u8; 8] = b"IMLETDFD";
+/// Magic bytes at the start of a binary sparse field.
+pub(crate) const SPARSE_FIELD_MAGIC: &[u8; 8] = b"IMLETSFD";
+/// Version of the binary field layout.
+pub(crate) const FIELD_FORMAT_VERSION: u32 = 1;
+
+/// Little endian writer for binary fields.
+///
+/// Values are written with the precision of `T`, as 32 or 64 bit floats.
+pub(crate) struct FieldWriter {
+    data: Vec<u8>,
+    double_precision: bool,
+}
+
+impl FieldWriter {
+    /// Start a field with the magic bytes, the format version and the value precision.
+    pub fn new<T: Float>(magic: &[u8; 8]) -> Self {
+        let double_precision = std::mem::size_of::<T>() > 4;
+        let mut writer = Self {
+            data: Vec::new(),
+            double_precision,
+        };
+        writer.data.extend_from_slice(magic);
+        writer.write_u32(FIELD_FORMAT_VERSION);
+        writer.write_u8(if double_precision { 8 } else { 4 });
+        writer
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_value<T: Float>(&mut self, value: T) {
+        if self.double_precision {
+            let value = value.to_f64().unwrap_or(f64::NAN);
+            self.data.extend_from_slice(&value.to_le_bytes());
+        } else {
+            let value = value.to_f32().unwrap_or(f32::NAN);
+            self.data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    pub fn write_values<T: Float>(&mut self, values: &[T]) {
+        self.write_usize(values.len());
+        values.iter().for_each(|&v| self.write_value(v));
+    }
+
+    pub fn write_vec3<T: Float>(&mut self, v: &Vec3<T>) {
+        self.write_value(v.x);
+        self.write_value(v.y);
+        self.write_value(v.z);
+    }
+
+    pub fn write_bounds<T: Float>(&mut self, bounds: &BoundingBox<T>) {
+        self.write_vec3(&bounds.min);
+        self.write_vec3(&bounds.max);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// Little endian reader for binary fields, which converts the values to `T`.
+pub(crate) struct FieldReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    double_precision: bool,
+}
+
+impl<'a> FieldReader<'a> {
+    /// Check the magic bytes and the format version, and read the value precision.
+    pub fn new(data: &'a [u8], magic: &[u8; 8]) -> io::Result<Self> {
+        if data.len() < magic.len() || &data[..magic.len()] != magic {
+            return Err(invalid_data(format!(
+                "Missing {} header, the data is not a binary field of this type.",
+                String::from_utf8_lossy(magic)
+            )));
+        }
+        let mut reader = Self {
+            data,
+            position: magic.len(),
+            double_precision: false,
+        };
+        let version = reader.read_u32()?;
+        if version != FIELD_FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported field format version {}, expected {}.",
+                version, FIELD_FORMAT_VERSION
+            )));
+        }
+        reader.double_precision = match reader.read_u8()? {
+            4 => false,
+            8 => true,
+            size => return Err(invalid_data(format!("Invalid value size {}.", size))),
+        };
+        Ok(reader)
+    }
+
+    fn take<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let end = self.position + N;
+        if end > self.data.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("Field data ends unexpectedly at byte {}.", self.data.len()),
+            ));
+        }
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.data[self.position..end]);
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> io::Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    pub fn read_u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_usize(&mut self) -> io::Result<usize> {
+        usize::try_from(self.read_u64()?).map_err(|_| invalid_data("Size is too large."))
+    }
+
+    pub fn read_value<T: Float>(&mut self) -> io::Result<T> {
+        let value = if self.double_precision {
+            T::from(f64::from_le_bytes(self.take()?))
+        } else {
+            T::from(f32::from_le_bytes(self.take()?))
+        };
+        value.ok_or_else(|| invalid_data("Value can't be represented by the field type."))
+    }
+
+    /// Read a list of values, which is expected to have `expected` elements.
+    pub fn read_values<T: Float>(&mut self, expected: usize) -> io::Result<Vec<T>> {
+        let count = self.read_usize()?;
+        if count != expected {
+            return Err(invalid_data(format!(
+                "Expected {} values, found {}.",
+                expected, count
+            )));
+        }
+        (0..count).map(|_| self.read_value()).collect()
+    }
+
+    pub fn read_vec3<T: Float>(&mut self) -> io::Result<Vec3<T>> {
+        Ok(Vec3::new(
+            self.read_value()?,
+            self.read_value()?,
+            self.read_value()?,
+        ))
+    }
+
+    pub fn read_bounds<T: Float>(&mut self) -> io::Result<BoundingBox<T>> {
+        Ok(BoundingBox::new(self.read_vec3()?, self.read_vec3()?))
+    }
+
+    /// Check that all data has been read.
+    pub fn finish(self) -> io::Result<()> {
+        if self.position != self.data.len() {
+            return Err(invalid_data(format!(
+                "Unexpected {} bytes after the end of the field.",
+                self.data.len() - self.position
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precision_conversion() {
+        let mut writer = FieldWriter::new::<f32>(DENSE_FIELD_MAGIC);
+        writer.write_values(&[1.5f32, -2.25]);
+        writer.write_bounds(&BoundingBox::new(
+            Vec3::origin(),
+            Vec3::new(1.0f32, 2.0, 3.0),
+        ));
+        let data = writer.into_bytes();
+        assert_eq!(data.len(), 8 + 4 + 1 + 8 + 2 * 4 + 6 * 4);
+
+        let mut reader = FieldReader::new(&data, DENSE_FIELD_MAGIC).unwrap();
+        assert_eq!(reader.read_values::<f64>(2).unwrap(), vec![1.5, -2.25]);
+        let bounds = reader.read_bounds::<f64>().unwrap();
+        assert_eq!(bounds.max.z, 3.0);
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        let mut writer = FieldWriter::new::<f64>(DENSE_FIELD_MAGIC);
+        writer.write_values(&[1.0f64, 2.0]);
+        let data = writer.into_bytes();
+
+        assert!(FieldReader::new(&data, SPARSE_FIELD_MAGIC).is_err());
+        let mut reader = FieldReader::new(&data, DENSE_FIELD_MAGIC).unwrap();
+        assert!(reader.read_values::<f64>(3).is_err());
+
+        let mut reader = FieldReader::new(&data[..data.len() - 1], DENSE_FIELD_MAGIC).unwrap();
+        let error = reader.read_values::<f64>(2).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let mut version = data.clone();
+        version[8] = 2;
+        assert!(FieldReader::new(&version, DENSE_FIELD_MAGIC).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt::Write;
+
+use num_traits::Float;
+
+use crate::types::geometry::{Mesh, Vec3};
+
+use super::base64;
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const COMPONENT_FLOAT: u32 = 5126;
+const COMPONENT_UNSIGNED_INT: u32 = 5125;
+
+const GLB_MAGIC: u32 = 0x46546C67;
+const GLB_CHUNK_JSON: u32 = 0x4E4F534A;
+const GLB_CHUNK_BIN: u32 = 0x004E4942;
+
+/// Write meshes as a glTF 2.0 asset, with one node per mesh under a common root node.
+///
+/// # Arguments
+///
+/// * `objects` - Names and meshes of the objects.
+/// * `scale` - Scale of the root node, converting the mesh coordinates to meters.
+/// * `binary` - Option to write a `.glb` file instead of a `.gltf` file with an embedded buffer.
+pub(crate) fn write_gltf<T: Float>(
+    objects: &[(&str, &Mesh<T>)],
+    scale: f64,
+    binary: bool,
+) -> Vec<u8> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut meshes = Vec::new();
+
+    let mut add_view = |buffer: &mut Vec<u8>, data: Vec<u8>, target: u32| {
+        let offset = buffer.len();
+        buffer.extend_from_slice(&data);
+        buffer_views.push(format!(
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+            offset,
+            data.len(),
+            target
+        ));
+        buffer_views.len() - 1
+    };
+
+    for (_, mesh) in objects {
+        let bounds = mesh.bounds();
+        let position_view = add_view(&mut buffer, vec3_bytes(mesh.vertices()), ARRAY_BUFFER);
+        accessors.push(format!(
+            r#"{{"bufferView":{},"componentType":{},"count":{},"type":"VEC3","min":[{},{},{}],"max":[{},{},{}]}}"#,
+            position_view,
+            COMPONENT_FLOAT,
+            mesh.num_vertices(),
+            f32_value(bounds.min.x),
+            f32_value(bounds.min.y),
+            f32_value(bounds.min.z),
+            f32_value(bounds.max.x),
+            f32_value(bounds.max.y),
+            f32_value(bounds.max.z),
+        ));
+        let mut attributes = format!(r#""POSITION":{}"#, accessors.len() - 1);
+
+        if let Some(normals) = mesh.normals() {
+            // glTF requires unit length normals.
+            let unit: Vec<Vec3<T>> = normals
+                .iter()
+                .map(|n| {
+                    let length = n.magnitude();
+                    if length > T::zero() {
+                        *n * (T::one() / length)
+                    } else {
+                        Vec3::z_axis()
+                    }
+                })
+                .collect();
+            let normal_view = add_view(&mut buffer, vec3_bytes(&unit), ARRAY_BUFFER);
+            accessors.push(format!(
+                r#"{{"bufferView":{},"componentType":{},"count":{},"type":"VEC3"}}"#,
+                normal_view,
+                COMPONENT_FLOAT,
+                unit.len()
+            ));
+            let _ = write!(attributes, r#","NORMAL":{}"#, accessors.len() - 1);
+        }
+
+        let indices: Vec<u8> = mesh
+            .faces()
+            .iter()
+            .flatten()
+            .flat_map(|&i| (i as u32).to_le_bytes())
+            .collect();
+        let index_view = add_view(&mut buffer, indices, ELEMENT_ARRAY_BUFFER);
+        accessors.push(format!(
+            r#"{{"bufferView":{},"componentType":{},"count":{},"type":"SCALAR"}}"#,
+            index_view,
+            COMPONENT_UNSIGNED_INT,
+            3 * mesh.num_faces()
+        ));
+
+        meshes.push(format!(
+            r#"{{"primitives":[{{"attributes":{{{}}},"indices":{},"mode":4}}]}}"#,
+            attributes,
+            accessors.len() - 1
+        ));
+    }
+
+    // The root node converts the mesh coordinates to meters and rotates from z-up to the y-up convention of glTF.
+    let half_sqrt_two = std::f64::consts::FRAC_1_SQRT_2;
+    let children: Vec<String> = (1..=objects.len()).map(|i| i.to_string()).collect();
+    let mut nodes = vec![format!(
+        r#"{{"name":"Root","children":[{}],"rotation":[{},0,0,{}],"scale":[{},{},{}]}}"#,
+        children.join(","),
+        -half_sqrt_two,
+        half_sqrt_two,
+        scale,
+        scale,
+        scale
+    )];
+    nodes.extend(
+        objects
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| format!(r#"{{"name":{},"mesh":{}}}"#, json_string(name), i)),
+    );
+
+    let uri = if binary {
+        String::new()
+    } else {
+        format!(
+            r#","uri":"data:application/octet-stream;base64,{}""#,
+            base64::encode(&buffer)
+        )
+    };
+    let json = format!(
+        r#"{{"asset":{{"version":"2.0","generator":"Imlet {}"}},"scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{}],"meshes":[{}],"accessors":[{}],"bufferViews":[{}],"buffers":[{{"byteLength":{}{}}}]}}"#,
+        crate::IMLET_VERSION,
+        nodes.join(","),
+        meshes.join(","),
+        accessors.join(","),
+        buffer_views.join(","),
+        buffer.len(),
+        uri
+    );
+
+    if binary {
+        glb(json, buffer)
+    } else {
+        json.into_bytes()
+    }
+}
+
+/// Pack the json and the binary buffer in the chunks of a `.glb` file.
+fn glb(json: String, mut buffer: Vec<u8>) -> Vec<u8> {
+    let mut json = json.into_bytes();
+    while !json.len().is_multiple_of(4) {
+        json.push(b' ');
+    }
+    while !buffer.len().is_multiple_of(4) {
+        buffer.push(0);
+    }
+
+    let length = 12 + 8 + json.len() + 8 + buffer.len();
+    let mut data = Vec::with_capacity(length);
+    data.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&(length as u32).to_le_bytes());
+    for (chunk_type, chunk) in [(GLB_CHUNK_JSON, json), (GLB_CHUNK_BIN, buffer)] {
+        data.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        data.extend_from_slice(&chunk_type.to_le_bytes());
+        data.extend_from_slice(&chunk);
+    }
+    data
+}
+
+fn f32_value<T: Float>(value: T) -> f32 {
+    value.to_f32().unwrap_or(0.0)
+}
+
+fn vec3_bytes<T: Float>(vectors: &[Vec3<T>]) -> Vec<u8> {
+    vectors
+        .iter()
+        .flat_map(|v| [v.x, v.y, v.z])
+        .flat_map(|value| f32_value(value).to_le_bytes())
+        .collect()
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::io::parse_obj_file;
+
+    use super::*;
+
+    fn read_u32(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_embedded_buffer() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let gltf = String::from_utf8(write_gltf(&[("Box \"A\"", &mesh)], 0.001, false)).unwrap();
+
+        assert!(gltf.contains(r#""name":"Box \"A\"""#));
+        assert!(gltf.contains(r#""scale":[0.001,0.001,0.001]"#));
+        assert!(gltf.contains(r#""min":[0,0,0],"max":[20,20,20]"#));
+        assert!(gltf.contains(r#""NORMAL":1"#));
+
+        // 8 positions and normals, and 36 indices.
+        let byte_length = 2 * 8 * 12 + 36 * 4;
+        assert!(gltf.contains(&format!(r#""byteLength":{}"#, byte_length)));
+        let start = gltf.find("base64,").unwrap() + 7;
+        let end = start + gltf[start..].find('"').unwrap();
+        assert_eq!(
+            base64::decode(&gltf[start..end]).unwrap().len(),
+            byte_length
+        );
+    }
+
+    #[test]
+    fn test_glb_layout() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let glb = write_gltf(&[("A", &mesh), ("B", &mesh)], 1.0, true);
+
+        assert_eq!(read_u32(&glb, 0), GLB_MAGIC);
+        assert_eq!(read_u32(&glb, 8) as usize, glb.len());
+        let json_length = read_u32(&glb, 12) as usize;
+        assert_eq!(json_length % 4, 0);
+        assert_eq!(read_u32(&glb, 16), GLB_CHUNK_JSON);
+        let json = std::str::from_utf8(&glb[20..20 + json_length]).unwrap();
+        assert!(json.contains(r#""scenes":[{"nodes":[0]}]"#));
+        assert!(json.contains(r#""children":[1,2]"#));
+        assert!(json.contains(r#""rotation":[-0.7071067811865476,0,0,0.7071067811865476]"#));
+        assert!(!json.contains("uri"));
+
+        let bin_length = read_u32(&glb, 20 + json_length) as usize;
+        assert_eq!(read_u32(&glb, 24 + json_length), GLB_CHUNK_BIN);
+        assert_eq!(bin_length, 2 * (2 * 8 * 12 + 36 * 4));
+        assert_eq!(28 + json_length + bin_length, glb.len());
+    }
+}
@@ -0,0 +1,350 @@
+use num_traits::Float;
+
+use crate::algorithms::marching_squares::PlaneSection;
+
+use super::zip::crc32;
+
+/// Maximum length of a stored deflate block.
+const MAX_STORED_BLOCK: usize = 65535;
+
+/// Colour map used to render the values of a field in section images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMap {
+    /// Blue below zero, white at zero and red above zero. The default range is symmetric around zero.
+    #[default]
+    Diverging,
+    /// Perceptually uniform map from dark purple for low values to yellow for high values.
+    Viridis,
+    /// Black for low values and white for high values.
+    Grayscale,
+}
+
+/// Style of section images written with [`write_section_ppm`](crate::utils::io::write_section_ppm) and [`write_section_png`](crate::utils::io::write_section_png).
+#[derive(Debug, Clone, Copy)]
+pub struct SectionImageStyle<T> {
+    /// Colour map for the values.
+    pub color_map: ColorMap,
+    /// Values mapped to the ends of the colour map. If `None`, the range of the sampled values is used.
+    pub range: Option<(T, T)>,
+    /// Whether to highlight the zero level in black.
+    pub zero_level: bool,
+    /// Distance between iso-lines drawn over the colours, or `None` for no iso-lines.
+    pub iso_line_spacing: Option<T>,
+}
+
+impl<T> Default for SectionImageStyle<T> {
+    fn default() -> Self {
+        Self {
+            color_map: ColorMap::default(),
+            range: None,
+            zero_level: true,
+            iso_line_spacing: None,
+        }
+    }
+}
+
+impl<T> SectionImageStyle<T> {
+    /// Set the colour map for the values.
+    pub fn with_color_map(mut self, color_map: ColorMap) -> Self {
+        self.color_map = color_map;
+        self
+    }
+
+    /// Set the values mapped to the ends of the colour map, or `None` to use the range of the sampled values.
+    pub fn with_range(mut self, range: Option<(T, T)>) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Set whether to highlight the zero level in black.
+    pub fn with_zero_level(mut self, zero_level: bool) -> Self {
+        self.zero_level = zero_level;
+        self
+    }
+
+    /// Set the distance between iso-lines, or `None` for no iso-lines.
+    pub fn with_iso_line_spacing(mut self, spacing: Option<T>) -> Self {
+        self.iso_line_spacing = spacing;
+        self
+    }
+}
+
+/// Image with 8 bit RGB pixels.
+#[derive(Debug, Clone)]
+pub(crate) struct RgbImage {
+    pub width: usize,
+    pub height: usize,
+    /// Red, green and blue bytes of each pixel, row by row, starting with the top row of the image.
+    pub pixels: Vec<u8>,
+}
+
+/// Render the values of a plane section as an image, with one pixel per sampled point.
+///
+/// The first in-plane axis points to the right and the second one up.
+pub(crate) fn render_section<T: Float>(
+    section: &PlaneSection<T>,
+    style: &SectionImageStyle<T>,
+) -> RgbImage {
+    let (nx, ny) = section.num_points();
+    let (low, high) = style
+        .range
+        .unwrap_or_else(|| value_range(section, style.color_map));
+    let zero_crossing = |a: T, b: T| (a < T::zero()) != (b < T::zero());
+    let iso_crossing = |a: T, b: T| match style.iso_line_spacing {
+        Some(spacing) if spacing > T::zero() => (a / spacing).floor() != (b / spacing).floor(),
+        _ => false,
+    };
+
+    let mut pixels = Vec::with_capacity(nx * ny * 3);
+    for row in 0..ny {
+        let j = ny - 1 - row;
+        for i in 0..nx {
+            let value = section.value(i, j);
+            let neighbours = [
+                (i + 1 < nx).then(|| section.value(i + 1, j)),
+                (j + 1 < ny).then(|| section.value(i, j + 1)),
+            ];
+            let crosses = |crossing: &dyn Fn(T, T) -> bool| {
+                neighbours
+                    .iter()
+                    .flatten()
+                    .any(|&other| crossing(value, other))
+            };
+
+            let color = if style.zero_level && crosses(&zero_crossing) {
+                [0, 0, 0]
+            } else {
+                let t = if high > low {
+                    ((value - low) / (high - low)).max(T::zero()).min(T::one())
+                } else {
+                    T::from(0.5).unwrap()
+                };
+                let color = map_color(style.color_map, t.to_f64().unwrap_or(0.5));
+                if crosses(&iso_crossing) {
+                    color.map(|c| c / 2)
+                } else {
+                    color
+                }
+            };
+            pixels.extend_from_slice(&color);
+        }
+    }
+
+    RgbImage {
+        width: nx,
+        height: ny,
+        pixels,
+    }
+}
+
+/// Range of the colour map, symmetric around zero for the diverging map.
+fn value_range<T: Float>(section: &PlaneSection<T>, color_map: ColorMap) -> (T, T) {
+    let (min, max) = section
+        .values()
+        .iter()
+        .fold((T::max_value(), T::min_value()), |(min, max), &v| {
+            (min.min(v), max.max(v))
+        });
+    match color_map {
+        ColorMap::Diverging => {
+            let extent = min.abs().max(max.abs());
+            (-extent, extent)
+        }
+        ColorMap::Viridis | ColorMap::Grayscale => (min, max),
+    }
+}
+
+/// Colour of a normalized value between 0 and 1, interpolated linearly between the control points of the map.
+fn map_color(color_map: ColorMap, t: f64) -> [u8; 3] {
+    let points: &[[f64; 3]] = match color_map {
+        ColorMap::Diverging => &[
+            [59.0, 76.0, 192.0],
+            [247.0, 247.0, 247.0],
+            [180.0, 4.0, 38.0],
+        ],
+        ColorMap::Viridis => &[
+            [68.0, 1.0, 84.0],
+            [59.0, 82.0, 139.0],
+            [33.0, 145.0, 140.0],
+            [94.0, 201.0, 98.0],
+            [253.0, 231.0, 37.0],
+        ],
+        ColorMap::Grayscale => &[[0.0, 0.0, 0.0], [255.0, 255.0, 255.0]],
+    };
+    let position = t.clamp(0.0, 1.0) * (points.len() - 1) as f64;
+    let index = (position.floor() as usize).min(points.len() - 2);
+    let local = position - index as f64;
+    let (a, b) = (points[index], points[index + 1]);
+    [0, 1, 2].map(|c| (a[c] + (b[c] - a[c]) * local).round() as u8)
+}
+
+/// Encode an image in the binary PPM (`P6`) format.
+pub(crate) fn to_ppm(image: &RgbImage) -> Vec<u8> {
+    let mut data = format!("P6\n{} {}\n255\n", image.width, image.height).into_bytes();
+    data.extend_from_slice(&image.pixels);
+    data
+}
+
+/// Encode an image in the PNG format, with the pixel data stored in uncompressed deflate blocks.
+pub(crate) fn to_png(image: &RgbImage) -> Vec<u8> {
+    let row_length = image.width * 3;
+    let mut raw = Vec::with_capacity((row_length + 1) * image.height);
+    for row in image.pixels.chunks_exact(row_length) {
+        // Each row starts with its filter type, 0 for no filtering.
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    // Zlib stream with the lowest compression level in the header.
+    let mut zlib = vec![0x78, 0x01];
+    let num_blocks = raw.len().div_ceil(MAX_STORED_BLOCK);
+    for (index, block) in raw.chunks(MAX_STORED_BLOCK).enumerate() {
+        zlib.push(u8::from(index + 1 == num_blocks));
+        zlib.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(image.width as u32).to_be_bytes());
+    header.extend_from_slice(&(image.height as u32).to_be_bytes());
+    // 8 bits per channel, RGB colour type, default compression, filtering and no interlacing.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+    push_chunk(&mut data, b"IHDR", &header);
+    push_chunk(&mut data, b"IDAT", &zlib);
+    push_chunk(&mut data, b"IEND", &[]);
+    data
+}
+
+fn push_chunk(data: &mut Vec<u8>, chunk_type: &[u8; 4], content: &[u8]) {
+    data.extend_from_slice(&(content.len() as u32).to_be_bytes());
+    let start = data.len();
+    data.extend_from_slice(chunk_type);
+    data.extend_from_slice(content);
+    let crc = crc32(&data[start..]);
+    data.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        computation::model::ImplicitModel,
+        geometry::{Plane, Sphere, Vec2, Vec3},
+    };
+
+    fn image() -> RgbImage {
+        RgbImage {
+            width: 2,
+            height: 1,
+            pixels: vec![255, 0, 0, 0, 0, 255],
+        }
+    }
+
+    #[test]
+    fn test_ppm() {
+        assert_eq!(to_ppm(&image()), b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\xff");
+    }
+
+    #[test]
+    fn test_png() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+
+        let png = to_png(&image());
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        // Read the chunks and check their checksums.
+        let mut chunks = Vec::new();
+        let mut position = 8;
+        while position < png.len() {
+            let length =
+                u32::from_be_bytes(png[position..position + 4].try_into().unwrap()) as usize;
+            let body = &png[position + 4..position + 8 + length];
+            let crc = u32::from_be_bytes(
+                png[position + 8 + length..position + 12 + length]
+                    .try_into()
+                    .unwrap(),
+            );
+            assert_eq!(crc32(body), crc);
+            chunks.push((body[..4].to_vec(), body[4..].to_vec()));
+            position += 12 + length;
+        }
+        let types: Vec<&[u8]> = chunks.iter().map(|(t, _)| t.as_slice()).collect();
+        assert_eq!(types, vec![&b"IHDR"[..], b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, vec![0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+
+        // A single final stored block with the filtered row.
+        let zlib = &chunks[1].1;
+        assert_eq!(&zlib[..3], &[0x78, 0x01, 1]);
+        assert_eq!(&zlib[3..7], &[7, 0, !7, 0xFF]);
+        assert_eq!(&zlib[7..14], &[0, 255, 0, 0, 0, 0, 255]);
+        assert_eq!(&zlib[14..], &adler32(&zlib[7..14]).to_be_bytes());
+    }
+
+    #[test]
+    fn test_render_section() {
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::origin(), 2.0))
+            .unwrap();
+        let section = PlaneSection::sample(
+            &model,
+            "Sphere",
+            &Plane::xy(),
+            Vec2::new(-4.0, -3.0),
+            Vec2::new(4.0, 3.0),
+            0.1,
+        )
+        .unwrap();
+        let pixel = |image: &RgbImage, x: usize, y: usize| {
+            let index = 3 * (x + image.width * y);
+            [
+                image.pixels[index],
+                image.pixels[index + 1],
+                image.pixels[index + 2],
+            ]
+        };
+
+        let image = render_section(&section, &SectionImageStyle::default());
+        assert_eq!((image.width, image.height), (81, 61));
+        assert_eq!(image.pixels.len(), 81 * 61 * 3);
+        // Inside is blue, outside red, with black pixels along the zero level.
+        let center = pixel(&image, 40, 30);
+        assert!(center[2] > center[0]);
+        let corner = pixel(&image, 0, 0);
+        assert!(corner[0] > corner[2]);
+        let black = image
+            .pixels
+            .chunks_exact(3)
+            .filter(|p| p == &[0, 0, 0])
+            .count();
+        assert!(black > 40 && black < 200);
+
+        let style = SectionImageStyle::default()
+            .with_color_map(ColorMap::Grayscale)
+            .with_zero_level(false);
+        let plain = render_section(&section, &style);
+        assert_eq!(pixel(&plain, 40, 30), [0, 0, 0]);
+        assert_eq!(pixel(&plain, 80, 0), [255, 255, 255]);
+
+        // Iso-lines darken the pixels where they cross, at the 7 levels from -1.5 to 1.5 up to x = 3.5.
+        let lines = render_section(&section, &style.with_iso_line_spacing(Some(0.5)));
+        let darkened: Vec<bool> = (40..76)
+            .map(|x| pixel(&lines, x, 30) != pixel(&plain, x, 30))
+            .collect();
+        let rings = darkened.windows(2).filter(|w| w[1] && !w[0]).count();
+        assert_eq!(rings, 7);
+    }
+}
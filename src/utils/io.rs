@@ -8,18 +8,39 @@ use std::{
 
 use num_traits::Float;
 
-#[cfg(feature = "serde")]
-use crate::types::computation::{model::ImplicitModel, traits::ModelFloat};
+use crate::types::computation::model::ImplicitModel;
+use crate::types::computation::traits::ModelFloat;
 #[cfg(feature = "serde")]
 use serde::de::DeserializeOwned;
 
 use crate::types::{
-    computation::data::field_iterator::ValueIterator,
-    geometry::{Mesh, Vec3},
+    computation::{
+        data::{field_iterator::ValueIterator, DenseField, SparseField},
+        functions::{MeshUnits, VolumeField},
+    },
+    geometry::{Mesh, Plane, Vec2, Vec3, Vec3i},
 };
 
+use crate::algorithms::{marching_squares::PlaneSection, slicing::SliceLayer};
 use crate::types::computation::data::field_iterator::PointIterator;
 
+pub use super::image::{ColorMap, SectionImageStyle};
+
+use super::{
+    gltf::write_gltf,
+    image::{render_section, to_png, to_ppm},
+    netpbm::read_pgm,
+    obj::{read_obj, ObjContent, ObjCorner},
+    ply::{read_ply, write_ply, PlyElement, PlyFormat, PlyValues},
+    slices::{contour_extents, layer_to_svg, layers_to_cli},
+    stl::{mesh_to_stl_ascii, mesh_to_stl_binary, read_stl},
+    three_mf::write_3mf,
+    volume::{
+        block_to_nrrd, block_to_vti, block_to_vtk_legacy, blocks_to_vtk_unstructured, vtm_index,
+        VolumeBlock,
+    },
+};
+
 pub(crate) fn mesh_to_obj<T: Display>(mesh: &Mesh<T>) -> String {
     let mut data = String::new();
 
@@ -28,16 +49,21 @@ pub(crate) fn mesh_to_obj<T: Display>(mesh: &Mesh<T>) -> String {
         data.push_str(&v_string);
     }
 
-    for f in mesh.faces() {
-        let f_string = format!("f {} {} {}\n", f[0] + 1, f[1] + 1, f[2] + 1);
-        data.push_str(&f_string);
-    }
-
     if let Some(normals) = mesh.normals() {
         for n in normals.iter() {
             let v_string = format!("vn {} {} {}\n", n.x, n.y, n.z);
             data.push_str(&v_string);
         }
+        // Each vertex uses the normal with the same index.
+        for f in mesh.faces() {
+            let [a, b, c] = f.map(|i| i + 1);
+            data.push_str(&format!("f {a}//{a} {b}//{b} {c}//{c}\n"));
+        }
+    } else {
+        for f in mesh.faces() {
+            let f_string = format!("f {} {} {}\n", f[0] + 1, f[1] + 1, f[2] + 1);
+            data.push_str(&f_string);
+        }
     }
 
     data
@@ -64,216 +90,1887 @@ pub fn write_obj_file<T: Display>(mesh: &Mesh<T>, file_name: &str) -> io::Result
     Ok(())
 }
 
-use std::fs::File;
+/// Encoding of an STL file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StlFormat {
+    /// Human readable text, with coordinates at full precision.
+    Ascii,
+    /// Compact binary data, with coordinates as 32 bit floats.
+    Binary,
+}
 
-/// Read a mesh from an .obj file.
+/// Write a mesh to an .stl file.
+///
+/// # Arguments
+///
+/// * `mesh` - Mesh to export.
+/// * `file_name` - Name of the target file to be created, without .stl extension.
+/// * `format` - Option to write the file as ascii text or binary data.
+pub fn write_stl_file<T: Float + Display>(
+    mesh: &Mesh<T>,
+    file_name: &str,
+    format: StlFormat,
+) -> io::Result<()> {
+    let file_path = Path::new(file_name).with_extension("stl");
+    let data = match format {
+        StlFormat::Ascii => {
+            let name = file_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("mesh");
+            mesh_to_stl_ascii(mesh, name).into_bytes()
+        }
+        StlFormat::Binary => mesh_to_stl_binary(mesh),
+    };
+    fs::write(&file_path, data)?;
+
+    log::info!(
+        "Stl file with {} triangles written as {}",
+        mesh.num_faces(),
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Read a mesh from an ascii or binary .stl file.
+///
+/// STL files store each triangle separately, so coincident vertices are merged to create an indexed mesh with smooth vertex normals.
 ///
 /// # Arguments
 ///
 /// * `file_path` - Relative path to the file.
 /// * `flip_yz` - Option to flip the y and z directions. Imlet uses z as up-direction so if the mesh has y, you may want to flip it.
-/// * `read_normals` - Option to read the normals from the `.obj`. If not smooth vertex normals will be calculated automatically. Using `false` is advised if the mesh is to be used as a distance field.
-pub fn parse_obj_file<T: Float>(
+pub fn parse_stl_file<T: ModelFloat>(
     file_path: &str,
     flip_yz: bool,
-    read_normals: bool,
 ) -> Result<Mesh<T>, Box<dyn std::error::Error>> {
     let path = Path::new(file_path);
-
-    let extension = path
+    if !path
         .extension()
-        .ok_or_else(|| format!("Cannot read file {file_path}. Only .obj files are supported."))?;
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("stl"))
+    {
+        return Err(format!("Cannot read file {file_path}. Only .stl files are supported.").into());
+    }
 
-    if !extension.eq_ignore_ascii_case("obj") {
-        return Err(format!("Cannot read file {file_path}. Only .obj files are supported.").into());
+    let mut triangles = read_stl::<T>(&fs::read(path)?)?;
+    if flip_yz {
+        for triangle in triangles.iter_mut() {
+            for v in triangle.p.iter_mut() {
+                *v = Vec3::new(v.x, -v.z, v.y);
+            }
+        }
     }
+    let mesh = Mesh::from_triangles(&triangles, true, None);
 
-    let file = File::open(path)?;
+    log::info!(
+        "Stl file {} with {} vertices and {} faces successfully read.",
+        file_path,
+        mesh.num_vertices(),
+        mesh.num_faces()
+    );
 
-    let mut vertices: Vec<Vec3<T>> = Vec::new();
-    let mut faces: Vec<[usize; 3]> = Vec::new();
-    let mut normals: Vec<Vec3<T>> = Vec::new();
-    let mut mesh = Mesh::new();
+    Ok(mesh)
+}
 
-    for line in io::BufReader::new(file).lines() {
-        let line = line?;
-        let parts: Vec<&str> = line.split_whitespace().collect();
+/// Encoding of a PLY file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlyEncoding {
+    /// Human readable text.
+    Ascii,
+    /// Compact binary data, in little endian byte order.
+    BinaryLittleEndian,
+}
 
-        if parts.is_empty() {
-            continue;
+/// Named scalar value for each vertex of a mesh or point cloud, such as field values sampled at the vertices.
+#[derive(Debug, Clone)]
+pub struct VertexProperty<T> {
+    /// Name of the property in the file.
+    pub name: String,
+    /// One value per vertex.
+    pub values: Vec<T>,
+}
+
+impl<T> VertexProperty<T> {
+    /// Create a new vertex property.
+    /// # Arguments
+    ///
+    /// * `name` - Name of the property in the file.
+    /// * `values` - One value per vertex.
+    pub fn new(name: &str, values: Vec<T>) -> Self {
+        Self {
+            name: name.to_string(),
+            values,
         }
+    }
+}
 
-        match parts[0] {
-            "v" => {
-                // Parse vertex position
-                if parts.len() != 4 {
-                    return Err("Invalid vertex format. Make sure file is triangulated.,".into());
-                }
-                let x: f32 = parts[1].parse()?;
-                let y: f32 = parts[2].parse()?;
-                let z: f32 = parts[3].parse()?;
-                vertices.push(Vec3::new(
-                    T::from(x).unwrap(),
-                    if flip_yz {
-                        -T::from(z).unwrap()
-                    } else {
-                        T::from(y).unwrap()
-                    },
+/// Mesh and additional vertex properties read from a PLY file.
+pub type PlyData<T> = (Mesh<T>, Vec<VertexProperty<T>>);
+
+/// Vertex properties with a special meaning, which are not returned as [`VertexProperty`].
+const PLY_GEOMETRY_PROPERTIES: [&str; 6] = ["x", "y", "z", "nx", "ny", "nz"];
+
+/// Write a mesh or point cloud to a .ply file, with vertex normals and additional vertex properties.
+///
+/// Vertex data is written as 32 bit floats. A mesh without faces is written as a point cloud.
+///
+/// # Arguments
+///
+/// * `mesh` - Mesh to export. The normals are written if the mesh has them.
+/// * `properties` - Additional properties with one value per vertex.
+/// * `file_name` - Name of the target file to be created, without .ply extension.
+/// * `encoding` - Option to write the file as ascii text or binary data.
+///
+/// # Example
+///
+/// Export the values of a model at the vertices of a mesh.
+///
+/// ```rust
+/// # use imlet::types::computation::{functions::Gyroid, model::ImplicitModel};
+/// # use imlet::types::geometry::BoundingBox;
+/// # use imlet::utils::io::{write_ply_file, PlyEncoding, VertexProperty};
+/// # let mut model = ImplicitModel::new();
+/// # model.add_function("Gyroid", Gyroid::with_equal_spacing(5.0, true)).unwrap();
+/// # let mesh = imlet::utils::io::parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+/// let values = mesh
+///     .vertices()
+///     .iter()
+///     .map(|v| model.evaluate_at("Gyroid", v.x, v.y, v.z).unwrap())
+///     .collect();
+/// let property = VertexProperty::new("gyroid", values);
+///
+/// # let file_name = std::env::temp_dir().join("imlet_doc_gyroid_values");
+/// # let file_name = file_name.to_str().unwrap();
+/// write_ply_file(&mesh, &[property], file_name, PlyEncoding::BinaryLittleEndian).unwrap();
+/// # std::fs::remove_file(format!("{}.ply", file_name)).unwrap();
+/// ```
+pub fn write_ply_file<T: Float>(
+    mesh: &Mesh<T>,
+    properties: &[VertexProperty<T>],
+    file_name: &str,
+    encoding: PlyEncoding,
+) -> io::Result<()> {
+    for property in properties {
+        if property.values.len() != mesh.num_vertices() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Vertex property {} has {} values, but the mesh has {} vertices.",
+                    property.name,
+                    property.values.len(),
+                    mesh.num_vertices()
+                ),
+            ));
+        }
+        if PLY_GEOMETRY_PROPERTIES.contains(&property.name.as_str()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Vertex property name {} is reserved.", property.name),
+            ));
+        }
+    }
+
+    let column = |values: &mut dyn Iterator<Item = T>| {
+        PlyValues::Scalar(values.map(|v| v.to_f64().unwrap()).collect())
+    };
+    let vertices = mesh.vertices();
+    let mut vertex_properties = vec![
+        ("x".to_string(), column(&mut vertices.iter().map(|v| v.x))),
+        ("y".to_string(), column(&mut vertices.iter().map(|v| v.y))),
+        ("z".to_string(), column(&mut vertices.iter().map(|v| v.z))),
+    ];
+    if let Some(normals) = mesh.normals() {
+        vertex_properties.extend([
+            ("nx".to_string(), column(&mut normals.iter().map(|n| n.x))),
+            ("ny".to_string(), column(&mut normals.iter().map(|n| n.y))),
+            ("nz".to_string(), column(&mut normals.iter().map(|n| n.z))),
+        ]);
+    }
+    for property in properties {
+        vertex_properties.push((
+            property.name.clone(),
+            column(&mut property.values.iter().copied()),
+        ));
+    }
+
+    let mut elements = vec![PlyElement {
+        name: "vertex".to_string(),
+        count: mesh.num_vertices(),
+        properties: vertex_properties,
+    }];
+    if mesh.num_faces() > 0 {
+        elements.push(PlyElement {
+            name: "face".to_string(),
+            count: mesh.num_faces(),
+            properties: vec![(
+                "vertex_indices".to_string(),
+                PlyValues::List(
+                    mesh.faces()
+                        .iter()
+                        .map(|f| f.iter().map(|&i| i as f64).collect())
+                        .collect(),
+                ),
+            )],
+        });
+    }
+
+    let format = match encoding {
+        PlyEncoding::Ascii => PlyFormat::Ascii,
+        PlyEncoding::BinaryLittleEndian => PlyFormat::BinaryLittleEndian,
+    };
+    let file_path = Path::new(file_name).with_extension("ply");
+    fs::write(&file_path, write_ply(format, &elements))?;
+
+    log::info!(
+        "Ply file with {} vertices and {} faces written as {}",
+        mesh.num_vertices(),
+        mesh.num_faces(),
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Read a mesh or point cloud from an ascii or binary .ply file, with the additional vertex properties.
+///
+/// Polygons are triangulated as fans. Vertex normals are read if the file has them, and computed from the faces otherwise.
+/// Any other scalar vertex property, such as colors or field values, is returned as a [`VertexProperty`].
+///
+/// # Arguments
+///
+/// * `file_path` - Relative path to the file.
+/// * `flip_yz` - Option to flip the y and z directions. Imlet uses z as up-direction so if the mesh has y, you may want to flip it.
+pub fn parse_ply_file<T: Float>(
+    file_path: &str,
+    flip_yz: bool,
+) -> Result<PlyData<T>, Box<dyn std::error::Error>> {
+    let file = File::open(file_path)?;
+    let elements = read_ply(io::BufReader::new(file))?;
+    let vertices = elements
+        .iter()
+        .find(|element| element.name == "vertex")
+        .ok_or("PLY file has no vertex element.")?;
+
+    let columns = |names: [&str; 3]| -> Option<Vec<Vec3<T>>> {
+        let [x, y, z] = names.map(|name| vertices.scalar(name));
+        let (x, y, z) = (x?, y?, z?);
+        Some(
+            (0..vertices.count)
+                .map(|i| {
+                    let (x, y, z) = (
+                        T::from(x[i]).unwrap(),
+                        T::from(y[i]).unwrap(),
+                        T::from(z[i]).unwrap(),
+                    );
                     if flip_yz {
-                        T::from(y).unwrap()
+                        Vec3::new(x, -z, y)
                     } else {
-                        T::from(z).unwrap()
-                    },
-                ));
+                        Vec3::new(x, y, z)
+                    }
+                })
+                .collect(),
+        )
+    };
+
+    let mut mesh = Mesh::new();
+    mesh.add_vertices(
+        &columns(["x", "y", "z"]).ok_or("PLY vertices have no x, y and z properties.")?,
+    );
+
+    if let Some(faces) = elements.iter().find(|element| element.name == "face") {
+        let polygons = faces
+            .list("vertex_indices")
+            .or_else(|| faces.list("vertex_index"))
+            .ok_or("PLY faces have no vertex_indices property.")?;
+        let mut triangles = Vec::with_capacity(polygons.len());
+        for (index, polygon) in polygons.iter().enumerate() {
+            if polygon.iter().any(|&i| i < 0.0) {
+                return Err(format!("Negative vertex index in PLY face {}.", index).into());
             }
-            "f" => {
-                // Parse face indices
-                if parts.len() != 4 {
-                    return Err("Invalid face format".into());
-                }
-                let mut face: [usize; 3] = [0; 3];
-                for i in 0..3 {
-                    let indices: Vec<&str> = parts[i + 1].split("/").collect();
-                    let index: usize = indices[0].parse().unwrap();
-                    face[i] = index - 1;
-                }
-                faces.push(face);
+            let polygon: Vec<usize> = polygon.iter().map(|&i| i as usize).collect();
+            if polygon.len() < 3 || polygon.iter().any(|&i| i >= mesh.num_vertices()) {
+                return Err(format!("Invalid PLY face {}.", index).into());
             }
-            "n" => {
-                if read_normals {
-                    if parts.len() != 4 {
-                        return Err(
-                            "Invalid vertex format. Make sure file is triangulated.,".into()
-                        );
-                    }
-                    let n_x: f32 = parts[1].parse()?;
-                    let n_y: f32 = parts[2].parse()?;
-                    let n_z: f32 = parts[3].parse()?;
-                    normals.push(Vec3::new(
-                        T::from(n_x).unwrap(),
-                        T::from(n_y).unwrap(),
-                        T::from(n_z).unwrap(),
-                    ));
-                }
+            for i in 1..polygon.len() - 1 {
+                triangles.push([polygon[0], polygon[i], polygon[i + 1]]);
             }
-            _ => continue,
         }
+        mesh.add_faces(&triangles);
     }
 
-    mesh.add_vertices(&vertices);
-    mesh.add_faces(&faces);
-    if read_normals {
-        mesh.set_normals(&normals);
-    } else {
-        mesh.compute_vertex_normals();
+    match columns(["nx", "ny", "nz"]) {
+        Some(normals) => {
+            mesh.set_normals(&normals);
+        }
+        None if mesh.num_faces() > 0 => mesh.compute_vertex_normals(),
+        None => (),
     }
 
+    let properties = vertices
+        .properties
+        .iter()
+        .filter(|(name, _)| !PLY_GEOMETRY_PROPERTIES.contains(&name.as_str()))
+        .filter_map(|(name, values)| match values {
+            PlyValues::Scalar(values) => Some(VertexProperty::new(
+                name,
+                values.iter().map(|&v| T::from(v).unwrap()).collect(),
+            )),
+            PlyValues::List(_) => None,
+        })
+        .collect();
+
     log::info!(
-        "Obj file {} with {} vertices and {} faces successfully read.",
+        "Ply file {} with {} vertices and {} faces successfully read.",
         file_path,
         mesh.num_vertices(),
         mesh.num_faces()
     );
 
-    Ok(mesh)
+    Ok((mesh, properties))
 }
 
-/// Write a field to a .csv file.
+/// Encoding of a glTF file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GltfFormat {
+    /// A `.gltf` json file, with the binary buffer embedded as base64 data.
+    Embedded,
+    /// A single binary `.glb` file.
+    Binary,
+}
+
+/// Write meshes to a glTF 2.0 file, with one named node per mesh.
 ///
-/// This will create a csv with the columns *{x, y, z, v}* where
-/// - `x` is the x coordinate of the data point
-/// - `y` is the y coordinate of the data point
-/// - `z` is the z coordinate of the data point
-/// - `v` is value of the field at the data point
+/// Vertex normals are written if the mesh has them. glTF uses meters and y as up-direction,
+/// so the mesh nodes are placed under a root node which scales the given units to meters and
+/// rotates the model -90° around the x-axis, mapping the z-up coordinates of the mesh to y-up.
 ///
 /// # Arguments
 ///
-/// * `field` - Field to export.
-/// * `file_name` - Name of the target file to be created, without .csv extension.
-pub fn write_field_csv<T, F>(field: &F, file_name: &str) -> io::Result<()>
-where
-    T: Float + Display,
-    F: PointIterator<T> + ValueIterator<T>,
-{
-    let file_path = Path::new(file_name).with_extension("csv");
-    let mut file = fs::File::create(file_path)?;
-
-    // Write header
-    file.write_all(b"x, y, z, v\n")?;
-
-    // Write all points using the iterator
-    for (point, value) in field.iter_points().zip(field.iter_values()) {
-        writeln!(file, "{}, {}, {}, {}", point.x, point.y, point.z, value)?;
-    }
+/// * `objects` - Names and meshes of the objects to export.
+/// * `units` - Unit of the mesh coordinates.
+/// * `file_name` - Name of the target file to be created, without extension.
+/// * `format` - Option to write a `.gltf` file or a `.glb` file.
+pub fn write_gltf_file<T: Float>(
+    objects: &[(&str, &Mesh<T>)],
+    units: MeshUnits,
+    file_name: &str,
+    format: GltfFormat,
+) -> io::Result<()> {
+    let (extension, binary) = match format {
+        GltfFormat::Embedded => ("gltf", false),
+        GltfFormat::Binary => ("glb", true),
+    };
+    let scale = units.to_millimeters() / 1000.0;
+    let file_path = Path::new(file_name).with_extension(extension);
+    fs::write(&file_path, write_gltf(objects, scale, binary))?;
 
-    log::info!("Field data written to {}", file_name.to_owned() + ".csv");
+    log::info!(
+        "{} file with {} objects written as {}",
+        extension,
+        objects.len(),
+        file_path.display()
+    );
 
     Ok(())
 }
 
-/// Write an imlet model to a text file as json.
-#[cfg(feature = "serde")]
-pub fn write_model_to_file<T: ModelFloat>(
-    model: &ImplicitModel<T>,
+/// Write meshes to a .3mf file, with one named object per mesh.
+///
+/// 3MF stores the units of the model, and expects closed meshes with outward facing triangles.
+/// If a mesh has vertex normals, they are used to orient the triangles.
+///
+/// # Arguments
+///
+/// * `objects` - Names and meshes of the objects to export.
+/// * `units` - Unit of the mesh coordinates.
+/// * `file_name` - Name of the target file to be created, without .3mf extension.
+pub fn write_3mf_file<T: Float>(
+    objects: &[(&str, &Mesh<T>)],
+    units: MeshUnits,
     file_name: &str,
 ) -> io::Result<()> {
-    let json = serde_json::ser::to_string_pretty(&model)?;
-    let file_path = Path::new(file_name).with_extension("json");
-    let mut file = fs::File::create(file_path)?;
-    file.write_all(json.as_bytes())?;
+    let file_path = Path::new(file_name).with_extension("3mf");
+    fs::write(&file_path, write_3mf(objects, units))?;
 
     log::info!(
-        "Model with {} componets successfully saved as {}.",
-        model.all_components().len(),
-        file_name.to_owned() + ".json"
+        "3mf file with {} objects written as {}",
+        objects.len(),
+        file_path.display()
     );
 
     Ok(())
 }
 
-/// Deserialize an imlet model from a json file.
-///
-/// # Arguments
+use std::fs::File;
+
+pub use super::obj::ObjError;
+
+/// Named part of an .obj file, from an `o` or `g` statement.
+#[derive(Debug, Clone)]
+pub struct ObjGroup<T> {
+    /// Name of the object or group. Faces before the first statement are in a group called `default`.
+    pub name: String,
+    /// Mesh with the faces of the group and only the vertices they use.
+    pub mesh: Mesh<T>,
+    /// Texture coordinates for each vertex of the mesh, if the faces reference any.
+    pub texture_coordinates: Option<Vec<Vec2<T>>>,
+}
+
+/// Read a mesh from an .obj file.
 ///
-/// * `file_name` - Name of the file to read with the `.json` extension.
+/// Polygons are triangulated as fans around their first vertex, which is correct for convex polygons.
+/// Relative (negative) indices are supported, and all groups are merged into one mesh.
 ///
-/// # Returns
+/// # Arguments
 ///
-/// An error is something went wrong, such as if the file can't be found or the deserialization failed.
+/// * `file_path` - Relative path to the file.
+/// * `flip_yz` - Option to flip the y and z directions. Imlet uses z as up-direction so if the mesh has y, you may want to flip it.
+/// * `read_normals` - Option to read the `vn` normals referenced by the faces. If not, or if the file has no normals, smooth vertex normals will be calculated automatically. Using `false` is advised if the mesh is to be used as a distance field.
+pub fn parse_obj_file<T: Float>(
+    file_path: &str,
+    flip_yz: bool,
+    read_normals: bool,
+) -> Result<Mesh<T>, ObjError> {
+    let content = read_obj_content(file_path)?;
+    let triangles: Vec<&[ObjCorner; 3]> = content
+        .groups
+        .iter()
+        .flat_map(|group| group.triangles.iter())
+        .collect();
+    let (mesh, _) = build_obj_mesh(&content, &triangles, false, flip_yz, read_normals);
+
+    log::info!(
+        "Obj file {} with {} vertices and {} faces successfully read.",
+        file_path,
+        mesh.num_vertices(),
+        mesh.num_faces()
+    );
+
+    Ok(mesh)
+}
+
+/// Read the objects and groups of an .obj file as separate meshes.
 ///
-/// Returns Ok() with the model if the read was successful.
-#[cfg(feature = "serde")]
-pub fn read_model_from_file<T: ModelFloat + 'static + DeserializeOwned>(
+/// See [`parse_obj_file`] for the arguments.
+pub fn parse_obj_groups<T: Float>(
     file_path: &str,
-) -> Result<ImplicitModel<T>, Box<dyn std::error::Error>> {
-    use std::io::Read;
+    flip_yz: bool,
+    read_normals: bool,
+) -> Result<Vec<ObjGroup<T>>, ObjError> {
+    let content = read_obj_content(file_path)?;
+    let groups: Vec<ObjGroup<T>> = content
+        .groups
+        .iter()
+        .map(|group| {
+            let triangles: Vec<&[ObjCorner; 3]> = group.triangles.iter().collect();
+            let (mesh, texture_coordinates) =
+                build_obj_mesh(&content, &triangles, true, flip_yz, read_normals);
+            ObjGroup {
+                name: group.name.clone(),
+                mesh,
+                texture_coordinates,
+            }
+        })
+        .collect();
+
+    log::info!(
+        "Obj file {} with {} groups successfully read.",
+        file_path,
+        groups.len()
+    );
+
+    Ok(groups)
+}
 
+fn read_obj_content(file_path: &str) -> Result<ObjContent, ObjError> {
     let path = Path::new(file_path);
+    if !path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("obj"))
+    {
+        return Err(ObjError::UnsupportedExtension(file_path.to_string()));
+    }
+    read_obj(io::BufReader::new(File::open(path)?))
+}
 
-    let extension = path.extension().ok_or_else(|| {
-        format!("Cannot read file {file_path}. No valid extension provided. Should be .json.")
-    })?;
+/// Create a mesh from triangles of an .obj file.
+///
+/// Normals and texture coordinates are stored per face corner in the file, but per vertex in the mesh,
+/// so the last value referenced for a vertex is used.
+fn build_obj_mesh<T: Float>(
+    content: &ObjContent,
+    triangles: &[&[ObjCorner; 3]],
+    compact: bool,
+    flip_yz: bool,
+    read_normals: bool,
+) -> (Mesh<T>, Option<Vec<Vec2<T>>>) {
+    let to_vec3 = |v: &[f32; 3]| {
+        let [x, y, z] = v.map(|value| T::from(value).unwrap());
+        if flip_yz {
+            Vec3::new(x, -z, y)
+        } else {
+            Vec3::new(x, y, z)
+        }
+    };
 
-    if !extension.eq_ignore_ascii_case("json") {
-        return Err(
-            format!("Cannot read file {file_path}. Only .json files are supported.").into(),
-        );
+    // Map from indices in the file to indices in the mesh.
+    let mut vertex_map: Vec<Option<usize>> = vec![None; content.vertices.len()];
+    let mut vertices = Vec::new();
+    if !compact {
+        vertices = content.vertices.iter().map(to_vec3).collect();
+        vertex_map = (0..content.vertices.len()).map(Some).collect();
     }
+    let mut faces = Vec::with_capacity(triangles.len());
+    let mut normals: Vec<Option<Vec3<T>>> = vec![None; vertices.len()];
+    let mut texture_coordinates: Vec<Option<Vec2<T>>> = vec![None; vertices.len()];
 
-    let mut file = File::open(path)?;
+    for triangle in triangles {
+        let mut face = [0; 3];
+        for (index, corner) in face.iter_mut().zip(triangle.iter()) {
+            *index = *vertex_map[corner.vertex].get_or_insert_with(|| {
+                vertices.push(to_vec3(&content.vertices[corner.vertex]));
+                normals.push(None);
+                texture_coordinates.push(None);
+                vertices.len() - 1
+            });
+            if let Some(normal) = corner.normal {
+                normals[*index] = Some(to_vec3(&content.normals[normal]));
+            }
+            if let Some(texture) = corner.texture {
+                let [u, v] = content.texture_coordinates[texture].map(|t| T::from(t).unwrap());
+                texture_coordinates[*index] = Some(Vec2::new(u, v));
+            }
+        }
+        faces.push(face);
+    }
 
-    let mut data: Vec<u8> = Vec::new();
-    file.read_to_end(&mut data)?;
+    let mut mesh = Mesh::new();
+    mesh.add_vertices(&vertices);
+    mesh.add_faces(&faces);
+    mesh.compute_vertex_normals();
+    if read_normals && normals.iter().any(Option::is_some) {
+        let computed = mesh.normals().unwrap_or_default().to_vec();
+        let normals: Vec<Vec3<T>> = normals
+            .iter()
+            .zip(computed)
+            .map(|(normal, computed)| normal.unwrap_or(computed))
+            .collect();
+        mesh.set_normals(&normals);
+    }
 
-    let deserialized: ImplicitModel<T> = serde_json::de::from_slice(&data)?;
+    let texture_coordinates = texture_coordinates.iter().any(Option::is_some).then(|| {
+        texture_coordinates
+            .iter()
+            .map(|t| t.unwrap_or(Vec2::new(T::zero(), T::zero())))
+            .collect()
+    });
+    (mesh, texture_coordinates)
+}
 
-    Ok(deserialized)
+/// Nodes, node radii and edges of a tube network.
+pub type TubeNetworkData<T> = (Vec<Vec3<T>>, Vec<T>, Vec<[usize; 2]>);
+
+/// Read a network of nodes and edges with a radius per node, as used by [`TubeNetwork`](crate::types::computation::functions::TubeNetwork).
+///
+/// Files with a `.json` extension are read as an object with a list of nodes as `[x, y, z, radius]` and an optional list of edges as pairs of node indices.
+/// This requires the `serde` feature.
+///
+/// ```json
+/// { "nodes": [[0, 0, 0, 2], [10, 0, 0, 1]], "edges": [[0, 1]] }
+/// ```
+///
+/// Other files are read as text, with one node or edge per line and `#` for comments.
+///
+/// ```text
+/// # n x y z radius
+/// n 0 0 0 2
+/// n 10 0 0 1
+/// # e start end
+/// e 0 1
+/// ```
+///
+/// Node indices start at zero. If the file has no edges, the nodes are connected in order as a polyline.
+///
+/// # Arguments
+///
+/// * `file_path` - Relative path to the file.
+pub fn parse_tube_network_file<T: Float>(
+    file_path: &str,
+) -> Result<TubeNetworkData<T>, Box<dyn std::error::Error>> {
+    let path = Path::new(file_path);
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+    let (nodes, radii, mut edges) = if is_json {
+        parse_tube_network_json(&fs::read_to_string(path)?)?
+    } else {
+        parse_tube_network_text(File::open(path)?)?
+    };
+
+    if edges.is_empty() {
+        edges = (1..nodes.len()).map(|i| [i - 1, i]).collect();
+    }
+
+    log::info!(
+        "Tube network {} with {} nodes and {} edges successfully read.",
+        file_path,
+        nodes.len(),
+        edges.len()
+    );
+
+    Ok((nodes, radii, edges))
 }
 
-#[cfg(test)]
-#[cfg(feature = "serde")]
+fn parse_tube_network_text<T: Float>(
+    file: File,
+) -> Result<TubeNetworkData<T>, Box<dyn std::error::Error>> {
+    let mut nodes = Vec::new();
+    let mut radii = Vec::new();
+    let mut edges = Vec::new();
+
+    for (line_index, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.first() {
+            None => continue,
+            Some(&"n") => {
+                if parts.len() != 5 {
+                    return Err(format!(
+                        "Invalid node on line {}. Expected n x y z radius.",
+                        line_index + 1
+                    )
+                    .into());
+                }
+                let values = parts[1..]
+                    .iter()
+                    .map(|part| part.parse::<f64>())
+                    .collect::<Result<Vec<f64>, _>>()
+                    .map_err(|err| format!("Invalid node on line {}: {}", line_index + 1, err))?;
+                nodes.push(Vec3::new(
+                    T::from(values[0]).unwrap(),
+                    T::from(values[1]).unwrap(),
+                    T::from(values[2]).unwrap(),
+                ));
+                radii.push(T::from(values[3]).unwrap());
+            }
+            Some(&"e") => {
+                if parts.len() != 3 {
+                    return Err(format!(
+                        "Invalid edge on line {}. Expected e start end.",
+                        line_index + 1
+                    )
+                    .into());
+                }
+                let start: usize = parts[1]
+                    .parse()
+                    .map_err(|err| format!("Invalid edge on line {}: {}", line_index + 1, err))?;
+                let end: usize = parts[2]
+                    .parse()
+                    .map_err(|err| format!("Invalid edge on line {}: {}", line_index + 1, err))?;
+                edges.push([start, end]);
+            }
+            Some(part) if part.starts_with('#') => continue,
+            Some(part) => {
+                return Err(format!(
+                    "Unknown entry {} on line {}. Expected n or e.",
+                    part,
+                    line_index + 1
+                )
+                .into())
+            }
+        }
+    }
+
+    Ok((nodes, radii, edges))
+}
+
+#[cfg(feature = "serde")]
+fn parse_tube_network_json<T: Float>(
+    json: &str,
+) -> Result<TubeNetworkData<T>, Box<dyn std::error::Error>> {
+    #[derive(serde::Deserialize)]
+    struct NetworkFile {
+        nodes: Vec<[f64; 4]>,
+        #[serde(default)]
+        edges: Vec<[usize; 2]>,
+    }
+
+    let network: NetworkFile = serde_json::from_str(json)?;
+    let nodes = network
+        .nodes
+        .iter()
+        .map(|n| {
+            Vec3::new(
+                T::from(n[0]).unwrap(),
+                T::from(n[1]).unwrap(),
+                T::from(n[2]).unwrap(),
+            )
+        })
+        .collect();
+    let radii = network
+        .nodes
+        .iter()
+        .map(|n| T::from(n[3]).unwrap())
+        .collect();
+
+    Ok((nodes, radii, network.edges))
+}
+
+#[cfg(not(feature = "serde"))]
+fn parse_tube_network_json<T: Float>(
+    _json: &str,
+) -> Result<TubeNetworkData<T>, Box<dyn std::error::Error>> {
+    Err("Reading tube networks from .json files requires the serde feature.".into())
+}
+
+/// Points and optional normals of a point cloud.
+pub type PointCloudData<T> = (Vec<Vec3<T>>, Option<Vec<Vec3<T>>>);
+
+/// Read a point cloud with optional normals from an .xyz or .ply file.
+///
+/// Files with a `.ply` extension are read from the `x`, `y` and `z` vertex properties, and the `nx`, `ny` and `nz` properties if present.
+/// Ascii and binary PLY files are supported.
+///
+/// Other files are read as text, with one point per line as `x y z` or `x y z nx ny nz`, separated by spaces or commas.
+/// Empty lines and lines starting with `#` are skipped. Either all or none of the points should have normals.
+///
+/// # Arguments
+///
+/// * `file_path` - Relative path to the file.
+pub fn parse_point_cloud_file<T: Float>(
+    file_path: &str,
+) -> Result<PointCloudData<T>, Box<dyn std::error::Error>> {
+    let path = Path::new(file_path);
+    let is_ply = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("ply"));
+
+    let file = File::open(path)?;
+    let (points, normals) = if is_ply {
+        parse_point_cloud_ply(io::BufReader::new(file))?
+    } else {
+        parse_point_cloud_xyz(file)?
+    };
+
+    log::info!(
+        "Point cloud {} with {} points successfully read.",
+        file_path,
+        points.len()
+    );
+
+    Ok((points, normals))
+}
+
+fn parse_point_cloud_xyz<T: Float>(
+    file: File,
+) -> Result<PointCloudData<T>, Box<dyn std::error::Error>> {
+    let mut points = Vec::new();
+    let mut normals = Vec::new();
+
+    for (line_index, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let values = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|err| format!("Invalid point on line {}: {}", line_index + 1, err))?;
+        let to_vec3 = |v: &[f64]| {
+            Vec3::new(
+                T::from(v[0]).unwrap(),
+                T::from(v[1]).unwrap(),
+                T::from(v[2]).unwrap(),
+            )
+        };
+
+        let has_normal = match values.len() {
+            3 => false,
+            6 => true,
+            _ => {
+                return Err(format!(
+                    "Invalid point on line {}. Expected x y z or x y z nx ny nz.",
+                    line_index + 1
+                )
+                .into())
+            }
+        };
+        if !points.is_empty() && has_normal != (normals.len() == points.len()) {
+            return Err(format!(
+                "Invalid point on line {}. Either all or none of the points should have normals.",
+                line_index + 1
+            )
+            .into());
+        }
+
+        points.push(to_vec3(&values[..3]));
+        if has_normal {
+            normals.push(to_vec3(&values[3..]));
+        }
+    }
+
+    let normals = (!normals.is_empty()).then_some(normals);
+    Ok((points, normals))
+}
+
+fn parse_point_cloud_ply<T: Float, R: BufRead>(
+    reader: R,
+) -> Result<PointCloudData<T>, Box<dyn std::error::Error>> {
+    let elements = read_ply(reader)?;
+    let vertices = elements
+        .iter()
+        .find(|element| element.name == "vertex")
+        .ok_or("PLY file has no vertex element.")?;
+
+    let columns = |names: [&str; 3]| -> Option<Vec<Vec3<T>>> {
+        let [x, y, z] = names.map(|name| vertices.scalar(name));
+        let (x, y, z) = (x?, y?, z?);
+        Some(
+            (0..vertices.count)
+                .map(|i| {
+                    Vec3::new(
+                        T::from(x[i]).unwrap(),
+                        T::from(y[i]).unwrap(),
+                        T::from(z[i]).unwrap(),
+                    )
+                })
+                .collect(),
+        )
+    };
+
+    let points = columns(["x", "y", "z"]).ok_or("PLY vertices have no x, y and z properties.")?;
+    Ok((points, columns(["nx", "ny", "nz"])))
+}
+
+/// Write a field to a .csv file.
+///
+/// This will create a csv with the columns *{x, y, z, v}* where
+/// - `x` is the x coordinate of the data point
+/// - `y` is the y coordinate of the data point
+/// - `z` is the z coordinate of the data point
+/// - `v` is value of the field at the data point
+///
+/// # Arguments
+///
+/// * `field` - Field to export.
+/// * `file_name` - Name of the target file to be created, without .csv extension.
+pub fn write_field_csv<T, F>(field: &F, file_name: &str) -> io::Result<()>
+where
+    T: Float + Display,
+    F: PointIterator<T> + ValueIterator<T>,
+{
+    let file_path = Path::new(file_name).with_extension("csv");
+    let mut file = fs::File::create(file_path)?;
+
+    // Write header
+    file.write_all(b"x, y, z, v\n")?;
+
+    // Write all points using the iterator
+    for (point, value) in field.iter_points().zip(field.iter_values()) {
+        writeln!(file, "{}, {}, {}, {}", point.x, point.y, point.z, value)?;
+    }
+
+    log::info!("Field data written to {}", file_name.to_owned() + ".csv");
+
+    Ok(())
+}
+
+/// Format of a VTK file for a [`DenseField`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VtkFormat {
+    /// Legacy `.vtk` structured points, with binary data.
+    Legacy,
+    /// XML `.vti` image data, with base64 encoded binary data.
+    Xml,
+}
+
+/// Format of a VTK file for a [`SparseField`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SparseVtkFormat {
+    /// Legacy `.vtk` unstructured grid, with one voxel per cell of the leaf blocks.
+    Unstructured,
+    /// XML `.vtm` multiblock data set, with one `.vti` file per leaf block in a directory next to it.
+    Multiblock,
+}
+
+fn dense_block<T: Float>(field: &DenseField<T>) -> VolumeBlock<'_, T> {
+    let spacing = field.cell_size();
+    VolumeBlock {
+        origin: *field.origin(),
+        spacing: Vec3::new(spacing, spacing, spacing),
+        dims: field.num_points_per_axis().into(),
+        values: field.data(),
+    }
+}
+
+/// Write a dense field to a VTK file, to inspect it in tools like ParaView.
+///
+/// The values are written as 32 bit floats in a point data array called `value`.
+///
+/// # Arguments
+///
+/// * `field` - Field to export.
+/// * `file_name` - Name of the target file to be created, without extension.
+/// * `format` - Option to write a legacy `.vtk` file or an XML `.vti` file.
+pub fn write_field_vtk<T: Float>(
+    field: &DenseField<T>,
+    file_name: &str,
+    format: VtkFormat,
+) -> io::Result<()> {
+    let block = dense_block(field);
+    let (extension, data) = match format {
+        VtkFormat::Legacy => ("vtk", block_to_vtk_legacy(&block)),
+        VtkFormat::Xml => ("vti", block_to_vti(&block)),
+    };
+    let file_path = Path::new(file_name).with_extension(extension);
+    fs::write(&file_path, data)?;
+
+    log::info!(
+        "Field with {} points written as {}",
+        field.num_points(),
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Write a dense field to a .nrrd file, with the values as raw 32 bit floats.
+///
+/// # Arguments
+///
+/// * `field` - Field to export.
+/// * `file_name` - Name of the target file to be created, without .nrrd extension.
+pub fn write_field_nrrd<T: Float>(field: &DenseField<T>, file_name: &str) -> io::Result<()> {
+    let file_path = Path::new(file_name).with_extension("nrrd");
+    fs::write(&file_path, block_to_nrrd(&dense_block(field)))?;
+
+    log::info!(
+        "Field with {} points written as {}",
+        field.num_points(),
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Write the leaf blocks of a sparse field to VTK files, to inspect it in tools like ParaView.
+///
+/// Only the dense leaf blocks are written. Regions with constant values are skipped.
+///
+/// # Arguments
+///
+/// * `field` - Field to export.
+/// * `file_name` - Name of the target file to be created, without extension.
+/// * `format` - Option to write an unstructured grid or a multiblock data set.
+pub fn write_sparse_field_vtk<T: Float>(
+    field: &SparseField<T>,
+    file_name: &str,
+    format: SparseVtkFormat,
+) -> io::Result<()> {
+    let blocks: Vec<VolumeBlock<T>> = field
+        .leaf_blocks()
+        .into_iter()
+        .map(|(bounds, values)| VolumeBlock::from_cube(bounds, values))
+        .collect();
+
+    let file_path = match format {
+        SparseVtkFormat::Unstructured => {
+            let file_path = Path::new(file_name).with_extension("vtk");
+            fs::write(&file_path, blocks_to_vtk_unstructured(&blocks))?;
+            file_path
+        }
+        SparseVtkFormat::Multiblock => {
+            let file_path = Path::new(file_name).with_extension("vtm");
+            let stem = file_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("field");
+            let directory_name = format!("{}_blocks", stem);
+            let directory = file_path.with_file_name(&directory_name);
+            fs::create_dir_all(&directory)?;
+
+            let mut block_files = Vec::with_capacity(blocks.len());
+            for (index, block) in blocks.iter().enumerate() {
+                let block_name = format!("block_{}.vti", index);
+                fs::write(directory.join(&block_name), block_to_vti(block))?;
+                block_files.push(format!("{}/{}", directory_name, block_name));
+            }
+            fs::write(&file_path, vtm_index(&block_files))?;
+            file_path
+        }
+    };
+
+    log::info!(
+        "Sparse field with {} leaf blocks written as {}",
+        blocks.len(),
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Save a sampled dense field to a binary `.field` file.
+///
+/// The field can be restored with [`read_dense_field_file`], which avoids sampling the model again.
+///
+/// # Arguments
+///
+/// * `field` - The field to save.
+/// * `file_name` - The path and name of the file, without extension.
+pub fn write_dense_field_file<T: Float>(field: &DenseField<T>, file_name: &str) -> io::Result<()> {
+    let file_path = Path::new(file_name).with_extension("field");
+    fs::write(&file_path, field.to_bytes())?;
+
+    log::info!(
+        "Field with {} points saved as {}",
+        field.num_points(),
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Load a dense field from a binary file written by [`write_dense_field_file`].
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the file.
+pub fn read_dense_field_file<T: Float>(file_path: &str) -> io::Result<DenseField<T>> {
+    DenseField::from_bytes(&fs::read(file_path)?)
+}
+
+/// Save a sampled sparse field, including its config and node tree, to a binary `.field` file.
+///
+/// The field can be restored with [`read_sparse_field_file`], which avoids sampling the model again.
+///
+/// # Arguments
+///
+/// * `field` - The field to save.
+/// * `file_name` - The path and name of the file, without extension.
+pub fn write_sparse_field_file<T: Float>(
+    field: &SparseField<T>,
+    file_name: &str,
+) -> io::Result<()> {
+    let file_path = Path::new(file_name).with_extension("field");
+    fs::write(&file_path, field.to_bytes())?;
+
+    log::info!(
+        "Sparse field with {} leaf blocks saved as {}",
+        field.leaf_blocks().len(),
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Load a sparse field from a binary file written by [`write_sparse_field_file`].
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the file.
+pub fn read_sparse_field_file<T: Float>(file_path: &str) -> io::Result<SparseField<T>> {
+    SparseField::from_bytes(&fs::read(file_path)?)
+}
+
+/// Write each layer of a sliced model to an SVG image, named `<file_name>_<index>.svg`.
+///
+/// All images share the same extents, so the layers stay aligned. Sizes are in millimeters.
+///
+/// # Arguments
+///
+/// * `layers` - The layers, e.g. from [`slice_model`](crate::algorithms::slicing::slice_model).
+/// * `file_name` - The path and name of the files, without index and extension.
+pub fn write_slices_svg<T: Float>(layers: &[SliceLayer<T>], file_name: &str) -> io::Result<()> {
+    let extents = contour_extents(layers);
+    for (index, layer) in layers.iter().enumerate() {
+        let file_path = Path::new(&format!("{}_{:04}", file_name, index)).with_extension("svg");
+        fs::write(file_path, layer_to_svg(layer, extents))?;
+    }
+
+    log::info!("{} layers written as {}_*.svg", layers.len(), file_name);
+
+    Ok(())
+}
+
+/// Write the layers of a sliced model to a single ascii `.cli` file, in the Common Layer Interface format.
+///
+/// Coordinates are in millimeters. Outer contours have the direction 1 and holes the direction 0.
+///
+/// # Arguments
+///
+/// * `layers` - The layers, e.g. from [`slice_model`](crate::algorithms::slicing::slice_model).
+/// * `file_name` - The path and name of the file, without extension.
+pub fn write_slices_cli<T: Float>(layers: &[SliceLayer<T>], file_name: &str) -> io::Result<()> {
+    let file_path = Path::new(file_name).with_extension("cli");
+    fs::write(&file_path, layers_to_cli(layers))?;
+
+    log::info!("{} layers written as {}", layers.len(), file_path.display());
+
+    Ok(())
+}
+
+/// Write a section of a model as a binary PPM image, with one pixel per sampled point.
+///
+/// Sample the section with [`PlaneSection::sample`], which takes the plane, the extent of the image in
+/// plane coordinates and the pixel size. The first in-plane axis of the section points to the right and
+/// the second one up.
+///
+/// # Arguments
+///
+/// * `section` - The sampled section.
+/// * `style` - The colour map and overlays of the image.
+/// * `file_name` - The path and name of the file, without extension.
+pub fn write_section_ppm<T: Float>(
+    section: &PlaneSection<T>,
+    style: &SectionImageStyle<T>,
+    file_name: &str,
+) -> io::Result<()> {
+    let file_path = Path::new(file_name).with_extension("ppm");
+    let image = render_section(section, style);
+    fs::write(&file_path, to_ppm(&image))?;
+
+    log::info!(
+        "Section image with {}x{} pixels written to {}",
+        image.width,
+        image.height,
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Write a section of a model as a PNG image, with one pixel per sampled point.
+///
+/// The image is written without compression. See [`write_section_ppm`] for the layout of the image.
+///
+/// # Arguments
+///
+/// * `section` - The sampled section.
+/// * `style` - The colour map and overlays of the image.
+/// * `file_name` - The path and name of the file, without extension.
+pub fn write_section_png<T: Float>(
+    section: &PlaneSection<T>,
+    style: &SectionImageStyle<T>,
+    file_name: &str,
+) -> io::Result<()> {
+    let file_path = Path::new(file_name).with_extension("png");
+    let image = render_section(section, style);
+    fs::write(&file_path, to_png(&image))?;
+
+    log::info!(
+        "Section image with {}x{} pixels written to {}",
+        image.width,
+        image.height,
+        file_path.display()
+    );
+
+    Ok(())
+}
+
+/// Sample a section of a model in a plane and write it as a binary PPM image, with one pixel per sampled point.
+///
+/// The model is compiled once and the pixels are evaluated in parallel. See [`write_section_ppm`] for the layout of the image.
+///
+/// # Arguments
+///
+/// * `model` - The model to sample.
+/// * `output` - The tag of the component to evaluate.
+/// * `plane` - The plane of the section.
+/// * `extent` - Minimum and maximum of the image, in plane coordinates relative to the plane origin.
+/// * `pixel_size` - The distance between the sampled points.
+/// * `style` - The colour map and overlays of the image.
+/// * `file_name` - The path and name of the file, without extension.
+pub fn write_model_section_ppm<T: ModelFloat + 'static>(
+    model: &ImplicitModel<T>,
+    output: &str,
+    plane: &Plane<T>,
+    extent: (Vec2<T>, Vec2<T>),
+    pixel_size: T,
+    style: &SectionImageStyle<T>,
+    file_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let section = PlaneSection::sample(model, output, plane, extent.0, extent.1, pixel_size)?;
+    write_section_ppm(&section, style, file_name)?;
+    Ok(())
+}
+
+/// Sample a section of a model in a plane and write it as a PNG image, with one pixel per sampled point.
+///
+/// The model is compiled once and the pixels are evaluated in parallel. See [`write_section_ppm`] for the layout of the image.
+///
+/// # Arguments
+///
+/// * `model` - The model to sample.
+/// * `output` - The tag of the component to evaluate.
+/// * `plane` - The plane of the section.
+/// * `extent` - Minimum and maximum of the image, in plane coordinates relative to the plane origin.
+/// * `pixel_size` - The distance between the sampled points.
+/// * `style` - The colour map and overlays of the image.
+/// * `file_name` - The path and name of the file, without extension.
+pub fn write_model_section_png<T: ModelFloat + 'static>(
+    model: &ImplicitModel<T>,
+    output: &str,
+    plane: &Plane<T>,
+    extent: (Vec2<T>, Vec2<T>),
+    pixel_size: T,
+    style: &SectionImageStyle<T>,
+    file_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let section = PlaneSection::sample(model, output, plane, extent.0, extent.1, pixel_size)?;
+    write_section_png(&section, style, file_name)?;
+    Ok(())
+}
+
+/// Sample type of the voxels in a raw volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoxelType {
+    /// Unsigned 8 bit integers.
+    U8,
+    /// Unsigned 16 bit integers.
+    U16,
+    /// Signed 16 bit integers, as used for Hounsfield units in CT scans.
+    I16,
+    /// Unsigned 32 bit integers.
+    U32,
+    /// 32 bit floats.
+    F32,
+    /// 64 bit floats.
+    F64,
+}
+
+impl VoxelType {
+    /// Returns the number of bytes of each voxel.
+    pub fn size(&self) -> usize {
+        match self {
+            VoxelType::U8 => 1,
+            VoxelType::U16 | VoxelType::I16 => 2,
+            VoxelType::U32 | VoxelType::F32 => 4,
+            VoxelType::F64 => 8,
+        }
+    }
+
+    fn read(&self, bytes: &[u8]) -> f64 {
+        match self {
+            VoxelType::U8 => bytes[0] as f64,
+            VoxelType::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            VoxelType::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            VoxelType::U32 => u32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            VoxelType::F32 => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            VoxelType::F64 => f64::from_le_bytes(bytes.try_into().unwrap()),
+        }
+    }
+}
+
+/// Check that a volume has at least two points along each axis and a positive spacing along each axis.
+fn validate_volume<T: Float>(dimensions: Vec3i, spacing: Vec3<T>) -> Result<(), String> {
+    if dimensions.i < 2 || dimensions.j < 2 || dimensions.k < 2 {
+        return Err(format!(
+            "A volume needs at least 2 voxels per axis, found {}x{}x{}.",
+            dimensions.i, dimensions.j, dimensions.k
+        ));
+    }
+    if spacing.x <= T::zero() || spacing.y <= T::zero() || spacing.z <= T::zero() {
+        return Err("The voxel spacing must be positive along all axes.".to_string());
+    }
+    Ok(())
+}
+
+/// Read a raw little endian volume into a [`VolumeField`].
+///
+/// The file holds the voxels without header, ordered by x, then y, then z, which is the layout of
+/// the field. Each voxel becomes a point of the field, so the spacing is the distance between voxel centers.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the raw file.
+/// * `dimensions` - The number of voxels in x, y and z.
+/// * `voxel_type` - The sample type of the voxels.
+/// * `origin` - The position of the first voxel.
+/// * `spacing` - The distance between voxels along each axis.
+///
+/// # Returns
+///
+/// An error if the file can't be read, or if its size doesn't match the dimensions and voxel type.
+pub fn parse_raw_volume<T: ModelFloat>(
+    file_path: &str,
+    dimensions: Vec3i,
+    voxel_type: VoxelType,
+    origin: Vec3<T>,
+    spacing: Vec3<T>,
+) -> Result<VolumeField<T>, Box<dyn std::error::Error>> {
+    validate_volume(dimensions, spacing)?;
+    let data = fs::read(file_path)?;
+    let expected = dimensions.product() * voxel_type.size();
+    if data.len() != expected {
+        return Err(format!(
+            "Raw volume {} has {} bytes, expected {} for {}x{}x{} voxels of type {:?}.",
+            file_path,
+            data.len(),
+            expected,
+            dimensions.i,
+            dimensions.j,
+            dimensions.k,
+            voxel_type
+        )
+        .into());
+    }
+
+    let values = data
+        .chunks_exact(voxel_type.size())
+        .map(|bytes| T::from(voxel_type.read(bytes)).unwrap())
+        .collect();
+    let field = DenseField::from_data(origin, spacing.x, dimensions, values)?;
+
+    log::info!(
+        "Raw volume {} with {} voxels successfully read.",
+        file_path,
+        field.num_points()
+    );
+
+    Ok(VolumeField::from_field(field).with_spacing(spacing))
+}
+
+/// Read a stack of PGM images into a [`VolumeField`], with one image per z layer.
+///
+/// Ascii (`P2`) and binary (`P5`) images with 8 or 16 bit values are supported. All images must have the same size.
+/// The values are used as they are, without scaling by the maximum value of the images.
+/// The first row of each image is at the lowest y coordinate of the field.
+///
+/// # Arguments
+///
+/// * `file_paths` - The paths of the images, ordered from the lowest to the highest z coordinate.
+/// * `origin` - The position of the first pixel of the first image.
+/// * `spacing` - The distance between pixels in x and y, and between images in z.
+pub fn parse_pgm_stack<T: ModelFloat>(
+    file_paths: &[&str],
+    origin: Vec3<T>,
+    spacing: Vec3<T>,
+) -> Result<VolumeField<T>, Box<dyn std::error::Error>> {
+    let mut values = Vec::new();
+    let mut size = None;
+    for file_path in file_paths {
+        let image = read_pgm(&fs::read(file_path)?)
+            .map_err(|err| format!("Cannot read image {}: {}", file_path, err))?;
+        match size {
+            None => size = Some((image.width, image.height)),
+            Some(size) if size != (image.width, image.height) => {
+                return Err(format!(
+                    "Image {} has size {}x{}, expected {}x{}.",
+                    file_path, image.width, image.height, size.0, size.1
+                )
+                .into())
+            }
+            _ => (),
+        }
+        values.extend(image.values.iter().map(|&v| T::from(v).unwrap()));
+    }
+
+    let (width, height) = size.unwrap_or((0, 0));
+    let dimensions = Vec3i::new(width, height, file_paths.len());
+    validate_volume(dimensions, spacing)?;
+    let field = DenseField::from_data(origin, spacing.x, dimensions, values)?;
+
+    log::info!(
+        "Image stack with {} layers of {}x{} pixels successfully read.",
+        file_paths.len(),
+        width,
+        height
+    );
+
+    Ok(VolumeField::from_field(field).with_spacing(spacing))
+}
+
+/// Write an imlet model to a text file as json.
+#[cfg(feature = "serde")]
+pub fn write_model_to_file<T: ModelFloat>(
+    model: &ImplicitModel<T>,
+    file_name: &str,
+) -> io::Result<()> {
+    let json = serde_json::ser::to_string_pretty(&model)?;
+    let file_path = Path::new(file_name).with_extension("json");
+    let mut file = fs::File::create(file_path)?;
+    file.write_all(json.as_bytes())?;
+
+    log::info!(
+        "Model with {} componets successfully saved as {}.",
+        model.all_components().len(),
+        file_name.to_owned() + ".json"
+    );
+
+    Ok(())
+}
+
+/// Deserialize an imlet model from a json file.
+///
+/// # Arguments
+///
+/// * `file_name` - Name of the file to read with the `.json` extension.
+///
+/// # Returns
+///
+/// An error is something went wrong, such as if the file can't be found or the deserialization failed.
+///
+/// Returns Ok() with the model if the read was successful.
+#[cfg(feature = "serde")]
+pub fn read_model_from_file<T: ModelFloat + 'static + DeserializeOwned>(
+    file_path: &str,
+) -> Result<ImplicitModel<T>, Box<dyn std::error::Error>> {
+    use std::io::Read;
+
+    let path = Path::new(file_path);
+
+    let extension = path.extension().ok_or_else(|| {
+        format!("Cannot read file {file_path}. No valid extension provided. Should be .json.")
+    })?;
+
+    if !extension.eq_ignore_ascii_case("json") {
+        return Err(
+            format!("Cannot read file {file_path}. Only .json files are supported.").into(),
+        );
+    }
+
+    let mut file = File::open(path)?;
+
+    let mut data: Vec<u8> = Vec::new();
+    file.read_to_end(&mut data)?;
+
+    let deserialized: ImplicitModel<T> = serde_json::de::from_slice(&data)?;
+
+    Ok(deserialized)
+}
+
+#[cfg(test)]
 mod tests {
+    use crate::utils::test_helpers::temp_file_name;
+
     use super::*;
+
+    #[test]
+    fn test_obj_round_trip_with_normals() {
+        let mut mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let normals = vec![Vec3::new(0.0, 0.0, 1.0); mesh.num_vertices()];
+        mesh.set_normals(&normals);
+
+        let file_name = temp_file_name("normals");
+        write_obj_file(&mesh, &file_name).unwrap();
+        let file_path = format!("{}.obj", file_name);
+        let read = parse_obj_file::<f64>(&file_path, false, true);
+        fs::remove_file(&file_path).unwrap();
+
+        let read = read.unwrap();
+        assert_eq!(read.faces(), mesh.faces());
+        for n in read.normals().unwrap() {
+            assert!(n.distance_to_vec3(&Vec3::new(0.0, 0.0, 1.0)) < 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_obj_groups() {
+        let file_path = temp_file_name("groups.obj");
+        let data = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 5 5 5\nvt 0 0\nvt 1 0\nvt 1 1\nvn 0 0 -1\no quad\nf 1/1/1 2/2/1 3/3/1 4\no triangle\nf -1 -4 -3\n";
+        fs::write(&file_path, data).unwrap();
+        let groups = parse_obj_groups::<f64>(&file_path, false, true);
+        let mesh = parse_obj_file::<f64>(&file_path, false, false);
+        fs::remove_file(&file_path).unwrap();
+
+        let mesh = mesh.unwrap();
+        assert_eq!(mesh.num_vertices(), 5);
+        assert_eq!(mesh.faces(), &[[0, 1, 2], [0, 2, 3], [4, 1, 2]]);
+
+        let groups = groups.unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].name, "quad");
+        assert_eq!(groups[0].mesh.num_vertices(), 4);
+        assert_eq!(groups[0].mesh.num_faces(), 2);
+        let normals = groups[0].mesh.normals().unwrap();
+        assert!(normals[0].distance_to_vec3(&Vec3::new(0.0, 0.0, -1.0)) < 1E-9);
+        // The last vertex has no normal in the file, so the computed normal is used.
+        assert!(normals[3].distance_to_vec3(&Vec3::new(0.0, 0.0, 1.0)) < 1E-9);
+        let texture_coordinates = groups[0].texture_coordinates.as_ref().unwrap();
+        assert_eq!(
+            (texture_coordinates[2].x, texture_coordinates[2].y),
+            (1.0, 1.0)
+        );
+
+        assert_eq!(groups[1].name, "triangle");
+        assert_eq!(groups[1].mesh.faces(), &[[0, 1, 2]]);
+        assert!(groups[1].mesh.vertices()[0].distance_to_vec3(&Vec3::new(5.0, 5.0, 5.0)) < 1E-9);
+        assert!(groups[1].texture_coordinates.is_none());
+    }
+
+    #[test]
+    fn test_obj_errors() {
+        assert!(matches!(
+            parse_obj_file::<f64>("assets/geometry/box.stl", false, false),
+            Err(ObjError::UnsupportedExtension(_))
+        ));
+        assert!(matches!(
+            parse_obj_file::<f64>("assets/geometry/missing.obj", false, false),
+            Err(ObjError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_raw_volume_and_pgm_stack() {
+        use crate::types::computation::traits::ImplicitFunction;
+
+        let file_path = temp_file_name("volume.raw");
+        let voxels: Vec<u8> = (0..24i16).flat_map(|v| (v - 12).to_le_bytes()).collect();
+        fs::write(&file_path, &voxels).unwrap();
+        let origin = Vec3::new(1.0, 2.0, 3.0);
+        let spacing = Vec3::new(0.5, 0.5, 2.0);
+        let volume: VolumeField<f64> = parse_raw_volume(
+            &file_path,
+            (4, 3, 2).into(),
+            VoxelType::I16,
+            origin,
+            spacing,
+        )
+        .unwrap();
+        let field = volume.field().unwrap();
+        assert_eq!(field.data()[0], -12.0);
+        assert_eq!(field.data()[23], 11.0);
+        assert_eq!(field.num_cells(), 3 * 2);
+        let bounds = volume.bounds().unwrap();
+        assert!(bounds.max.distance_to_vec3(&Vec3::new(2.5, 3.0, 5.0)) < 1E-9);
+        // The second layer starts at index 12, one slice distance above the origin.
+        assert!((volume.eval(1.0, 2.0, 5.0) - 0.0).abs() < 1E-9);
+        assert!(parse_raw_volume::<f64>(
+            &file_path,
+            (4, 3, 2).into(),
+            VoxelType::F32,
+            origin,
+            spacing
+        )
+        .is_err());
+        assert!(parse_raw_volume::<f64>(
+            &file_path,
+            (4, 3, 2).into(),
+            VoxelType::I16,
+            origin,
+            Vec3::new(0.5, 0.0, 0.5)
+        )
+        .is_err());
+        fs::remove_file(&file_path).unwrap();
+
+        let first = temp_file_name("slice_0.pgm");
+        let second = temp_file_name("slice_1.pgm");
+        let mut binary = b"P5 3 2 255\n".to_vec();
+        binary.extend_from_slice(&[6, 7, 8, 9, 10, 11]);
+        fs::write(&first, b"P2\n3 2\n255\n0 1 2\n3 4 5\n").unwrap();
+        fs::write(&second, &binary).unwrap();
+        let spacing = Vec3::new(1.0, 1.0, 3.0);
+        let volume: VolumeField<f32> =
+            parse_pgm_stack(&[&first, &second], Vec3::origin(), spacing).unwrap();
+        let expected: Vec<f32> = (0..12).map(|v| v as f32).collect();
+        assert_eq!(volume.field().unwrap().data(), expected.as_slice());
+        assert!((volume.eval(2.0, 1.0, 3.0) - 11.0).abs() < 1E-6);
+
+        fs::write(&second, b"P2 2 2 255\n0 1 2 3\n").unwrap();
+        let err = parse_pgm_stack::<f32>(&[&first, &second], Vec3::origin(), spacing).unwrap_err();
+        assert!(err.to_string().contains("expected 3x2"));
+        assert!(parse_pgm_stack::<f32>(&[&first], Vec3::origin(), spacing).is_err());
+        fs::remove_file(&first).unwrap();
+        fs::remove_file(&second).unwrap();
+    }
+
+    #[test]
+    fn test_write_section_images() {
+        use crate::types::geometry::Sphere;
+
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::origin(), 4.0))
+            .unwrap();
+        let plane = Plane::new(Vec3::origin(), Vec3::new(1.0, 0.0, 1.0));
+        let section = PlaneSection::sample(
+            &model,
+            "Sphere",
+            &plane,
+            Vec2::new(-5.0, -5.0),
+            Vec2::new(5.0, 5.0),
+            0.5,
+        )
+        .unwrap();
+        let style = SectionImageStyle::default().with_iso_line_spacing(Some(1.0));
+
+        let file_name = temp_file_name("section");
+        write_section_ppm(&section, &style, &file_name).unwrap();
+        let ppm = fs::read(format!("{}.ppm", file_name)).unwrap();
+        fs::remove_file(format!("{}.ppm", file_name)).unwrap();
+        assert!(ppm.starts_with(b"P6\n21 21\n255\n"));
+        assert_eq!(ppm.len(), 13 + 21 * 21 * 3);
+
+        write_section_png(&section, &style, &file_name).unwrap();
+        let png = fs::read(format!("{}.png", file_name)).unwrap();
+        fs::remove_file(format!("{}.png", file_name)).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(b"IEND\xaeB`\x82"));
+
+        let extent = (Vec2::new(-5.0, -5.0), Vec2::new(5.0, 5.0));
+        write_model_section_ppm(&model, "Sphere", &plane, extent, 0.5, &style, &file_name).unwrap();
+        let model_ppm = fs::read(format!("{}.ppm", file_name)).unwrap();
+        fs::remove_file(format!("{}.ppm", file_name)).unwrap();
+        assert_eq!(model_ppm, ppm);
+
+        write_model_section_png(&model, "Sphere", &plane, extent, 0.5, &style, &file_name).unwrap();
+        let model_png = fs::read(format!("{}.png", file_name)).unwrap();
+        fs::remove_file(format!("{}.png", file_name)).unwrap();
+        assert_eq!(model_png, png);
+
+        assert!(write_model_section_ppm(
+            &model, "Missing", &plane, extent, 0.5, &style, &file_name
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_write_slices() {
+        use crate::{
+            algorithms::slicing::slice_model,
+            types::{
+                computation::model::ImplicitModel,
+                geometry::{BoundingBox, Sphere},
+            },
+        };
+
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::origin(), 4.0))
+            .unwrap();
+        let bounds = BoundingBox::new(Vec3::new(-5.0, -5.0, -5.0), Vec3::new(5.0, 5.0, 5.0));
+        let layers = slice_model(&model, "Sphere", &bounds, 0.5, 2.5, 0.0).unwrap();
+        assert_eq!(layers.len(), 4);
+
+        let file_name = temp_file_name("slices");
+        write_slices_svg(&layers, &file_name).unwrap();
+        for index in 0..layers.len() {
+            let file_path = format!("{}_{:04}.svg", file_name, index);
+            let svg = fs::read_to_string(&file_path).unwrap();
+            fs::remove_file(&file_path).unwrap();
+            assert_eq!(svg.matches(" Z").count(), 1);
+        }
+
+        write_slices_cli(&layers, &file_name).unwrap();
+        let cli = fs::read_to_string(format!("{}.cli", file_name)).unwrap();
+        fs::remove_file(format!("{}.cli", file_name)).unwrap();
+        assert!(cli.contains("$$LAYERS/4"));
+        // The layers are written at their top, and the dimension spans the full height of the layers.
+        let dimension: Vec<f64> = cli
+            .lines()
+            .find_map(|line| line.strip_prefix("$$DIMENSION/"))
+            .unwrap()
+            .split(',')
+            .map(|value| value.parse().unwrap())
+            .collect();
+        assert_eq!((dimension[2], dimension[5]), (-5.0, 5.0));
+        assert!(cli.contains("$$LAYER/-2.5\n"));
+        assert_eq!(cli.matches("$$POLYLINE/1,1,").count(), 4);
+    }
+
+    #[test]
+    fn test_field_file_round_trip() {
+        use crate::{
+            algorithms::marching_cubes::generate_iso_surface,
+            types::{
+                computation::{
+                    data::{
+                        sampler::{Sampler, SparseSampler},
+                        BlockSize, SparseFieldConfig,
+                    },
+                    model::ImplicitModel,
+                },
+                geometry::{BoundingBox, Sphere},
+            },
+        };
+
+        let values: Vec<f64> = (0..24).map(|i| i as f64 - 11.5).collect();
+        let field = DenseField::from_data(Vec3::origin(), 0.5, (4, 3, 2).into(), values).unwrap();
+        let file_name = temp_file_name("dense_binary");
+        write_dense_field_file(&field, &file_name).unwrap();
+        let loaded: DenseField<f64> =
+            read_dense_field_file(&format!("{}.field", file_name)).unwrap();
+        assert!(read_sparse_field_file::<f64>(&format!("{}.field", file_name)).is_err());
+        fs::remove_file(format!("{}.field", file_name)).unwrap();
+        assert_eq!(loaded.data(), field.data());
+        assert_eq!(loaded.num_cells(), field.num_cells());
+        assert_eq!(loaded.cell_size(), field.cell_size());
+        assert_eq!(
+            generate_iso_surface(&loaded, 0.0).len(),
+            generate_iso_surface(&field, 0.0).len()
+        );
+
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::new(5.0, 5.0, 5.0), 3.0))
+            .unwrap();
+        let mut sampler = SparseSampler::builder()
+            .with_bounds(BoundingBox::new(
+                Vec3::origin(),
+                Vec3::new(10.0, 10.0, 10.0),
+            ))
+            .with_config(SparseFieldConfig {
+                internal_size: BlockSize::Size4,
+                leaf_size: BlockSize::Size4,
+                cell_size: 0.5,
+            })
+            .build()
+            .unwrap();
+        sampler.sample_field(&model).unwrap();
+
+        let file_name = temp_file_name("sparse_binary");
+        write_sparse_field_file(sampler.field(), &file_name).unwrap();
+        let loaded: SparseField<f64> =
+            read_sparse_field_file(&format!("{}.field", file_name)).unwrap();
+        fs::remove_file(format!("{}.field", file_name)).unwrap();
+        assert_eq!(loaded.to_bytes(), sampler.field().to_bytes());
+        assert_eq!(loaded.n_active_nodes(), sampler.field().n_active_nodes());
+
+        let original = generate_iso_surface(sampler.field(), 0.0);
+        let reloaded = generate_iso_surface(&loaded, 0.0);
+        assert!(!original.is_empty());
+        assert_eq!(original.len(), reloaded.len());
+    }
+
+    #[test]
+    fn test_write_fields() {
+        use crate::types::{
+            computation::{
+                data::{
+                    sampler::{Sampler, SparseSampler},
+                    BlockSize, SparseFieldConfig,
+                },
+                model::ImplicitModel,
+            },
+            geometry::{BoundingBox, Sphere},
+        };
+
+        let values: Vec<f32> = (0..24).map(|i| i as f32).collect();
+        let field = DenseField::from_data(Vec3::origin(), 0.5, (4, 3, 2).into(), values).unwrap();
+        let file_name = temp_file_name("dense");
+        write_field_vtk(&field, &file_name, VtkFormat::Legacy).unwrap();
+        write_field_vtk(&field, &file_name, VtkFormat::Xml).unwrap();
+        write_field_nrrd(&field, &file_name).unwrap();
+        for extension in ["vtk", "vti", "nrrd"] {
+            let data = fs::read(format!("{}.{}", file_name, extension)).unwrap();
+            fs::remove_file(format!("{}.{}", file_name, extension)).unwrap();
+            assert!(data.len() > 24 * 4);
+        }
+
+        let mut model = ImplicitModel::new();
+        model
+            .add_function("Sphere", Sphere::new(Vec3::new(5.0, 5.0, 5.0), 3.0))
+            .unwrap();
+        let mut sampler = SparseSampler::builder()
+            .with_bounds(BoundingBox::new(
+                Vec3::origin(),
+                Vec3::new(10.0, 10.0, 10.0),
+            ))
+            .with_config(SparseFieldConfig {
+                internal_size: BlockSize::Size4,
+                leaf_size: BlockSize::Size4,
+                cell_size: 1.0,
+            })
+            .build()
+            .unwrap();
+        sampler.sample_field(&model).unwrap();
+        let num_blocks = sampler.field().leaf_blocks().len();
+        assert!(num_blocks > 0);
+
+        let file_name = temp_file_name("sparse");
+        write_sparse_field_vtk(sampler.field(), &file_name, SparseVtkFormat::Unstructured).unwrap();
+        let vtk = fs::read(format!("{}.vtk", file_name)).unwrap();
+        fs::remove_file(format!("{}.vtk", file_name)).unwrap();
+        let header = format!("POINTS {} float", num_blocks * 64);
+        assert!(String::from_utf8_lossy(&vtk).contains(&header));
+
+        write_sparse_field_vtk(sampler.field(), &file_name, SparseVtkFormat::Multiblock).unwrap();
+        let index = fs::read_to_string(format!("{}.vtm", file_name)).unwrap();
+        let directory = format!("{}_blocks", file_name);
+        let num_files = fs::read_dir(&directory).unwrap().count();
+        fs::remove_file(format!("{}.vtm", file_name)).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(num_files, num_blocks);
+        assert_eq!(index.matches("<DataSet ").count(), num_blocks);
+    }
+
+    #[test]
+    fn test_ply_round_trip() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let heights = VertexProperty::new("height", mesh.vertices().iter().map(|v| v.z).collect());
+
+        for (name, encoding) in [
+            ("box_ascii", PlyEncoding::Ascii),
+            ("box_binary", PlyEncoding::BinaryLittleEndian),
+        ] {
+            let file_name = temp_file_name(name);
+            write_ply_file(&mesh, std::slice::from_ref(&heights), &file_name, encoding).unwrap();
+            let file_path = format!("{}.ply", file_name);
+            let data = parse_ply_file::<f64>(&file_path, false);
+            fs::remove_file(&file_path).unwrap();
+
+            let (read, properties) = data.unwrap();
+            assert_eq!(read.faces(), mesh.faces());
+            for (a, b) in read.vertices().iter().zip(mesh.vertices()) {
+                assert!(a.distance_to_vec3(b) < 1E-6);
+            }
+            for (a, b) in read.normals().unwrap().iter().zip(mesh.normals().unwrap()) {
+                assert!(a.distance_to_vec3(b) < 1E-6);
+            }
+            assert_eq!(properties.len(), 1);
+            assert_eq!(properties[0].name, "height");
+            assert_eq!(properties[0].values, heights.values);
+        }
+
+        let invalid = VertexProperty::new("height", vec![0.0]);
+        assert!(write_ply_file(
+            &mesh,
+            &[invalid],
+            &temp_file_name("invalid"),
+            PlyEncoding::Ascii
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_ply_polygons() {
+        let file_path = temp_file_name("quad.ply");
+        let data = "ply\nformat ascii 1.0\nelement vertex 4\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\nelement face 1\nproperty list uchar int vertex_index\nend_header\n0 0 0 255\n1 0 0 0\n1 1 0 0\n0 1 0 10\n4 0 1 2 3\n";
+        fs::write(&file_path, data).unwrap();
+        let result = parse_ply_file::<f64>(&file_path, true);
+        fs::remove_file(&file_path).unwrap();
+
+        let (mesh, properties) = result.unwrap();
+        assert_eq!(mesh.faces(), &[[0, 1, 2], [0, 2, 3]]);
+        // The quad lies in the xz-plane after flipping, facing in negative y-direction.
+        assert!(mesh.vertices()[2].distance_to_vec3(&Vec3::new(1.0, 0.0, 1.0)) < 1E-9);
+        assert!(mesh.normals().unwrap()[0].distance_to_vec3(&Vec3::new(0.0, -1.0, 0.0)) < 1E-9);
+        assert_eq!(properties[0].name, "red");
+        assert_eq!(properties[0].values, vec![255.0, 0.0, 0.0, 10.0]);
+    }
+
+    #[test]
+    fn test_ply_negative_index() {
+        let file_path = temp_file_name("negative_index.ply");
+        let data = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n0 0 0\n1 0 0\n1 1 0\n3 0 -1 2\n";
+        fs::write(&file_path, data).unwrap();
+        let result = parse_ply_file::<f64>(&file_path, false);
+        fs::remove_file(&file_path).unwrap();
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Negative vertex index"));
+    }
+
+    #[cfg(feature = "serde")]
     #[test]
     fn test_deserialize_simple_model() {
         let model: ImplicitModel<f32> =
@@ -288,6 +1985,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn test_deserialize_model_with_file() {
         let model: ImplicitModel<f32> =
@@ -60,7 +60,7 @@ pub(crate) fn format_integer(n: usize) -> String {
     let mut s = n.to_string();
     let len = s.len();
     for i in (1..len).rev() {
-        if (len - i) % 3 == 0 {
+        if (len - i).is_multiple_of(3) {
             s.insert(i, ',');
         }
     }
@@ -0,0 +1,122 @@
+/// Grayscale image read from a PGM file.
+#[derive(Debug, Clone)]
+pub(crate) struct GrayImage {
+    pub width: usize,
+    pub height: usize,
+    /// Values row by row, starting with the top row of the image.
+    pub values: Vec<u16>,
+}
+
+/// Read an ascii (`P2`) or binary (`P5`) PGM image.
+///
+/// Binary images with a maximum value above 255 use two big endian bytes per value.
+pub(crate) fn read_pgm(data: &[u8]) -> Result<GrayImage, String> {
+    let mut position = 0;
+    let magic = next_token(data, &mut position).ok_or("Missing PGM header.")?;
+    let binary = match magic {
+        b"P2" => false,
+        b"P5" => true,
+        _ => return Err("Not a PGM image, expected P2 or P5 header.".to_string()),
+    };
+
+    let mut header = [0usize; 3];
+    for (value, name) in header.iter_mut().zip(["width", "height", "maximum value"]) {
+        *value = next_token(data, &mut position)
+            .and_then(|token| std::str::from_utf8(token).ok()?.parse().ok())
+            .ok_or_else(|| format!("Missing or invalid {} in PGM header.", name))?;
+    }
+    let [width, height, max_value] = header;
+    if width == 0 || height == 0 {
+        return Err("PGM image is empty.".to_string());
+    }
+    if max_value == 0 || max_value > u16::MAX as usize {
+        return Err(format!("Invalid PGM maximum value {}.", max_value));
+    }
+
+    let too_short = || format!("PGM data is too short for {}x{} pixels.", width, height);
+    let count = width.checked_mul(height).ok_or_else(too_short)?;
+    let values = if binary {
+        // A single whitespace character separates the header from the pixel data.
+        position += 1;
+        let bytes_per_value = if max_value > 255 { 2 } else { 1 };
+        let pixels = count
+            .checked_mul(bytes_per_value)
+            .and_then(|size| data.get(position..position.checked_add(size)?))
+            .ok_or_else(too_short)?;
+        if bytes_per_value == 2 {
+            pixels
+                .chunks_exact(2)
+                .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+                .collect()
+        } else {
+            pixels.iter().map(|&byte| byte as u16).collect()
+        }
+    } else {
+        // Each value takes at least one byte, which bounds the capacity for invalid headers.
+        let mut values = Vec::with_capacity(count.min(data.len().saturating_sub(position)));
+        for _ in 0..count {
+            let value = next_token(data, &mut position)
+                .and_then(|token| std::str::from_utf8(token).ok()?.parse().ok())
+                .ok_or_else(too_short)?;
+            values.push(value);
+        }
+        values
+    };
+
+    Ok(GrayImage {
+        width,
+        height,
+        values,
+    })
+}
+
+/// Next whitespace separated token, skipping `#` comments.
+fn next_token<'a>(data: &'a [u8], position: &mut usize) -> Option<&'a [u8]> {
+    loop {
+        while *position < data.len() && data[*position].is_ascii_whitespace() {
+            *position += 1;
+        }
+        if data.get(*position) != Some(&b'#') {
+            break;
+        }
+        while *position < data.len() && data[*position] != b'\n' {
+            *position += 1;
+        }
+    }
+    let start = *position;
+    while *position < data.len() && !data[*position].is_ascii_whitespace() {
+        *position += 1;
+    }
+    (*position > start).then(|| &data[start..*position])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_and_binary() {
+        let ascii = read_pgm(b"P2\n# comment\n3 2\n# another\n255\n0 1 2\n3 4 255\n").unwrap();
+        assert_eq!((ascii.width, ascii.height), (3, 2));
+        assert_eq!(ascii.values, vec![0, 1, 2, 3, 4, 255]);
+
+        let mut binary = b"P5 2 2 255\n".to_vec();
+        binary.extend_from_slice(&[0, 10, 32, 255]);
+        assert_eq!(read_pgm(&binary).unwrap().values, vec![0, 10, 32, 255]);
+
+        let mut wide = b"P5\n2 1\n4095\n".to_vec();
+        wide.extend_from_slice(&[0x0F, 0xFF, 0x01, 0x00]);
+        assert_eq!(read_pgm(&wide).unwrap().values, vec![4095, 256]);
+    }
+
+    #[test]
+    fn test_invalid_images() {
+        assert!(read_pgm(b"P6 1 1 255\n\0\0\0").is_err());
+        assert!(read_pgm(b"P2 2 2 255\n0 1 2").is_err());
+        assert!(read_pgm(b"P5 2 2 255\n\0\0\0").is_err());
+        assert!(read_pgm(b"P2 0 2 255\n").is_err());
+        assert!(read_pgm(b"P2 100000000000 100000000 255\n0 1").is_err());
+        assert!(read_pgm(b"P5 100000000000 100000000 255\n\0\0").is_err());
+        assert!(read_pgm(b"P5 4294967296 4294967296 255\n\0\0").is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead},
+};
+
+/// Error when reading an .obj file.
+#[derive(Debug)]
+pub enum ObjError {
+    /// The file couldn't be opened or read.
+    Io(io::Error),
+    /// The file doesn't have the .obj extension.
+    UnsupportedExtension(String),
+    /// A line couldn't be parsed.
+    InvalidLine {
+        /// Line number, starting at 1.
+        line: usize,
+        /// Description of the problem.
+        message: String,
+    },
+    /// A face references a vertex, texture coordinate or normal which doesn't exist.
+    IndexOutOfRange {
+        /// Line number, starting at 1.
+        line: usize,
+        /// Index as written in the file.
+        index: i64,
+        /// Number of elements which were defined at this line.
+        count: usize,
+    },
+}
+
+impl Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjError::Io(err) => write!(f, "Failed to read obj file: {}", err),
+            ObjError::UnsupportedExtension(path) => {
+                write!(
+                    f,
+                    "Cannot read file {}. Only .obj files are supported.",
+                    path
+                )
+            }
+            ObjError::InvalidLine { line, message } => {
+                write!(f, "Invalid obj data on line {}: {}", line, message)
+            }
+            ObjError::IndexOutOfRange { line, index, count } => write!(
+                f,
+                "Index {} on line {} is out of range, only {} elements are defined.",
+                index, line, count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ObjError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ObjError {
+    fn from(err: io::Error) -> Self {
+        ObjError::Io(err)
+    }
+}
+
+/// Corner of a face, with zero based indices into the vertex, texture coordinate and normal lists.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ObjCorner {
+    pub vertex: usize,
+    pub texture: Option<usize>,
+    pub normal: Option<usize>,
+}
+
+/// Faces following an `o` or `g` statement.
+#[derive(Debug, Clone)]
+pub(crate) struct ObjFaceGroup {
+    pub name: String,
+    /// Triangles, with polygons split as fans around their first corner.
+    pub triangles: Vec<[ObjCorner; 3]>,
+}
+
+/// Raw data of an .obj file, with values read as 32 bit floats.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ObjContent {
+    pub vertices: Vec<[f32; 3]>,
+    pub texture_coordinates: Vec<[f32; 2]>,
+    pub normals: Vec<[f32; 3]>,
+    /// Groups with at least one face, in the order of the file.
+    pub groups: Vec<ObjFaceGroup>,
+}
+
+/// Name of the group for faces before the first `o` or `g` statement.
+pub(crate) const DEFAULT_GROUP_NAME: &str = "default";
+
+/// Read the vertices, texture coordinates, normals and grouped faces of an .obj file.
+///
+/// Materials, smoothing groups, lines and points are ignored.
+pub(crate) fn read_obj<R: BufRead>(reader: R) -> Result<ObjContent, ObjError> {
+    let mut content = ObjContent::default();
+    let mut group = ObjFaceGroup {
+        name: DEFAULT_GROUP_NAME.to_string(),
+        triangles: Vec::new(),
+    };
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        let line = line.split('#').next().unwrap_or("");
+        let mut parts = line.split_whitespace();
+        let Some(keyword) = parts.next() else {
+            continue;
+        };
+        let arguments: Vec<&str> = parts.collect();
+        let invalid = |message: String| ObjError::InvalidLine {
+            line: line_number,
+            message,
+        };
+
+        match keyword {
+            "v" => content
+                .vertices
+                .push(parse_values(&arguments, 3, line_number)?),
+            "vn" => content
+                .normals
+                .push(parse_values(&arguments, 3, line_number)?),
+            "vt" => {
+                // The optional third coordinate of 3D textures is ignored.
+                content
+                    .texture_coordinates
+                    .push(parse_values(&arguments, 2, line_number)?)
+            }
+            "f" => {
+                if arguments.len() < 3 {
+                    return Err(invalid(format!(
+                        "A face needs at least 3 vertices, found {}.",
+                        arguments.len()
+                    )));
+                }
+                let corners = arguments
+                    .iter()
+                    .map(|corner| parse_corner(corner, &content, line_number))
+                    .collect::<Result<Vec<_>, _>>()?;
+                for i in 1..corners.len() - 1 {
+                    group
+                        .triangles
+                        .push([corners[0], corners[i], corners[i + 1]]);
+                }
+            }
+            "o" | "g" => {
+                let name = if arguments.is_empty() {
+                    DEFAULT_GROUP_NAME.to_string()
+                } else {
+                    arguments.join(" ")
+                };
+                let previous = std::mem::replace(
+                    &mut group,
+                    ObjFaceGroup {
+                        name,
+                        triangles: Vec::new(),
+                    },
+                );
+                if !previous.triangles.is_empty() {
+                    content.groups.push(previous);
+                }
+            }
+            _ => continue,
+        }
+    }
+
+    if !group.triangles.is_empty() {
+        content.groups.push(group);
+    }
+    Ok(content)
+}
+
+/// Parse at least `count` numbers, ignoring optional additional values such as weights or colors.
+fn parse_values<const N: usize>(
+    arguments: &[&str],
+    count: usize,
+    line: usize,
+) -> Result<[f32; N], ObjError> {
+    if arguments.len() < count {
+        return Err(ObjError::InvalidLine {
+            line,
+            message: format!("Expected {} values, found {}.", count, arguments.len()),
+        });
+    }
+    let mut values = [0.0; N];
+    for (value, argument) in values.iter_mut().zip(arguments) {
+        *value = argument.parse().map_err(|_| ObjError::InvalidLine {
+            line,
+            message: format!("{} is not a number.", argument),
+        })?;
+    }
+    Ok(values)
+}
+
+/// Parse a face corner such as `1`, `1/2`, `1//3` or `1/2/3`.
+fn parse_corner(corner: &str, content: &ObjContent, line: usize) -> Result<ObjCorner, ObjError> {
+    let mut indices = corner.split('/');
+    let vertex = indices.next().unwrap_or("");
+    let texture = indices.next().filter(|s| !s.is_empty());
+    let normal = indices.next().filter(|s| !s.is_empty());
+    if indices.next().is_some() {
+        return Err(ObjError::InvalidLine {
+            line,
+            message: format!("Invalid face corner {}.", corner),
+        });
+    }
+
+    Ok(ObjCorner {
+        vertex: resolve_index(vertex, content.vertices.len(), line)?,
+        texture: texture
+            .map(|s| resolve_index(s, content.texture_coordinates.len(), line))
+            .transpose()?,
+        normal: normal
+            .map(|s| resolve_index(s, content.normals.len(), line))
+            .transpose()?,
+    })
+}
+
+/// Convert a one based or negative relative index to a zero based index.
+fn resolve_index(index: &str, count: usize, line: usize) -> Result<usize, ObjError> {
+    let value: i64 = index.parse().map_err(|_| ObjError::InvalidLine {
+        line,
+        message: format!("{} is not a valid index.", index),
+    })?;
+    let resolved = if value > 0 {
+        value - 1
+    } else {
+        count as i64 + value
+    };
+    if value == 0 || resolved < 0 || resolved >= count as i64 {
+        return Err(ObjError::IndexOutOfRange {
+            line,
+            index: value,
+            count,
+        });
+    }
+    Ok(resolved as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polygons_and_groups() {
+        let data = "# comment\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0 1.0\nvt 0 0\nvt 1 1 0\nvn 0 0 1\no first\nf 1/1/1 2/2/1 3//1 4\ng second part\nf -4 -3 -2\n";
+        let content = read_obj(data.as_bytes()).unwrap();
+
+        assert_eq!(content.vertices.len(), 4);
+        assert_eq!(content.texture_coordinates, vec![[0.0, 0.0], [1.0, 1.0]]);
+        assert_eq!(content.normals, vec![[0.0, 0.0, 1.0]]);
+        assert_eq!(content.groups.len(), 2);
+        assert_eq!(content.groups[0].name, "first");
+        assert_eq!(content.groups[1].name, "second part");
+
+        let quad = &content.groups[0].triangles;
+        assert_eq!(quad.len(), 2);
+        let vertices = |triangle: &[ObjCorner; 3]| triangle.map(|c| c.vertex);
+        assert_eq!(vertices(&quad[0]), [0, 1, 2]);
+        assert_eq!(vertices(&quad[1]), [0, 2, 3]);
+        assert_eq!(quad[0][1].texture, Some(1));
+        assert_eq!(quad[0][2].texture, None);
+        assert_eq!(quad[0][2].normal, Some(0));
+        assert_eq!(quad[1][2].normal, None);
+        assert_eq!(vertices(&content.groups[1].triangles[0]), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_errors_with_line_numbers() {
+        let error = |data: &str| read_obj(data.as_bytes()).unwrap_err();
+
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0\n"),
+            ObjError::InvalidLine { line: 2, .. }
+        ));
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0 a\n"),
+            ObjError::InvalidLine { line: 2, .. }
+        ));
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0 0\nf 1 2\n"),
+            ObjError::InvalidLine { line: 3, .. }
+        ));
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0 0\n\nf 1 2 x\n"),
+            ObjError::InvalidLine { line: 4, .. }
+        ));
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n"),
+            ObjError::IndexOutOfRange {
+                line: 4,
+                index: 4,
+                count: 3
+            }
+        ));
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 0 1 2\n"),
+            ObjError::IndexOutOfRange { index: 0, .. }
+        ));
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -1 -2 -4\n"),
+            ObjError::IndexOutOfRange { index: -4, .. }
+        ));
+        assert!(matches!(
+            error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1/1 2 3\n"),
+            ObjError::IndexOutOfRange {
+                index: 1,
+                count: 0,
+                ..
+            }
+        ));
+        assert!(error("v 0 0 0\nv 1 0 0\nf 1 2 x\n")
+            .to_string()
+            .contains("line 3"));
+    }
+}
@@ -0,0 +1,561 @@
+use std::{error::Error, fmt::Write, io::BufRead};
+
+/// Encoding of the data section of a PLY file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// Scalar types allowed in a PLY header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PlyScalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyScalar {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::I8),
+            "uchar" | "uint8" => Some(Self::U8),
+            "short" | "int16" => Some(Self::I16),
+            "ushort" | "uint16" => Some(Self::U16),
+            "int" | "int32" => Some(Self::I32),
+            "uint" | "uint32" => Some(Self::U32),
+            "float" | "float32" => Some(Self::F32),
+            "double" | "float64" => Some(Self::F64),
+            _ => None,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+}
+
+/// Type of a property, either a single value or a list with a count prefix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PlyPropertyType {
+    Scalar(PlyScalar),
+    List { count: PlyScalar, item: PlyScalar },
+}
+
+/// Values of one property for all the items of an element.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PlyValues {
+    Scalar(Vec<f64>),
+    List(Vec<Vec<f64>>),
+}
+
+/// Data of one element, such as the vertices or the faces of a mesh.
+#[derive(Debug, Clone)]
+pub(crate) struct PlyElement {
+    pub name: String,
+    pub count: usize,
+    pub properties: Vec<(String, PlyValues)>,
+}
+
+impl PlyElement {
+    /// Values of a scalar property, if the element has it.
+    pub fn scalar(&self, name: &str) -> Option<&[f64]> {
+        self.properties
+            .iter()
+            .find_map(|(property, values)| match values {
+                PlyValues::Scalar(values) if property == name => Some(values.as_slice()),
+                _ => None,
+            })
+    }
+
+    /// Values of a list property, if the element has it.
+    pub fn list(&self, name: &str) -> Option<&[Vec<f64>]> {
+        self.properties
+            .iter()
+            .find_map(|(property, values)| match values {
+                PlyValues::List(values) if property == name => Some(values.as_slice()),
+                _ => None,
+            })
+    }
+}
+
+struct ElementHeader {
+    name: String,
+    count: usize,
+    properties: Vec<(String, PlyPropertyType)>,
+}
+
+/// Read all the elements of a PLY file.
+pub(crate) fn read_ply<R: BufRead>(mut reader: R) -> Result<Vec<PlyElement>, Box<dyn Error>> {
+    let (format, headers) = read_header(&mut reader)?;
+
+    let mut body = Vec::new();
+    reader.read_to_end(&mut body)?;
+
+    let mut source = match format {
+        PlyFormat::Ascii => ValueSource::Ascii(
+            std::str::from_utf8(&body)
+                .map_err(|_| "PLY ascii data is not valid text.")?
+                .split_whitespace(),
+        ),
+        PlyFormat::BinaryLittleEndian => ValueSource::Binary {
+            data: &body,
+            position: 0,
+            little_endian: true,
+        },
+        PlyFormat::BinaryBigEndian => ValueSource::Binary {
+            data: &body,
+            position: 0,
+            little_endian: false,
+        },
+    };
+
+    let mut elements = Vec::with_capacity(headers.len());
+    for header in headers {
+        // Check the count from the header against the data before allocating for it.
+        let min_item_size: usize = header
+            .properties
+            .iter()
+            .map(|(_, data_type)| match (format, data_type) {
+                (PlyFormat::Ascii, _) => 1,
+                (_, PlyPropertyType::Scalar(scalar)) => scalar.size(),
+                (_, PlyPropertyType::List { count, .. }) => count.size(),
+            })
+            .sum();
+        if header
+            .count
+            .checked_mul(min_item_size)
+            .is_none_or(|size| size > source.remaining(body.len()))
+        {
+            return Err(format!(
+                "PLY element {} with {} items exceeds the size of the data.",
+                header.name, header.count
+            )
+            .into());
+        }
+
+        let mut properties: Vec<(String, PlyValues)> = header
+            .properties
+            .iter()
+            .map(|(name, data_type)| {
+                let values = match data_type {
+                    PlyPropertyType::Scalar(_) => {
+                        PlyValues::Scalar(Vec::with_capacity(header.count))
+                    }
+                    PlyPropertyType::List { .. } => {
+                        PlyValues::List(Vec::with_capacity(header.count))
+                    }
+                };
+                (name.clone(), values)
+            })
+            .collect();
+
+        for item in 0..header.count {
+            for ((_, data_type), (_, values)) in header.properties.iter().zip(&mut properties) {
+                let value_error =
+                    || format!("Missing or invalid PLY data for {} {}.", header.name, item);
+                match (data_type, values) {
+                    (PlyPropertyType::Scalar(scalar), PlyValues::Scalar(values)) => {
+                        values.push(source.next(*scalar).ok_or_else(value_error)?);
+                    }
+                    (PlyPropertyType::List { count, item }, PlyValues::List(values)) => {
+                        let length = source.next(*count).ok_or_else(value_error)?;
+                        if length < 0.0 {
+                            return Err(value_error().into());
+                        }
+                        let list = (0..length as usize)
+                            .map(|_| source.next(*item))
+                            .collect::<Option<Vec<f64>>>()
+                            .ok_or_else(value_error)?;
+                        values.push(list);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        elements.push(PlyElement {
+            name: header.name,
+            count: header.count,
+            properties,
+        });
+    }
+
+    Ok(elements)
+}
+
+fn read_header<R: BufRead>(
+    reader: &mut R,
+) -> Result<(PlyFormat, Vec<ElementHeader>), Box<dyn Error>> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    if line.trim() != "ply" {
+        return Err("Not a PLY file. Expected ply on the first line.".into());
+    }
+
+    let mut format = None;
+    let mut elements: Vec<ElementHeader> = Vec::new();
+    let mut line_number = 1;
+    loop {
+        line.clear();
+        line_number += 1;
+        if reader.read_line(&mut line)? == 0 {
+            return Err("PLY header is missing end_header.".into());
+        }
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let header_error = || format!("Invalid PLY header on line {}.", line_number);
+
+        match parts.as_slice() {
+            ["end_header"] => break,
+            [] | ["comment", ..] | ["obj_info", ..] => continue,
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => return Err(format!("Unknown PLY format {}.", name).into()),
+                });
+            }
+            ["element", name, count] => elements.push(ElementHeader {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| header_error())?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count, item, name] => {
+                let data_type = PlyPropertyType::List {
+                    count: PlyScalar::parse(count).ok_or_else(header_error)?,
+                    item: PlyScalar::parse(item).ok_or_else(header_error)?,
+                };
+                elements
+                    .last_mut()
+                    .ok_or_else(header_error)?
+                    .properties
+                    .push((name.to_string(), data_type));
+            }
+            ["property", scalar, name] => {
+                let data_type =
+                    PlyPropertyType::Scalar(PlyScalar::parse(scalar).ok_or_else(header_error)?);
+                elements
+                    .last_mut()
+                    .ok_or_else(header_error)?
+                    .properties
+                    .push((name.to_string(), data_type));
+            }
+            _ => return Err(header_error().into()),
+        }
+    }
+
+    let format = format.ok_or("PLY header is missing the format.")?;
+    Ok((format, elements))
+}
+
+/// Write elements to PLY data.
+///
+/// Scalar properties are written as `float` and list properties as `list uchar int`, which covers
+/// vertex attributes and polygon indices. Lists with more than 255 items are written with a `uint` count instead.
+pub(crate) fn write_ply(format: PlyFormat, elements: &[PlyElement]) -> Vec<u8> {
+    let mut header = String::from("ply\n");
+    let format_name = match format {
+        PlyFormat::Ascii => "ascii",
+        PlyFormat::BinaryLittleEndian => "binary_little_endian",
+        PlyFormat::BinaryBigEndian => "binary_big_endian",
+    };
+    let _ = writeln!(header, "format {} 1.0", format_name);
+    let _ = writeln!(header, "comment Exported with Imlet");
+    for element in elements {
+        let _ = writeln!(header, "element {} {}", element.name, element.count);
+        for (name, values) in &element.properties {
+            match values {
+                PlyValues::Scalar(_) => {
+                    let _ = writeln!(header, "property float {}", name);
+                }
+                PlyValues::List(values) => {
+                    let count = if has_long_list(values) {
+                        "uint"
+                    } else {
+                        "uchar"
+                    };
+                    let _ = writeln!(header, "property list {} int {}", count, name);
+                }
+            }
+        }
+    }
+    header.push_str("end_header\n");
+
+    let mut data = header.into_bytes();
+    let mut sink = ValueSink {
+        format,
+        data: &mut data,
+    };
+    for element in elements {
+        let long_lists: Vec<bool> = element
+            .properties
+            .iter()
+            .map(|(_, values)| match values {
+                PlyValues::List(values) => has_long_list(values),
+                PlyValues::Scalar(_) => false,
+            })
+            .collect();
+        for item in 0..element.count {
+            for (index, (_, values)) in element.properties.iter().enumerate() {
+                if index > 0 {
+                    sink.separator();
+                }
+                match values {
+                    PlyValues::Scalar(values) => sink.float(values[item]),
+                    PlyValues::List(values) => {
+                        let list = &values[item];
+                        sink.count(list.len(), long_lists[index]);
+                        for &value in list {
+                            sink.separator();
+                            sink.int(value as i32);
+                        }
+                    }
+                }
+            }
+            sink.end_item();
+        }
+    }
+    data
+}
+
+/// Check if any list is too long for its length to be stored as a `uchar`.
+fn has_long_list(values: &[Vec<f64>]) -> bool {
+    values.iter().any(|list| list.len() > u8::MAX as usize)
+}
+
+struct ValueSink<'a> {
+    format: PlyFormat,
+    data: &'a mut Vec<u8>,
+}
+
+impl ValueSink<'_> {
+    fn float(&mut self, value: f64) {
+        let value = value as f32;
+        match self.format {
+            PlyFormat::Ascii => self.data.extend_from_slice(value.to_string().as_bytes()),
+            PlyFormat::BinaryLittleEndian => self.data.extend_from_slice(&value.to_le_bytes()),
+            PlyFormat::BinaryBigEndian => self.data.extend_from_slice(&value.to_be_bytes()),
+        }
+    }
+
+    fn int(&mut self, value: i32) {
+        match self.format {
+            PlyFormat::Ascii => self.data.extend_from_slice(value.to_string().as_bytes()),
+            PlyFormat::BinaryLittleEndian => self.data.extend_from_slice(&value.to_le_bytes()),
+            PlyFormat::BinaryBigEndian => self.data.extend_from_slice(&value.to_be_bytes()),
+        }
+    }
+
+    fn count(&mut self, value: usize, wide: bool) {
+        match (self.format, wide) {
+            (PlyFormat::Ascii, _) => self.data.extend_from_slice(value.to_string().as_bytes()),
+            (_, false) => self.data.push(value as u8),
+            (PlyFormat::BinaryLittleEndian, true) => {
+                self.data.extend_from_slice(&(value as u32).to_le_bytes())
+            }
+            (PlyFormat::BinaryBigEndian, true) => {
+                self.data.extend_from_slice(&(value as u32).to_be_bytes())
+            }
+        }
+    }
+
+    fn separator(&mut self) {
+        if self.format == PlyFormat::Ascii {
+            self.data.push(b' ');
+        }
+    }
+
+    fn end_item(&mut self) {
+        if self.format == PlyFormat::Ascii {
+            self.data.push(b'\n');
+        }
+    }
+}
+
+enum ValueSource<'a> {
+    Ascii(std::str::SplitWhitespace<'a>),
+    Binary {
+        data: &'a [u8],
+        position: usize,
+        little_endian: bool,
+    },
+}
+
+impl ValueSource<'_> {
+    /// Upper bound of the number of bytes left to read, given the total size of the data.
+    fn remaining(&self, total: usize) -> usize {
+        match self {
+            ValueSource::Ascii(_) => total,
+            ValueSource::Binary { data, position, .. } => data.len() - position,
+        }
+    }
+
+    fn next(&mut self, scalar: PlyScalar) -> Option<f64> {
+        match self {
+            ValueSource::Ascii(tokens) => tokens.next()?.parse().ok(),
+            ValueSource::Binary {
+                data,
+                position,
+                little_endian,
+            } => {
+                let size = scalar.size();
+                let bytes = data.get(*position..*position + size)?;
+                *position += size;
+
+                let mut buffer = [0u8; 8];
+                buffer[..size].copy_from_slice(bytes);
+                if !*little_endian {
+                    buffer[..size].reverse();
+                }
+                Some(match scalar {
+                    PlyScalar::I8 => buffer[0] as i8 as f64,
+                    PlyScalar::U8 => buffer[0] as f64,
+                    PlyScalar::I16 => i16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    PlyScalar::U16 => u16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    PlyScalar::I32 => {
+                        i32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as f64
+                    }
+                    PlyScalar::U32 => {
+                        u32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as f64
+                    }
+                    PlyScalar::F32 => {
+                        f32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as f64
+                    }
+                    PlyScalar::F64 => f64::from_le_bytes(buffer),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_ascii_and_binary() {
+        let ascii = "ply\nformat ascii 1.0\ncomment test\nelement vertex 2\nproperty float x\nproperty uchar red\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n1.5 255\n-2 7\n3 0 1 1\n";
+        let elements = read_ply(ascii.as_bytes()).unwrap();
+
+        let mut binary = b"ply\nformat binary_big_endian 1.0\nelement vertex 2\nproperty float x\nproperty uchar red\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n".to_vec();
+        for (x, red) in [(1.5f32, 255u8), (-2.0, 7)] {
+            binary.extend_from_slice(&x.to_be_bytes());
+            binary.push(red);
+        }
+        binary.push(3);
+        for index in [0i32, 1, 1] {
+            binary.extend_from_slice(&index.to_be_bytes());
+        }
+        let binary_elements = read_ply(binary.as_slice()).unwrap();
+
+        for elements in [elements, binary_elements] {
+            assert_eq!(elements.len(), 2);
+            assert_eq!(elements[0].count, 2);
+            assert_eq!(elements[0].scalar("x").unwrap(), &[1.5, -2.0]);
+            assert_eq!(elements[0].scalar("red").unwrap(), &[255.0, 7.0]);
+            assert_eq!(
+                elements[1].properties[0].1,
+                PlyValues::List(vec![vec![0.0, 1.0, 1.0]])
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let elements = [
+            PlyElement {
+                name: "vertex".to_string(),
+                count: 3,
+                properties: vec![
+                    ("x".to_string(), PlyValues::Scalar(vec![0.0, 1.0, 0.0])),
+                    (
+                        "value".to_string(),
+                        PlyValues::Scalar(vec![0.5, -1.25, 3.0]),
+                    ),
+                ],
+            },
+            PlyElement {
+                name: "face".to_string(),
+                count: 1,
+                properties: vec![(
+                    "vertex_indices".to_string(),
+                    PlyValues::List(vec![vec![0.0, 1.0, 2.0]]),
+                )],
+            },
+        ];
+
+        for format in [
+            PlyFormat::Ascii,
+            PlyFormat::BinaryLittleEndian,
+            PlyFormat::BinaryBigEndian,
+        ] {
+            let read = read_ply(write_ply(format, &elements).as_slice()).unwrap();
+            assert_eq!(read.len(), 2);
+            assert_eq!(read[0].scalar("value").unwrap(), &[0.5, -1.25, 3.0]);
+            assert_eq!(
+                read[1].list("vertex_indices").unwrap(),
+                &[vec![0.0, 1.0, 2.0]]
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_long_list() {
+        let list: Vec<f64> = (0..300).map(|i| i as f64).collect();
+        let elements = [PlyElement {
+            name: "face".to_string(),
+            count: 1,
+            properties: vec![(
+                "vertex_indices".to_string(),
+                PlyValues::List(vec![list.clone()]),
+            )],
+        }];
+
+        for format in [
+            PlyFormat::Ascii,
+            PlyFormat::BinaryLittleEndian,
+            PlyFormat::BinaryBigEndian,
+        ] {
+            let read = read_ply(write_ply(format, &elements).as_slice()).unwrap();
+            assert_eq!(
+                read[0].list("vertex_indices").unwrap(),
+                std::slice::from_ref(&list)
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncated_data() {
+        let ascii = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1.5\n";
+        assert!(read_ply(ascii.as_bytes()).is_err());
+        assert!(read_ply("format ascii 1.0\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_count_exceeding_data() {
+        let ascii = "ply\nformat ascii 1.0\nelement vertex 100000000000000000\nproperty double x\nend_header\n1.5\n";
+        assert!(read_ply(ascii.as_bytes()).is_err());
+
+        let mut binary = b"ply\nformat binary_little_endian 1.0\nelement vertex 100000000000000000\nproperty double x\nend_header\n".to_vec();
+        binary.extend_from_slice(&1.5f64.to_le_bytes());
+        assert!(read_ply(binary.as_slice()).is_err());
+
+        let overflow = format!(
+            "ply\nformat binary_little_endian 1.0\nelement vertex {}\nproperty double x\nend_header\n",
+            usize::MAX
+        );
+        assert!(read_ply(overflow.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt::Write;
+
+use num_traits::Float;
+
+use crate::{
+    algorithms::{marching_squares::signed_area, slicing::SliceLayer},
+    types::geometry::Vec3,
+};
+
+/// Extents of the contours of all layers in x and y, as `(min_x, min_y, max_x, max_y)`.
+pub(crate) fn contour_extents<T: Float>(layers: &[SliceLayer<T>]) -> (f64, f64, f64, f64) {
+    let mut extents = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for point in layers
+        .iter()
+        .flat_map(|layer| layer.contours.iter().flatten())
+    {
+        let (x, y) = (to_f64(point.x), to_f64(point.y));
+        extents = (
+            extents.0.min(x),
+            extents.1.min(y),
+            extents.2.max(x),
+            extents.3.max(y),
+        );
+    }
+    if extents.0 > extents.2 {
+        (0.0, 0.0, 0.0, 0.0)
+    } else {
+        extents
+    }
+}
+
+/// Write the contours of a layer as an SVG image, with one path and sizes in millimeters.
+///
+/// The y axis points up, as seen from above the layer.
+///
+/// # Arguments
+///
+/// * `layer` - The layer to write.
+/// * `extents` - Region of the image, as `(min_x, min_y, max_x, max_y)`.
+pub(crate) fn layer_to_svg<T: Float>(
+    layer: &SliceLayer<T>,
+    extents: (f64, f64, f64, f64),
+) -> String {
+    let (min_x, min_y, max_x, max_y) = extents;
+    let (width, height) = (max_x - min_x, max_y - min_y);
+
+    let mut path = String::new();
+    for contour in layer.contours.iter().filter(|c| !c.is_empty()) {
+        for (index, point) in contour.iter().enumerate() {
+            let _ = write!(
+                path,
+                "{}{} {} ",
+                if index == 0 { "M" } else { "L" },
+                to_f64(point.x) - min_x,
+                max_y - to_f64(point.y)
+            );
+        }
+        path.push_str("Z ");
+    }
+
+    let mut svg = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}mm" height="{}mm" viewBox="0 0 {} {}">"#,
+        width, height, width, height
+    );
+    let _ = writeln!(svg, "  <!-- Layer at z = {} -->", to_f64(layer.z));
+    let _ = writeln!(
+        svg,
+        r#"  <path d="{}" fill="black" fill-rule="evenodd" stroke="none"/>"#,
+        path.trim_end()
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Write layers in the ascii Common Layer Interface (CLI) format, with coordinates in millimeters.
+///
+/// Each layer is written at the height of its top, and the dimension spans from the bottom of the first layer to the top of the last layer.
+/// Contours are written as closed polylines, with the direction 1 for counter-clockwise outer contours
+/// and 0 for clockwise contours around holes.
+pub(crate) fn layers_to_cli<T: Float>(layers: &[SliceLayer<T>]) -> String {
+    let (min_x, min_y, max_x, max_y) = contour_extents(layers);
+    let (min_z, max_z) = match (layers.first(), layers.last()) {
+        (Some(first), Some(last)) => (to_f64(first.bottom()), to_f64(last.top())),
+        _ => (0.0, 0.0),
+    };
+
+    let mut cli = String::from("$$HEADERSTART\n$$ASCII\n$$UNITS/1\n$$VERSION/200\n");
+    let _ = writeln!(
+        cli,
+        "$$DIMENSION/{},{},{},{},{},{}",
+        min_x, min_y, min_z, max_x, max_y, max_z
+    );
+    let _ = writeln!(cli, "$$LAYERS/{}", layers.len());
+    cli.push_str("$$HEADEREND\n$$GEOMETRYSTART\n");
+    for layer in layers {
+        let _ = writeln!(cli, "$$LAYER/{}", to_f64(layer.top()));
+        for contour in layer.contours.iter().filter(|c| !c.is_empty()) {
+            let direction = if signed_area(xy(contour)) > T::zero() {
+                1
+            } else {
+                0
+            };
+            // Closed polylines repeat the first point.
+            let _ = write!(cli, "$$POLYLINE/1,{},{}", direction, contour.len() + 1);
+            for point in contour.iter().chain(contour.first()) {
+                let _ = write!(cli, ",{},{}", to_f64(point.x), to_f64(point.y));
+            }
+            cli.push('\n');
+        }
+    }
+    cli.push_str("$$GEOMETRYEND\n");
+    cli
+}
+
+fn xy<T: Float>(contour: &[Vec3<T>]) -> impl Iterator<Item = (T, T)> + Clone + '_ {
+    contour.iter().map(|p| (p.x, p.y))
+}
+
+fn to_f64<T: Float>(value: T) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(z: f64, size: f64, clockwise: bool) -> Vec<Vec3<f64>> {
+        let mut points = vec![
+            Vec3::new(0.0, 0.0, z),
+            Vec3::new(size, 0.0, z),
+            Vec3::new(size, size, z),
+            Vec3::new(0.0, size, z),
+        ];
+        if clockwise {
+            points.reverse();
+        }
+        points
+    }
+
+    fn layers() -> Vec<SliceLayer<f64>> {
+        vec![
+            SliceLayer {
+                z: 0.5,
+                thickness: 1.0,
+                contours: vec![square(0.5, 4.0, false), square(0.5, 1.0, true)],
+            },
+            SliceLayer {
+                z: 1.5,
+                thickness: 1.0,
+                contours: vec![square(1.5, 2.0, false)],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_svg() {
+        let layers = layers();
+        let extents = contour_extents(&layers);
+        assert_eq!(extents, (0.0, 0.0, 4.0, 4.0));
+
+        let svg = layer_to_svg(&layers[0], extents);
+        assert!(svg.contains(r#"width="4mm" height="4mm" viewBox="0 0 4 4""#));
+        // The y axis is flipped, so the origin is at the bottom of the image.
+        assert!(svg.contains(r#"d="M0 4 L4 4 L4 0 L0 0 Z M0 3 L1 3 L1 4 L0 4 Z""#));
+    }
+
+    #[test]
+    fn test_cli() {
+        let cli = layers_to_cli(&layers());
+        let lines: Vec<&str> = cli.lines().collect();
+
+        assert_eq!(lines[0], "$$HEADERSTART");
+        assert!(lines.contains(&"$$DIMENSION/0,0,0,4,4,2"));
+        assert!(lines.contains(&"$$LAYERS/2"));
+        assert!(lines.contains(&"$$LAYER/1"));
+        assert!(lines.contains(&"$$LAYER/2"));
+        assert!(lines.contains(&"$$POLYLINE/1,1,5,0,0,4,0,4,4,0,4,0,0"));
+        assert!(lines.contains(&"$$POLYLINE/1,0,5,0,1,1,1,1,0,0,0,0,1"));
+        assert_eq!(cli.matches("$$LAYER/").count(), 2);
+        assert_eq!(lines.last(), Some(&"$$GEOMETRYEND"));
+    }
+}
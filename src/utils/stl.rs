@@ -0,0 +1,178 @@
+use std::{error::Error, fmt::Display};
+
+use num_traits::Float;
+
+use crate::types::geometry::{Mesh, Triangle, Vec3};
+
+/// Size of the binary header, including the triangle count.
+const BINARY_HEADER_SIZE: usize = 84;
+/// Size of one binary facet: normal, three vertices and the attribute byte count.
+const BINARY_FACET_SIZE: usize = 50;
+
+/// Read the triangles of an ascii or binary STL file.
+///
+/// Binary files are detected by their size, since some exporters also start binary headers with `solid`.
+pub(crate) fn read_stl<T: Float>(data: &[u8]) -> Result<Vec<Triangle<T>>, Box<dyn Error>> {
+    if data.len() >= BINARY_HEADER_SIZE {
+        let count = u32::from_le_bytes([data[80], data[81], data[82], data[83]]) as usize;
+        if data.len() == BINARY_HEADER_SIZE + count * BINARY_FACET_SIZE {
+            return Ok(read_binary(data, count));
+        }
+    }
+
+    let text = std::str::from_utf8(data)
+        .map_err(|_| "Invalid STL file. Binary data doesn't match the triangle count.")?;
+    if !text.trim_start().starts_with("solid") {
+        return Err("Invalid STL file. Expected solid at the start of an ascii file.".into());
+    }
+    read_ascii(text)
+}
+
+fn read_binary<T: Float>(data: &[u8], count: usize) -> Vec<Triangle<T>> {
+    let value = |offset: usize| {
+        let bytes = [
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ];
+        T::from(f32::from_le_bytes(bytes)).unwrap()
+    };
+    let vertex = |offset: usize| Vec3::new(value(offset), value(offset + 4), value(offset + 8));
+
+    (0..count)
+        .map(|i| {
+            // Skip the stored normal, which is recomputed from the vertices.
+            let offset = BINARY_HEADER_SIZE + i * BINARY_FACET_SIZE + 12;
+            Triangle::new(vertex(offset), vertex(offset + 12), vertex(offset + 24))
+        })
+        .collect()
+}
+
+fn read_ascii<T: Float>(text: &str) -> Result<Vec<Triangle<T>>, Box<dyn Error>> {
+    let mut triangles = Vec::new();
+    let mut facet: Vec<Vec3<T>> = Vec::with_capacity(3);
+    for (index, line) in text.lines().enumerate() {
+        let line_error = || format!("Invalid STL data on line {}.", index + 1);
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["vertex", x, y, z] => {
+                let coordinate = |s: &str| -> Result<T, String> {
+                    s.parse::<f64>()
+                        .ok()
+                        .and_then(T::from)
+                        .ok_or_else(line_error)
+                };
+                facet.push(Vec3::new(coordinate(x)?, coordinate(y)?, coordinate(z)?));
+            }
+            ["vertex", ..] => return Err(line_error().into()),
+            ["endfacet", ..] => {
+                if facet.len() != 3 {
+                    return Err(format!(
+                        "Invalid STL facet ending on line {}. Expected 3 vertices, found {}.",
+                        index + 1,
+                        facet.len()
+                    )
+                    .into());
+                }
+                triangles.push(Triangle::new(facet[0], facet[1], facet[2]));
+                facet.clear();
+            }
+            _ => continue,
+        }
+    }
+    Ok(triangles)
+}
+
+/// Normal of a face, or zero for degenerate faces.
+fn facet_normal<T: Float>(triangle: &Triangle<T>) -> Vec3<T> {
+    let normal = (triangle.p2() - triangle.p1()).cross(&(triangle.p3() - triangle.p1()));
+    let length = normal.magnitude();
+    if length > T::zero() {
+        normal * (T::one() / length)
+    } else {
+        Vec3::origin()
+    }
+}
+
+pub(crate) fn mesh_to_stl_ascii<T: Float + Display>(mesh: &Mesh<T>, name: &str) -> String {
+    let mut data = format!("solid {}\n", name);
+    for triangle in mesh.as_triangles() {
+        let n = facet_normal(&triangle);
+        data.push_str(&format!("  facet normal {} {} {}\n", n.x, n.y, n.z));
+        data.push_str("    outer loop\n");
+        for v in triangle.p.iter() {
+            data.push_str(&format!("      vertex {} {} {}\n", v.x, v.y, v.z));
+        }
+        data.push_str("    endloop\n  endfacet\n");
+    }
+    data.push_str(&format!("endsolid {}\n", name));
+    data
+}
+
+pub(crate) fn mesh_to_stl_binary<T: Float>(mesh: &Mesh<T>) -> Vec<u8> {
+    let mut data = Vec::with_capacity(BINARY_HEADER_SIZE + mesh.num_faces() * BINARY_FACET_SIZE);
+    let mut header = [0u8; 80];
+    let text = b"Binary STL exported with Imlet";
+    header[..text.len()].copy_from_slice(text);
+    data.extend_from_slice(&header);
+    data.extend_from_slice(&(mesh.num_faces() as u32).to_le_bytes());
+
+    for triangle in mesh.as_triangles() {
+        for v in std::iter::once(facet_normal(&triangle)).chain(triangle.p) {
+            for value in [v.x, v.y, v.z] {
+                data.extend_from_slice(&value.to_f32().unwrap_or(0.0).to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&[0, 0]);
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::io::parse_obj_file;
+
+    use super::*;
+
+    #[test]
+    fn test_ascii_and_binary_round_trip() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let binary = mesh_to_stl_binary(&mesh);
+        assert_eq!(binary.len(), 84 + 50 * mesh.num_faces());
+
+        for triangles in [
+            read_stl::<f64>(mesh_to_stl_ascii(&mesh, "box").as_bytes()).unwrap(),
+            read_stl::<f64>(&binary).unwrap(),
+        ] {
+            assert_eq!(triangles.len(), mesh.num_faces());
+            for (a, b) in triangles.iter().zip(mesh.as_triangles()) {
+                for (p, q) in a.p.iter().zip(b.p.iter()) {
+                    assert!(p.distance_to_vec3(q) < 1E-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_header_starting_with_solid() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let mut binary = mesh_to_stl_binary(&mesh);
+        binary[..6].copy_from_slice(b"solid ");
+
+        assert_eq!(read_stl::<f64>(&binary).unwrap().len(), mesh.num_faces());
+    }
+
+    #[test]
+    fn test_invalid_ascii() {
+        let incomplete = "solid test\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nendloop\nendfacet\nendsolid test\n";
+        let err = read_stl::<f64>(incomplete.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 7"));
+
+        let invalid = "solid test\nfacet normal 0 0 1\nouter loop\nvertex 0 a 0\n";
+        let err = read_stl::<f64>(invalid.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 4"));
+
+        assert!(read_stl::<f64>(b"not an stl").is_err());
+    }
+}
@@ -0,0 +1,31 @@
+use std::{fs, path::PathBuf};
+
+use crate::types::geometry::Vec3;
+
+/// Path and name of a file in the temporary directory, unique to the test process.
+pub(crate) fn temp_file_name(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("imlet_{}_{}", std::process::id(), name));
+    path.to_str().unwrap().to_string()
+}
+
+/// Write a file with some contents to the temporary directory, and return its path.
+pub(crate) fn temp_file(name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+    let path = PathBuf::from(temp_file_name(name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Evenly spread points on a sphere around the origin, with outward normals.
+pub(crate) fn sphere_samples(radius: f64, count: usize) -> (Vec<Vec3<f64>>, Vec<Vec3<f64>>) {
+    let golden_angle = std::f64::consts::PI * (3.0 - 5.0_f64.sqrt());
+    let normals: Vec<Vec3<f64>> = (0..count)
+        .map(|i| {
+            let z = 1.0 - 2.0 * (i as f64 + 0.5) / count as f64;
+            let r = (1.0 - z * z).sqrt();
+            let theta = golden_angle * i as f64;
+            Vec3::new(r * theta.cos(), r * theta.sin(), z)
+        })
+        .collect();
+    let points = normals.iter().map(|&n| n * radius).collect();
+    (points, normals)
+}
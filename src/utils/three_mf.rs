@@ -0,0 +1,147 @@
+use std::fmt::Write;
+
+use num_traits::Float;
+
+use crate::types::{computation::functions::MeshUnits, geometry::Mesh};
+
+use super::zip::write_zip;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+/// Write meshes to a 3MF package, with one object and build item per mesh.
+///
+/// 3MF has no vertex normals, but requires outward facing triangles. If a mesh has normals,
+/// faces pointing against the normals at their vertices are flipped.
+///
+/// # Arguments
+///
+/// * `objects` - Names and meshes of the objects.
+/// * `units` - Unit of the mesh coordinates.
+pub(crate) fn write_3mf<T: Float>(objects: &[(&str, &Mesh<T>)], units: MeshUnits) -> Vec<u8> {
+    let model = model_xml(objects, units);
+    write_zip(&[
+        ("[Content_Types].xml", CONTENT_TYPES.as_bytes()),
+        ("_rels/.rels", RELATIONSHIPS.as_bytes()),
+        ("3D/3dmodel.model", model.as_bytes()),
+    ])
+}
+
+fn model_xml<T: Float>(objects: &[(&str, &Mesh<T>)], units: MeshUnits) -> String {
+    let unit = match units {
+        MeshUnits::Millimeters => "millimeter",
+        MeshUnits::Centimeters => "centimeter",
+        MeshUnits::Meters => "meter",
+        MeshUnits::Inches => "inch",
+        MeshUnits::Feet => "foot",
+    };
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<model unit="{}" xml:lang="en-US" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">"#,
+        unit
+    );
+    xml.push_str("  <resources>\n");
+    for (index, (name, mesh)) in objects.iter().enumerate() {
+        let _ = writeln!(
+            xml,
+            r#"    <object id="{}" name="{}" type="model">"#,
+            index + 1,
+            xml_escape(name)
+        );
+        xml.push_str("      <mesh>\n        <vertices>\n");
+        for v in mesh.vertices() {
+            let _ = writeln!(
+                xml,
+                r#"          <vertex x="{}" y="{}" z="{}"/>"#,
+                v.x.to_f64().unwrap_or(0.0),
+                v.y.to_f64().unwrap_or(0.0),
+                v.z.to_f64().unwrap_or(0.0)
+            );
+        }
+        xml.push_str("        </vertices>\n        <triangles>\n");
+        for face in mesh.faces() {
+            let [v1, v2, v3] = oriented_face(mesh, face);
+            let _ = writeln!(
+                xml,
+                r#"          <triangle v1="{}" v2="{}" v3="{}"/>"#,
+                v1, v2, v3
+            );
+        }
+        xml.push_str("        </triangles>\n      </mesh>\n    </object>\n");
+    }
+    xml.push_str("  </resources>\n  <build>\n");
+    for index in 0..objects.len() {
+        let _ = writeln!(xml, r#"    <item objectid="{}"/>"#, index + 1);
+    }
+    xml.push_str("  </build>\n</model>\n");
+    xml
+}
+
+/// Face with the winding flipped if it points against the vertex normals.
+fn oriented_face<T: Float>(mesh: &Mesh<T>, face: &[usize; 3]) -> [usize; 3] {
+    let Some(normals) = mesh.normals() else {
+        return *face;
+    };
+    let [a, b, c] = face.map(|i| mesh.vertices()[i]);
+    let face_normal = (b - a).cross(&(c - a));
+    let vertex_normal = normals[face[0]] + normals[face[1]] + normals[face[2]];
+    if face_normal.dot(&vertex_normal) < T::zero() {
+        [face[0], face[2], face[1]]
+    } else {
+        *face
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{types::geometry::Vec3, utils::io::parse_obj_file};
+
+    use super::*;
+
+    #[test]
+    fn test_model_xml() {
+        let mesh = parse_obj_file::<f64>("assets/geometry/box.obj", false, false).unwrap();
+        let xml = model_xml(&[("Box <1>", &mesh), ("Box 2", &mesh)], MeshUnits::Inches);
+
+        assert!(xml.contains(r#"<model unit="inch""#));
+        assert!(xml.contains(r#"<object id="1" name="Box &lt;1&gt;" type="model">"#));
+        assert!(xml.contains(r#"<item objectid="2"/>"#));
+        assert_eq!(xml.matches("<vertex ").count(), 16);
+        assert_eq!(xml.matches("<triangle ").count(), 24);
+    }
+
+    #[test]
+    fn test_orientation_from_normals() {
+        let mut mesh = Mesh::new();
+        mesh.add_vertices(&[
+            Vec3::origin(),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ]);
+        mesh.add_faces(&[[0, 1, 2]]);
+        assert_eq!(oriented_face(&mesh, &[0, 1, 2]), [0, 1, 2]);
+
+        mesh.set_normals(&[Vec3::new(0.0, 0.0, -1.0); 3]);
+        assert_eq!(oriented_face(&mesh, &[0, 1, 2]), [0, 2, 1]);
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt::Write;
+
+use num_traits::Float;
+
+use crate::types::geometry::{BoundingBox, Vec3};
+
+use super::base64;
+
+/// VTK cell type of an axis aligned hexahedron.
+const VTK_VOXEL: i32 = 11;
+
+/// Regular grid of values, ordered by x, then y, then z.
+pub(crate) struct VolumeBlock<'a, T> {
+    pub origin: Vec3<T>,
+    pub spacing: Vec3<T>,
+    pub dims: (usize, usize, usize),
+    pub values: &'a [T],
+}
+
+impl<'a, T: Float> VolumeBlock<'a, T> {
+    /// Block of a cubic leaf, spanning the bounds with `n`x`n`x`n` values.
+    pub fn from_cube(bounds: BoundingBox<T>, values: &'a [T]) -> Self {
+        let n = (values.len() as f64).cbrt().round() as usize;
+        let (dx, dy, dz) = bounds.dimensions();
+        let steps = T::from(n.max(2) - 1).unwrap();
+        Self {
+            origin: bounds.min,
+            spacing: Vec3::new(dx / steps, dy / steps, dz / steps),
+            dims: (n, n, n),
+            values,
+        }
+    }
+
+    fn num_values(&self) -> usize {
+        self.dims.0 * self.dims.1 * self.dims.2
+    }
+}
+
+fn f32_value<T: Float>(value: T) -> f32 {
+    value.to_f32().unwrap_or(f32::NAN)
+}
+
+fn push_be_f32<T: Float>(data: &mut Vec<u8>, values: impl Iterator<Item = T>) {
+    for value in values {
+        data.extend_from_slice(&f32_value(value).to_be_bytes());
+    }
+}
+
+/// Legacy VTK structured points, with binary big endian values.
+pub(crate) fn block_to_vtk_legacy<T: Float>(block: &VolumeBlock<T>) -> Vec<u8> {
+    let (nx, ny, nz) = block.dims;
+    let mut header = String::from("# vtk DataFile Version 3.0\nImlet field\nBINARY\n");
+    let _ = writeln!(header, "DATASET STRUCTURED_POINTS");
+    let _ = writeln!(header, "DIMENSIONS {} {} {}", nx, ny, nz);
+    let _ = writeln!(
+        header,
+        "ORIGIN {} {} {}",
+        f32_value(block.origin.x),
+        f32_value(block.origin.y),
+        f32_value(block.origin.z)
+    );
+    let _ = writeln!(
+        header,
+        "SPACING {} {} {}",
+        f32_value(block.spacing.x),
+        f32_value(block.spacing.y),
+        f32_value(block.spacing.z)
+    );
+    let _ = writeln!(header, "POINT_DATA {}", block.num_values());
+    header.push_str("SCALARS value float 1\nLOOKUP_TABLE default\n");
+
+    let mut data = header.into_bytes();
+    push_be_f32(&mut data, block.values.iter().copied());
+    data.push(b'\n');
+    data
+}
+
+/// XML VTK image data, with base64 encoded little endian values.
+pub(crate) fn block_to_vti<T: Float>(block: &VolumeBlock<T>) -> Vec<u8> {
+    let (nx, ny, nz) = block.dims;
+    let extent = format!("0 {} 0 {} 0 {}", nx - 1, ny - 1, nz - 1);
+
+    // Binary data arrays start with the number of bytes that follow.
+    let mut payload = Vec::with_capacity(4 + 4 * block.num_values());
+    payload.extend_from_slice(&((4 * block.num_values()) as u32).to_le_bytes());
+    for &value in block.values {
+        payload.extend_from_slice(&f32_value(value).to_le_bytes());
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\"?>\n");
+    xml.push_str("<VTKFile type=\"ImageData\" version=\"1.0\" byte_order=\"LittleEndian\" header_type=\"UInt32\">\n");
+    let _ = writeln!(
+        xml,
+        "  <ImageData WholeExtent=\"{}\" Origin=\"{} {} {}\" Spacing=\"{} {} {}\">",
+        extent,
+        f32_value(block.origin.x),
+        f32_value(block.origin.y),
+        f32_value(block.origin.z),
+        f32_value(block.spacing.x),
+        f32_value(block.spacing.y),
+        f32_value(block.spacing.z)
+    );
+    let _ = writeln!(xml, "    <Piece Extent=\"{}\">", extent);
+    xml.push_str("      <PointData Scalars=\"value\">\n");
+    let _ = writeln!(
+        xml,
+        "        <DataArray type=\"Float32\" Name=\"value\" format=\"binary\">{}</DataArray>",
+        base64::encode(&payload)
+    );
+    xml.push_str("      </PointData>\n    </Piece>\n  </ImageData>\n</VTKFile>\n");
+    xml.into_bytes()
+}
+
+/// XML VTK multiblock index, referencing the files of the blocks.
+pub(crate) fn vtm_index(block_files: &[String]) -> Vec<u8> {
+    let mut xml = String::from("<?xml version=\"1.0\"?>\n");
+    xml.push_str(
+        "<VTKFile type=\"vtkMultiBlockDataSet\" version=\"1.0\" byte_order=\"LittleEndian\">\n",
+    );
+    xml.push_str("  <vtkMultiBlockDataSet>\n");
+    for (index, file) in block_files.iter().enumerate() {
+        let _ = writeln!(xml, "    <DataSet index=\"{}\" file=\"{}\"/>", index, file);
+    }
+    xml.push_str("  </vtkMultiBlockDataSet>\n</VTKFile>\n");
+    xml.into_bytes()
+}
+
+/// Legacy VTK unstructured grid with one voxel per cell of the blocks, with binary big endian data.
+///
+/// Points on the boundaries between blocks are duplicated.
+pub(crate) fn blocks_to_vtk_unstructured<T: Float>(blocks: &[VolumeBlock<T>]) -> Vec<u8> {
+    let num_points: usize = blocks.iter().map(|b| b.num_values()).sum();
+    let num_cells: usize = blocks
+        .iter()
+        .map(|b| {
+            b.dims.0.saturating_sub(1) * b.dims.1.saturating_sub(1) * b.dims.2.saturating_sub(1)
+        })
+        .sum();
+
+    let mut data = String::from("# vtk DataFile Version 3.0\nImlet sparse field\nBINARY\n");
+    let _ = writeln!(data, "DATASET UNSTRUCTURED_GRID");
+    let _ = writeln!(data, "POINTS {} float", num_points);
+    let mut data = data.into_bytes();
+    for block in blocks {
+        let (nx, ny, nz) = block.dims;
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let offset = Vec3::new(
+                        block.spacing.x * T::from(i).unwrap(),
+                        block.spacing.y * T::from(j).unwrap(),
+                        block.spacing.z * T::from(k).unwrap(),
+                    );
+                    let p = block.origin + offset;
+                    push_be_f32(&mut data, [p.x, p.y, p.z].into_iter());
+                }
+            }
+        }
+    }
+
+    data.extend_from_slice(format!("\nCELLS {} {}\n", num_cells, 9 * num_cells).as_bytes());
+    let mut start = 0;
+    for block in blocks {
+        let (nx, ny, nz) = block.dims;
+        let id = |i: usize, j: usize, k: usize| (start + i + nx * (j + ny * k)) as i32;
+        for k in 0..nz.saturating_sub(1) {
+            for j in 0..ny.saturating_sub(1) {
+                for i in 0..nx.saturating_sub(1) {
+                    let voxel = [
+                        8,
+                        id(i, j, k),
+                        id(i + 1, j, k),
+                        id(i, j + 1, k),
+                        id(i + 1, j + 1, k),
+                        id(i, j, k + 1),
+                        id(i + 1, j, k + 1),
+                        id(i, j + 1, k + 1),
+                        id(i + 1, j + 1, k + 1),
+                    ];
+                    for value in voxel {
+                        data.extend_from_slice(&value.to_be_bytes());
+                    }
+                }
+            }
+        }
+        start += block.num_values();
+    }
+
+    data.extend_from_slice(format!("\nCELL_TYPES {}\n", num_cells).as_bytes());
+    for _ in 0..num_cells {
+        data.extend_from_slice(&VTK_VOXEL.to_be_bytes());
+    }
+
+    data.extend_from_slice(
+        format!(
+            "\nPOINT_DATA {}\nSCALARS value float 1\nLOOKUP_TABLE default\n",
+            num_points
+        )
+        .as_bytes(),
+    );
+    for block in blocks {
+        push_be_f32(&mut data, block.values.iter().copied());
+    }
+    data.push(b'\n');
+    data
+}
+
+/// NRRD volume with an attached header and raw little endian values.
+pub(crate) fn block_to_nrrd<T: Float>(block: &VolumeBlock<T>) -> Vec<u8> {
+    let (nx, ny, nz) = block.dims;
+    let mut header = String::from("NRRD0004\n# Imlet field\ntype: float\ndimension: 3\n");
+    let _ = writeln!(header, "sizes: {} {} {}", nx, ny, nz);
+    header.push_str("encoding: raw\nendian: little\nspace dimension: 3\n");
+    let _ = writeln!(
+        header,
+        "space origin: ({},{},{})",
+        f32_value(block.origin.x),
+        f32_value(block.origin.y),
+        f32_value(block.origin.z)
+    );
+    let _ = writeln!(
+        header,
+        "space directions: ({},0,0) (0,{},0) (0,0,{})",
+        f32_value(block.spacing.x),
+        f32_value(block.spacing.y),
+        f32_value(block.spacing.z)
+    );
+    header.push('\n');
+
+    let mut data = header.into_bytes();
+    for &value in block.values {
+        data.extend_from_slice(&f32_value(value).to_le_bytes());
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(data: &[u8], pattern: &[u8]) -> usize {
+        data.windows(pattern.len())
+            .position(|w| w == pattern)
+            .unwrap()
+    }
+
+    fn test_block(values: &[f64]) -> VolumeBlock<'_, f64> {
+        VolumeBlock {
+            origin: Vec3::new(1.0, 2.0, 3.0),
+            spacing: Vec3::new(0.5, 0.5, 0.5),
+            dims: (3, 2, 2),
+            values,
+        }
+    }
+
+    #[test]
+    fn test_legacy_and_nrrd() {
+        let values: Vec<f64> = (0..12).map(|i| i as f64).collect();
+        let block = test_block(&values);
+
+        let vtk = block_to_vtk_legacy(&block);
+        let text = String::from_utf8_lossy(&vtk);
+        assert!(text.contains("DIMENSIONS 3 2 2\nORIGIN 1 2 3\nSPACING 0.5 0.5 0.5\n"));
+        let start = find(&vtk, b"LOOKUP_TABLE default\n") + 21;
+        assert_eq!(vtk.len(), start + 12 * 4 + 1);
+        assert_eq!(&vtk[start + 4..start + 8], &1f32.to_be_bytes());
+
+        let nrrd = block_to_nrrd(&block);
+        let start = find(&nrrd, b"\n\n") + 2;
+        let header = String::from_utf8_lossy(&nrrd[..start]);
+        assert!(header.starts_with("NRRD0004\n"));
+        assert!(header.contains("sizes: 3 2 2\n"));
+        assert!(header.contains("space directions: (0.5,0,0) (0,0.5,0) (0,0,0.5)\n"));
+        assert_eq!(nrrd.len(), start + 12 * 4);
+        assert_eq!(&nrrd[nrrd.len() - 4..], &11f32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_vti() {
+        let values: Vec<f64> = (0..12).map(|i| i as f64).collect();
+        let vti = String::from_utf8(block_to_vti(&test_block(&values))).unwrap();
+
+        assert!(vti.contains("WholeExtent=\"0 2 0 1 0 1\" Origin=\"1 2 3\""));
+        let start = vti.find("format=\"binary\">").unwrap() + 16;
+        let end = vti.find("</DataArray>").unwrap();
+        let payload = base64::decode(&vti[start..end]).unwrap();
+        assert_eq!(&payload[..4], &48u32.to_le_bytes());
+        assert_eq!(&payload[payload.len() - 4..], &11f32.to_le_bytes());
+
+        let index = String::from_utf8(vtm_index(&["a/0.vti".to_string()])).unwrap();
+        assert!(index.contains("<DataSet index=\"0\" file=\"a/0.vti\"/>"));
+    }
+
+    #[test]
+    fn test_unstructured() {
+        let values = vec![0.0; 8];
+        let bounds = BoundingBox::new(Vec3::origin(), Vec3::new(1.0, 1.0, 1.0));
+        let block = VolumeBlock::from_cube(bounds, &values);
+        assert_eq!(block.dims, (2, 2, 2));
+        assert!(block.spacing.distance_to_vec3(&Vec3::new(1.0, 1.0, 1.0)) < 1E-12);
+
+        let vtk = blocks_to_vtk_unstructured(&[block, VolumeBlock::from_cube(bounds, &values)]);
+        let text = String::from_utf8_lossy(&vtk);
+        assert!(text.contains("POINTS 16 float\n"));
+        assert!(text.contains("\nCELLS 2 18\n"));
+        assert!(text.contains("\nCELL_TYPES 2\n"));
+        assert!(text.contains("\nPOINT_DATA 16\n"));
+
+        // The second voxel uses the points of the second block.
+        let cells = find(&vtk, b"\nCELLS 2 18\n") + 12;
+        let second = cells + 9 * 4;
+        assert_eq!(&vtk[second..second + 4], &8i32.to_be_bytes());
+        assert_eq!(&vtk[second + 4..second + 8], &8i32.to_be_bytes());
+        assert_eq!(&vtk[second + 32..second + 36], &15i32.to_be_bytes());
+    }
+}
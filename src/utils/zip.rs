@@ -0,0 +1,105 @@
+/// Date of all entries, 1980-01-01 in MS-DOS format, so archives don't depend on the time of export.
+const DOS_DATE: u16 = 0x21;
+
+/// Write files to a zip archive, stored without compression.
+///
+/// # Arguments
+///
+/// * `files` - Paths in the archive and the file contents.
+pub(crate) fn write_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for (name, content) in files {
+        let offset = data.len() as u32;
+        let crc = crc32(content);
+        let size = content.len() as u32;
+
+        data.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        push_entry_fields(&mut data, crc, size, name);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(content);
+
+        central_directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes());
+        push_entry_fields(&mut central_directory, crc, size, name);
+        // Extra field, comment, disk number, internal and external attributes.
+        central_directory.extend_from_slice(&[0; 12]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = data.len() as u32;
+    data.extend_from_slice(&central_directory);
+    data.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    data.extend_from_slice(&[0; 4]);
+    data.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    data.extend_from_slice(&directory_offset.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data
+}
+
+/// Fields shared by the local header and the central directory, up to the file name length.
+fn push_entry_fields(data: &mut Vec<u8>, crc: u32, size: u32, name: &str) {
+    // Version needed, flags, stored method and modification time.
+    data.extend_from_slice(&20u16.to_le_bytes());
+    data.extend_from_slice(&[0; 6]);
+    data.extend_from_slice(&DOS_DATE.to_le_bytes());
+    data.extend_from_slice(&crc.to_le_bytes());
+    data.extend_from_slice(&size.to_le_bytes());
+    data.extend_from_slice(&size.to_le_bytes());
+    data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+}
+
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_u32(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn test_archive_layout() {
+        let zip = write_zip(&[("a.txt", b"hello"), ("dir/b.txt", b"world!")]);
+
+        // Local entries are followed by the central directory and its end record.
+        assert_eq!(read_u32(&zip, 0), 0x04034b50);
+        assert_eq!(&zip[30..35], b"a.txt");
+        assert_eq!(&zip[35..40], b"hello");
+        let end = zip.len() - 22;
+        assert_eq!(read_u32(&zip, end), 0x06054b50);
+        let directory_offset = read_u32(&zip, end + 16) as usize;
+        assert_eq!(read_u32(&zip, directory_offset), 0x02014b50);
+        assert_eq!(read_u32(&zip, directory_offset + 16), crc32(b"hello"));
+        assert_eq!(
+            directory_offset + read_u32(&zip, end + 12) as usize,
+            end,
+            "The central directory should end at the end record."
+        );
+    }
+}